@@ -89,13 +89,11 @@ impl LogBuilder for Builder {
     }
 
     fn allocate<T: Digital>(&self, tag: TagID<T>, width: usize) {
-        let name = self.path.join("$");
-        let signal = LogSignal::new(name, width);
-        let context_id: usize = tag.context;
-        let scope = &mut self.inner.borrow_mut().scopes[context_id];
-        let tag_id: usize = tag.id;
-        let tag = &mut scope.tags[tag_id];
-        tag.data.push(signal);
+        self.push_signal(tag, LogSignal::new(self.path.join("$"), width));
+    }
+
+    fn allocate_logic<T: Digital>(&self, tag: TagID<T>, width: usize) {
+        self.push_signal(tag, LogSignal::new_logic(self.path.join("$"), width));
     }
 
     fn namespace(&self, name: &str) -> Self {
@@ -123,4 +121,8 @@ impl Builder {
             time_in_fs: 0,
         }
     }
+    fn push_signal<T: Digital>(&self, tag: TagID<T>, signal: LogSignal<'static>) {
+        let scope = &mut self.inner.borrow_mut().scopes[tag.context];
+        scope.tags[tag.id].data.push(signal);
+    }
 }
@@ -17,6 +17,9 @@ pub(crate) struct TimedValue<T: Clone + PartialEq + Eq> {
 pub(crate) enum LogValues<'a> {
     Bool(Vec<TimedValue<bool>>),
     Bits(Vec<TimedValue<u128>>),
+    WideBits(Vec<TimedValue<Vec<u64>>>),
+    // 4-state values as (aval, bval) planes
+    Logic(Vec<TimedValue<(u128, u128)>>),
     #[serde(borrow)]
     Enum(Vec<TimedValue<&'a str>>),
 }
@@ -26,6 +29,8 @@ impl<'a> LogValues<'a> {
         match self {
             LogValues::Bool(v) => v.len(),
             LogValues::Bits(v) => v.len(),
+            LogValues::WideBits(v) => v.len(),
+            LogValues::Logic(v) => v.len(),
             LogValues::Enum(v) => v.len(),
         }
     }
@@ -48,11 +53,20 @@ impl<'a> LogSignal<'a> {
                 LogValues::Enum(vec![])
             } else if width == 1 {
                 LogValues::Bool(vec![])
-            } else {
+            } else if width <= 128 {
                 LogValues::Bits(vec![])
+            } else {
+                LogValues::WideBits(vec![])
             },
         }
     }
+    pub(crate) fn new_logic(name: String, width: usize) -> LogSignal<'a> {
+        LogSignal {
+            name,
+            width,
+            values: LogValues::Logic(vec![]),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -233,7 +247,7 @@ impl Logger<'static> {
         self.field_index = (self.field_index + 1) % len;
         ret
     }
-    fn build_scope_tree(&self) -> ScopeNode {
+    fn build_scope_tree(&self) -> ScopeNode<'_> {
         let mut root = ScopeNode::new_scope();
         for scope in &self.scopes {
             println!("scope name: {}", scope.name);
@@ -350,7 +364,45 @@ impl Logger<'static> {
                                         .writer()
                                         .write_all(&sbuf[0..(ptr.signal.width + 2)])?;
                                     writer.writer().write_all(&ptr.code_as_bytes)?;
-                                    writer.writer().write_all(&[b'\n'])?;
+                                    writer.writer().write_all(b"\n")?;
+                                    ptr.index += 1;
+                                    found_match = true;
+                                } else {
+                                    next_time = next_time.min(value.time_in_fs);
+                                }
+                                keep_running = true;
+                            }
+                        }
+                        LogValues::WideBits(ref values) => {
+                            if let Some(value) = values.get(ptr.index) {
+                                if value.time_in_fs == current_time {
+                                    let mut wbuf = vec![b'b'];
+                                    wide_bits_to_vcd(
+                                        value.value.as_deref(),
+                                        ptr.signal.width,
+                                        &mut wbuf,
+                                    );
+                                    wbuf.push(b' ');
+                                    writer.writer().write_all(&wbuf)?;
+                                    writer.writer().write_all(&ptr.code_as_bytes)?;
+                                    writer.writer().write_all(b"\n")?;
+                                    ptr.index += 1;
+                                    found_match = true;
+                                } else {
+                                    next_time = next_time.min(value.time_in_fs);
+                                }
+                                keep_running = true;
+                            }
+                        }
+                        LogValues::Logic(ref values) => {
+                            if let Some(value) = values.get(ptr.index) {
+                                if value.time_in_fs == current_time {
+                                    let mut lbuf = vec![b'b'];
+                                    logic_to_vcd(value.value, ptr.signal.width, &mut lbuf);
+                                    lbuf.push(b' ');
+                                    writer.writer().write_all(&lbuf)?;
+                                    writer.writer().write_all(&ptr.code_as_bytes)?;
+                                    writer.writer().write_all(b"\n")?;
                                     ptr.index += 1;
                                     found_match = true;
                                 } else {
@@ -421,6 +473,34 @@ fn bits_to_vcd(x: Option<u128>, width: usize, buffer: &mut [u8]) {
     }
 }
 
+fn wide_bits_to_vcd(x: Option<&[u64]>, width: usize, buffer: &mut Vec<u8>) {
+    if let Some(x) = x {
+        buffer.extend((0..width).rev().map(|i| {
+            if x[i / 64] & (1 << (i % 64)) != 0 {
+                b'1'
+            } else {
+                b'0'
+            }
+        }));
+    } else {
+        buffer.extend(std::iter::repeat_n(b'x', width));
+    }
+}
+
+fn logic_to_vcd(x: Option<(u128, u128)>, width: usize, buffer: &mut Vec<u8>) {
+    let (aval, bval) = x.unwrap_or((u128::MAX, u128::MAX));
+    buffer.extend(
+        (0..width)
+            .rev()
+            .map(|i| match (aval & (1 << i) != 0, bval & (1 << i) != 0) {
+                (false, false) => b'0',
+                (true, false) => b'1',
+                (false, true) => b'z',
+                (true, true) => b'x',
+            }),
+    );
+}
+
 impl LoggerImpl for Logger<'static> {
     fn write_bool<T: Digital>(&mut self, tag_id: TagID<T>, value: bool) {
         let time_in_fs = self.time_in_fs;
@@ -444,6 +524,28 @@ impl LoggerImpl for Logger<'static> {
             panic!("Wrong type");
         }
     }
+    fn write_wide_bits<T: Digital>(&mut self, tag_id: TagID<T>, value: &[u64]) {
+        let time_in_fs = self.time_in_fs;
+        if let LogValues::WideBits(ref mut values) = self.signal(tag_id).values {
+            values.push(TimedValue {
+                time_in_fs,
+                value: Some(value.to_vec()),
+            });
+        } else {
+            panic!("Wrong type");
+        }
+    }
+    fn write_logic<T: Digital>(&mut self, tag_id: TagID<T>, aval: u128, bval: u128) {
+        let time_in_fs = self.time_in_fs;
+        if let LogValues::Logic(ref mut values) = self.signal(tag_id).values {
+            values.push(TimedValue {
+                time_in_fs,
+                value: Some((aval, bval)),
+            });
+        } else {
+            panic!("Wrong type");
+        }
+    }
     fn write_string<T: Digital>(&mut self, tag_id: TagID<T>, val: &'static str) {
         let time_in_fs = self.time_in_fs;
         if let LogValues::Enum(ref mut values) = self.signal(tag_id).values {
@@ -470,6 +572,18 @@ impl LoggerImpl for Logger<'static> {
                     value: None,
                 });
             }
+            LogValues::WideBits(ref mut values) => {
+                values.push(TimedValue {
+                    time_in_fs,
+                    value: None,
+                });
+            }
+            LogValues::Logic(ref mut values) => {
+                values.push(TimedValue {
+                    time_in_fs,
+                    value: None,
+                });
+            }
             LogValues::Enum(ref mut values) => {
                 values.push(TimedValue {
                     time_in_fs,
@@ -3,6 +3,8 @@ use std::ops::AddAssign;
 
 use crate::bits::Bits;
 use crate::signed_bits::SignedBits;
+use crate::signed_wide_bits::SignedWideBits;
+use crate::wide_bits::WideBits;
 
 impl<const N: usize> Add<u128> for Bits<N> {
     type Output = Self;
@@ -73,6 +75,72 @@ impl<const N: usize> AddAssign<SignedBits<N>> for SignedBits<N> {
     }
 }
 
+impl<const N: usize> Add<WideBits<N>> for WideBits<N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.wrapping_add(rhs)
+    }
+}
+
+impl<const N: usize> Add<u128> for WideBits<N> {
+    type Output = Self;
+    fn add(self, rhs: u128) -> Self::Output {
+        self + WideBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> Add<WideBits<N>> for u128 {
+    type Output = WideBits<N>;
+    fn add(self, rhs: WideBits<N>) -> Self::Output {
+        WideBits::<N>::from(self) + rhs
+    }
+}
+
+impl<const N: usize> AddAssign<WideBits<N>> for WideBits<N> {
+    fn add_assign(&mut self, rhs: WideBits<N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> AddAssign<u128> for WideBits<N> {
+    fn add_assign(&mut self, rhs: u128) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> Add<SignedWideBits<N>> for SignedWideBits<N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        (self.as_unsigned() + rhs.as_unsigned()).as_signed()
+    }
+}
+
+impl<const N: usize> Add<i128> for SignedWideBits<N> {
+    type Output = Self;
+    fn add(self, rhs: i128) -> Self::Output {
+        self + SignedWideBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> Add<SignedWideBits<N>> for i128 {
+    type Output = SignedWideBits<N>;
+    fn add(self, rhs: SignedWideBits<N>) -> Self::Output {
+        SignedWideBits::<N>::from(self) + rhs
+    }
+}
+
+impl<const N: usize> AddAssign<SignedWideBits<N>> for SignedWideBits<N> {
+    fn add_assign(&mut self, rhs: SignedWideBits<N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> AddAssign<i128> for SignedWideBits<N> {
+    fn add_assign(&mut self, rhs: i128) {
+        *self = *self + rhs;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -161,4 +229,56 @@ mod test {
         x += 7;
         assert_eq!(x.0, 6);
     }
+
+    #[test]
+    fn test_wide_addition_matches_bits() {
+        for i in [
+            0,
+            1,
+            0xDEAD_BEEF,
+            u64::MAX as u128,
+            u128::MAX - 1,
+            u128::MAX,
+        ] {
+            for j in [0, 1, 0xCAFE, u64::MAX as u128, u128::MAX] {
+                let wide = WideBits::<128>::from(i) + WideBits::<128>::from(j);
+                let narrow = Bits::<128>::from(i) + Bits::<128>::from(j);
+                assert_eq!(wide, narrow.raw());
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_addition_carries_across_limbs() {
+        let x = WideBits::<256>::from(u128::MAX);
+        let y = x + 1;
+        assert!(y.get_bit(128));
+        assert_eq!(y.slice::<128>(0), 0);
+        let mut z = WideBits::<256>::mask();
+        z += 1;
+        assert_eq!(z, 0);
+        let z = 1 + WideBits::<200>::mask();
+        assert_eq!(z, 0);
+    }
+
+    #[test]
+    fn test_wide_signed_addition_matches_built_in_behavior_for_i8() {
+        for i in i8::MIN..i8::MAX {
+            for j in i8::MIN..i8::MAX {
+                let k = SignedWideBits::<8>::from(i as i128) + SignedWideBits::<8>::from(j as i128);
+                assert_eq!(k, i8::wrapping_add(i, j) as i128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_signed_addition() {
+        let mut x = SignedWideBits::<256>::from(-1);
+        x += 2;
+        assert_eq!(x, 1);
+        let x = SignedWideBits::<256>::from(i128::MIN) + SignedWideBits::<256>::from(i128::MIN);
+        assert!(x.is_negative());
+        assert_eq!(x.slice::<8>(128), 0xFF);
+        assert_eq!(x.slice::<128>(0), 0);
+    }
 }
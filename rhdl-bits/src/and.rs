@@ -1,6 +1,8 @@
 use std::ops::{BitAnd, BitAndAssign};
 
-use crate::{bits::Bits, signed_bits::SignedBits};
+use crate::{
+    bits::Bits, signed_bits::SignedBits, signed_wide_bits::SignedWideBits, wide_bits::WideBits,
+};
 
 impl<const N: usize> BitAnd<Bits<N>> for u128 {
     type Output = Bits<N>;
@@ -42,6 +44,72 @@ impl<const N: usize> BitAndAssign<i128> for SignedBits<N> {
     }
 }
 
+impl<const N: usize> BitAnd<WideBits<N>> for WideBits<N> {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.map2(rhs, |a, b| a & b)
+    }
+}
+
+impl<const N: usize> BitAnd<WideBits<N>> for u128 {
+    type Output = WideBits<N>;
+    fn bitand(self, rhs: WideBits<N>) -> Self::Output {
+        WideBits::<N>::from(self) & rhs
+    }
+}
+
+impl<const N: usize> BitAnd<u128> for WideBits<N> {
+    type Output = Self;
+    fn bitand(self, rhs: u128) -> Self::Output {
+        self & WideBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> BitAndAssign<WideBits<N>> for WideBits<N> {
+    fn bitand_assign(&mut self, rhs: WideBits<N>) {
+        *self = *self & rhs;
+    }
+}
+
+impl<const N: usize> BitAndAssign<u128> for WideBits<N> {
+    fn bitand_assign(&mut self, rhs: u128) {
+        *self = *self & rhs;
+    }
+}
+
+impl<const N: usize> BitAnd<SignedWideBits<N>> for SignedWideBits<N> {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        (self.as_unsigned() & rhs.as_unsigned()).as_signed()
+    }
+}
+
+impl<const N: usize> BitAnd<SignedWideBits<N>> for i128 {
+    type Output = SignedWideBits<N>;
+    fn bitand(self, rhs: SignedWideBits<N>) -> Self::Output {
+        SignedWideBits::<N>::from(self) & rhs
+    }
+}
+
+impl<const N: usize> BitAnd<i128> for SignedWideBits<N> {
+    type Output = Self;
+    fn bitand(self, rhs: i128) -> Self::Output {
+        self & SignedWideBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> BitAndAssign<SignedWideBits<N>> for SignedWideBits<N> {
+    fn bitand_assign(&mut self, rhs: SignedWideBits<N>) {
+        *self = *self & rhs;
+    }
+}
+
+impl<const N: usize> BitAndAssign<i128> for SignedWideBits<N> {
+    fn bitand_assign(&mut self, rhs: i128) {
+        *self = *self & rhs;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -143,4 +211,39 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_and_wide_bits() {
+        let a = 0xDEAD_BEEF_0000_FFFF_1234_5678_9ABC_DEF0_u128;
+        let b = 0x0F0F_0F0F_F0F0_F0F0_FFFF_0000_FFFF_0000_u128;
+        let wide = WideBits::<128>::from(a) & WideBits::<128>::from(b);
+        assert_eq!(wide, (Bits::<128>::from(a) & Bits::<128>::from(b)).raw());
+        assert_eq!(WideBits::<128>::from(a) & b, wide);
+        assert_eq!(a & WideBits::<128>::from(b), wide);
+        let mut x = WideBits::<256>::mask();
+        x.set_bit(200, false);
+        let mut y = WideBits::<256>::default();
+        y.set_bit(200, true);
+        y.set_bit(3, true);
+        let mut z = x;
+        z &= y;
+        assert_eq!(z, x & y);
+        for bit in [3, 200] {
+            assert_eq!(z.get_bit(bit), x.get_bit(bit) & y.get_bit(bit));
+        }
+    }
+
+    #[test]
+    fn test_and_signed_wide_bits_i8() {
+        for i in i8::MIN..i8::MAX {
+            for j in i8::MIN..i8::MAX {
+                let x = SignedWideBits::<8>::from(i as i128);
+                let y = SignedWideBits::<8>::from(j as i128);
+                assert_eq!(x & y, (i & j) as i128);
+                let mut z = x;
+                z &= j as i128;
+                assert_eq!(z, (i & j) as i128);
+            }
+        }
+    }
 }
@@ -67,7 +67,7 @@ seq!(N in 1..=128 {
 /// const VALUE : Bits<8> = bits(0b1010_1010);
 /// ```
 pub const fn bits<const N: usize>(value: u128) -> Bits<N> {
-    assert!(N <= 128);
+    const { assert!(N <= 128) }
     assert!(value <= Bits::<N>::mask().0);
     Bits(value)
 }
@@ -82,6 +82,7 @@ impl<const N: usize> Bits<N> {
     /// assert_eq!(bits, 0xFF);
     /// ```
     pub const fn mask() -> Self {
+        const { assert!(N <= 128, "Bits<N> only supports N <= 128") }
         // Do not compute this as you will potentially
         // cause overflow.
         if N < 128 {
@@ -161,6 +162,94 @@ impl<const N: usize> Bits<N> {
     pub fn slice<const M: usize>(&self, start: usize) -> Bits<M> {
         Bits((self.0 >> start) & Bits::<M>::mask().0)
     }
+    /// Extracts `M` bits starting at bit `START` from the [Bits]
+    /// value.  Unlike [Bits::slice], the start is a constant, and
+    /// a range that does not fit inside the `N` bits of the value
+    /// is a compile time error.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let bits: Bits<8> = 0b1101_1010.into();
+    /// assert_eq!(bits.slice_at::<4, 4>(), 0b1101);
+    /// ```
+    /// Overrunning the end of the value will fail to build:
+    /// ```compile_fail
+    /// # use rhdl_bits::Bits;
+    /// let bits: Bits<8> = 0b1101_1010.into();
+    /// let _ = bits.slice_at::<6, 4>();
+    /// ```
+    pub fn slice_at<const START: usize, const M: usize>(&self) -> Bits<M> {
+        const { assert!(START + M <= N, "slice_at range overruns the value") }
+        Bits((self.0 >> START) & Bits::<M>::mask().0)
+    }
+    /// Convert the [Bits] value to a different width.  If `M`
+    /// is smaller than `N`, the upper bits are discarded.  If
+    /// `M` is larger than `N`, the value is zero extended.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<8> = 0b1101_1010.into();
+    /// assert_eq!(x.resize::<4>(), 0b1010);
+    /// assert_eq!(x.resize::<12>(), 0b1101_1010);
+    /// ```
+    pub fn resize<const M: usize>(self) -> Bits<M> {
+        const { assert!(M <= 128) }
+        Bits(self.0 & Bits::<M>::mask().0)
+    }
+    /// Zero extend the [Bits] value to a wider value.  An
+    /// `M` smaller than `N` is a compile time error.  Use
+    /// [Bits::resize] if you want to truncate the value.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<4> = 0b1010.into();
+    /// let y: Bits<8> = x.zero_extend();
+    /// assert_eq!(y, 0b0000_1010);
+    /// ```
+    /// Extending to a narrower value will fail to build:
+    /// ```compile_fail
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<8> = 0b1010.into();
+    /// let y: Bits<4> = x.zero_extend();
+    /// ```
+    pub fn zero_extend<const M: usize>(self) -> Bits<M> {
+        const { assert!(M >= N) }
+        self.resize()
+    }
+    /// Concatenate two [Bits] values.  The `self` value is
+    /// placed in the most significant bits of the result, and
+    /// the `lsbs` argument in the least significant bits, just
+    /// like `{self, lsbs}` in Verilog.  The output width `P`
+    /// must be equal to `N + M`.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<4> = 0b1101.into();
+    /// let y: Bits<8> = 0b0000_0011.into();
+    /// let z: Bits<12> = x.concat(y);
+    /// assert_eq!(z, 0b1101_0000_0011);
+    /// ```
+    pub fn concat<const M: usize, const P: usize>(self, lsbs: Bits<M>) -> Bits<P> {
+        const { assert!(P == N + M) }
+        const { assert!(P <= 128) }
+        Bits(self.0.checked_shl(M as u32).unwrap_or(0) | lsbs.0)
+    }
+    /// Replicate the [Bits] value to fill a wider value, like
+    /// `{R{self}}` in Verilog.  The output width `M` must be a
+    /// multiple of `N`.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<2> = 0b10.into();
+    /// let y: Bits<8> = x.replicate();
+    /// assert_eq!(y, 0b1010_1010);
+    /// ```
+    /// Replicating to a width that is not a multiple of `N` will fail to build:
+    /// ```compile_fail
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<3> = 0b101.into();
+    /// let y: Bits<8> = x.replicate();
+    /// ```
+    pub fn replicate<const M: usize>(self) -> Bits<M> {
+        const { assert!(M <= 128) }
+        const { assert!(M.is_multiple_of(N)) }
+        Bits((0..M / N).fold(0, |acc, ndx| acc | (self.0 << (ndx * N))))
+    }
     /// Reinterpret the [Bits] value as a [SignedBits] value.
     pub fn as_signed(self) -> SignedBits<N> {
         // Need to a sign extension here.
@@ -199,7 +288,7 @@ impl<const N: usize> Default for Bits<N> {
 /// is larger than the [Bits] value can hold.
 impl<const N: usize> From<u128> for Bits<N> {
     fn from(value: u128) -> Self {
-        assert!(N <= 128);
+        const { assert!(N <= 128) }
         assert!(value <= Self::mask().0);
         Self(value)
     }
@@ -358,6 +447,29 @@ mod tests {
         assert_eq!(result.0, 0b11);
     }
 
+    #[test]
+    fn test_resize_and_zero_extend() {
+        let bits: Bits<8> = 0b1101_1010.into();
+        assert_eq!(bits.resize::<4>().0, 0b1010);
+        assert_eq!(bits.resize::<8>(), bits);
+        assert_eq!(bits.resize::<128>().0, 0b1101_1010);
+        assert_eq!(bits.zero_extend::<16>().0, 0b1101_1010);
+    }
+
+    #[test]
+    fn test_concat_and_replicate() {
+        let x: Bits<4> = 0b1101.into();
+        let y: Bits<4> = 0b0110.into();
+        let z: Bits<8> = x.concat(y);
+        assert_eq!(z.0, 0b1101_0110);
+        let z: Bits<128> = Bits::<64>::mask().concat(Bits::<64>::from(0));
+        assert_eq!(z.0, (u64::MAX as u128) << 64);
+        let r: Bits<12> = x.replicate();
+        assert_eq!(r.0, 0b1101_1101_1101);
+        let r: Bits<128> = Bits::<1>::from(1).replicate();
+        assert_eq!(r.0, u128::MAX);
+    }
+
     #[test]
     fn test_round_trip_unsigned_signed() {
         let bits: Bits<8> = 0b1101_1010.into();
@@ -0,0 +1,206 @@
+use crate::bits::Bits;
+use crate::parse::ParseBitsError;
+use crate::signed_bits::SignedBits;
+
+// Conversions to and from byte arrays.  The array must have exactly
+// enough bytes to hold the value (`M == N.div_ceil(8)`), which is
+// checked at compile time.  When `N` is not a multiple of 8, the unused
+// bits of the most significant byte are zero filled (or sign extended
+// for SignedBits), and must be so when converting back.
+
+fn to_le_bytes<const M: usize>(value: u128) -> [u8; M] {
+    let mut bytes = [0; M];
+    bytes.copy_from_slice(&value.to_le_bytes()[..M]);
+    bytes
+}
+
+fn from_le_bytes<const M: usize>(bytes: [u8; M]) -> u128 {
+    let mut buffer = [0; 16];
+    buffer[..M].copy_from_slice(&bytes);
+    u128::from_le_bytes(buffer)
+}
+
+fn reversed<const M: usize>(mut bytes: [u8; M]) -> [u8; M] {
+    bytes.reverse();
+    bytes
+}
+
+impl<const N: usize> Bits<N> {
+    /// Convert the value to a little endian byte array.  The
+    /// array must be `N.div_ceil(8)` bytes long, and the unused bits
+    /// of the last byte are zero.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x = Bits::<12>::from(0xABC);
+    /// assert_eq!(x.to_le_bytes(), [0xBC, 0x0A]);
+    /// ```
+    /// Using the wrong size array is a compile time error:
+    /// ```compile_fail
+    /// # use rhdl_bits::Bits;
+    /// let x: [u8; 1] = Bits::<12>::from(0xABC).to_le_bytes();
+    /// ```
+    pub fn to_le_bytes<const M: usize>(self) -> [u8; M] {
+        const {
+            assert!(
+                M == N.div_ceil(8),
+                "byte array must be N.div_ceil(8) bytes long"
+            )
+        }
+        to_le_bytes(self.0)
+    }
+    /// Convert the value to a big endian byte array.  The
+    /// array must be `N.div_ceil(8)` bytes long, and the unused bits
+    /// of the first byte are zero.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x = Bits::<12>::from(0xABC);
+    /// assert_eq!(x.to_be_bytes(), [0x0A, 0xBC]);
+    /// ```
+    pub fn to_be_bytes<const M: usize>(self) -> [u8; M] {
+        reversed(self.to_le_bytes())
+    }
+    /// Build a value from a little endian byte array that is
+    /// `N.div_ceil(8)` bytes long.  If any of the unused bits of the
+    /// last byte are set, an [Overflow](ParseBitsError::Overflow)
+    /// error is returned.
+    /// ```
+    /// # use rhdl_bits::{Bits, ParseBitsError};
+    /// assert_eq!(Bits::<12>::from_le_bytes([0xBC, 0x0A]), Ok(Bits::from(0xABC)));
+    /// assert_eq!(
+    ///     Bits::<12>::from_le_bytes([0xBC, 0x1A]),
+    ///     Err(ParseBitsError::Overflow { width: 12 })
+    /// );
+    /// ```
+    pub fn from_le_bytes<const M: usize>(bytes: [u8; M]) -> Result<Self, ParseBitsError> {
+        const {
+            assert!(
+                M == N.div_ceil(8),
+                "byte array must be N.div_ceil(8) bytes long"
+            )
+        }
+        let value = from_le_bytes(bytes);
+        if value & !Self::mask().0 != 0 {
+            return Err(ParseBitsError::Overflow { width: N });
+        }
+        Ok(Self(value))
+    }
+    /// Build a value from a big endian byte array that is
+    /// `N.div_ceil(8)` bytes long.  If any of the unused bits of the
+    /// first byte are set, an [Overflow](ParseBitsError::Overflow)
+    /// error is returned.
+    pub fn from_be_bytes<const M: usize>(bytes: [u8; M]) -> Result<Self, ParseBitsError> {
+        Self::from_le_bytes(reversed(bytes))
+    }
+}
+
+impl<const N: usize> SignedBits<N> {
+    /// Convert the value to a little endian byte array.  The
+    /// array must be `N.div_ceil(8)` bytes long, and the unused bits
+    /// of the last byte are sign extended.
+    /// ```
+    /// # use rhdl_bits::SignedBits;
+    /// let x = SignedBits::<12>::from(-2);
+    /// assert_eq!(x.to_le_bytes(), [0xFE, 0xFF]);
+    /// ```
+    pub fn to_le_bytes<const M: usize>(self) -> [u8; M] {
+        const {
+            assert!(
+                M == N.div_ceil(8),
+                "byte array must be N.div_ceil(8) bytes long"
+            )
+        }
+        to_le_bytes(self.0 as u128)
+    }
+    /// Convert the value to a big endian byte array.  The
+    /// array must be `N.div_ceil(8)` bytes long, and the unused bits
+    /// of the first byte are sign extended.
+    pub fn to_be_bytes<const M: usize>(self) -> [u8; M] {
+        reversed(self.to_le_bytes())
+    }
+    /// Build a value from a little endian byte array that is
+    /// `N.div_ceil(8)` bytes long.  If the unused bits of the last
+    /// byte are not a sign extension of bit `N - 1`, an
+    /// [Overflow](ParseBitsError::Overflow) error is returned.
+    /// ```
+    /// # use rhdl_bits::{SignedBits, ParseBitsError};
+    /// assert_eq!(SignedBits::<12>::from_le_bytes([0xFE, 0xFF]), Ok(SignedBits::from(-2)));
+    /// assert_eq!(
+    ///     SignedBits::<12>::from_le_bytes([0xFE, 0x0F]),
+    ///     Err(ParseBitsError::Overflow { width: 12 })
+    /// );
+    /// ```
+    pub fn from_le_bytes<const M: usize>(bytes: [u8; M]) -> Result<Self, ParseBitsError> {
+        const {
+            assert!(
+                M == N.div_ceil(8),
+                "byte array must be N.div_ceil(8) bytes long"
+            )
+        }
+        // Sign extend from the top of the byte array, and then
+        // check that the value fits in N bits.
+        let shift = 128 - 8 * M;
+        let value = ((from_le_bytes(bytes) << shift) as i128) >> shift;
+        if value > Self::max_value() || value < Self::min_value() {
+            return Err(ParseBitsError::Overflow { width: N });
+        }
+        Ok(Self(value))
+    }
+    /// Build a value from a big endian byte array that is
+    /// `N.div_ceil(8)` bytes long.  If the unused bits of the first
+    /// byte are not a sign extension of bit `N - 1`, an
+    /// [Overflow](ParseBitsError::Overflow) error is returned.
+    pub fn from_be_bytes<const M: usize>(bytes: [u8; M]) -> Result<Self, ParseBitsError> {
+        Self::from_le_bytes(reversed(bytes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_unsigned() {
+        for i in 0..(1 << 12) {
+            let x = Bits::<12>(i);
+            let le: [u8; 2] = x.to_le_bytes();
+            let be: [u8; 2] = x.to_be_bytes();
+            assert_eq!(le, (i as u16).to_le_bytes());
+            assert_eq!(be, (i as u16).to_be_bytes());
+            assert_eq!(Bits::<12>::from_le_bytes(le), Ok(x));
+            assert_eq!(Bits::<12>::from_be_bytes(be), Ok(x));
+        }
+        let x = Bits::<128>::mask();
+        assert_eq!(x.to_le_bytes(), [0xFF; 16]);
+        assert_eq!(Bits::<128>::from_be_bytes([0xFF; 16]), Ok(x));
+        assert_eq!(Bits::<1>(1).to_le_bytes(), [1]);
+    }
+
+    #[test]
+    fn test_round_trip_signed() {
+        for i in -(1 << 11)..(1 << 11) {
+            let x = SignedBits::<12>(i);
+            let le: [u8; 2] = x.to_le_bytes();
+            let be: [u8; 2] = x.to_be_bytes();
+            assert_eq!(le, (i as i16).to_le_bytes());
+            assert_eq!(be, (i as i16).to_be_bytes());
+            assert_eq!(SignedBits::<12>::from_le_bytes(le), Ok(x));
+            assert_eq!(SignedBits::<12>::from_be_bytes(be), Ok(x));
+        }
+        let x = SignedBits::<128>::min_value();
+        assert_eq!(SignedBits::<128>(x).to_le_bytes(), x.to_le_bytes());
+        assert_eq!(
+            SignedBits::<128>::from_le_bytes(x.to_le_bytes()),
+            Ok(SignedBits(x))
+        );
+    }
+
+    #[test]
+    fn test_unused_bits_are_checked() {
+        for top in 0..=255_u8 {
+            let unsigned = Bits::<12>::from_be_bytes([top, 0]);
+            assert_eq!(unsigned.is_ok(), top < 0x10);
+            let signed = SignedBits::<12>::from_be_bytes([top, 0]);
+            assert_eq!(signed.is_ok(), !(0x08..0xF8).contains(&top));
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::ops::Div;
+use std::ops::DivAssign;
+
+use crate::bits::Bits;
+use crate::signed_bits::SignedBits;
+
+impl<const N: usize> Div<u128> for Bits<N> {
+    type Output = Self;
+    fn div(self, rhs: u128) -> Self::Output {
+        self / Bits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> Div<Bits<N>> for u128 {
+    type Output = Bits<N>;
+    fn div(self, rhs: Bits<N>) -> Self::Output {
+        Bits::<N>::from(self) / rhs
+    }
+}
+
+impl<const N: usize> Div<Bits<N>> for Bits<N> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        // A hardware divider does not trap.  Dividing by zero
+        // produces a quotient with all bits set.
+        Self(self.0.checked_div(rhs.0).unwrap_or(Self::mask().0))
+    }
+}
+
+impl<const N: usize> DivAssign<Bits<N>> for Bits<N> {
+    fn div_assign(&mut self, rhs: Bits<N>) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const N: usize> DivAssign<u128> for Bits<N> {
+    fn div_assign(&mut self, rhs: u128) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const N: usize> Div<i128> for SignedBits<N> {
+    type Output = Self;
+    fn div(self, rhs: i128) -> Self::Output {
+        self / SignedBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> Div<SignedBits<N>> for i128 {
+    type Output = SignedBits<N>;
+    fn div(self, rhs: SignedBits<N>) -> Self::Output {
+        SignedBits::<N>::from(self) / rhs
+    }
+}
+
+impl<const N: usize> Div<SignedBits<N>> for SignedBits<N> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        // Dividing by zero produces -1 (all bits set), and
+        // the one overflowing case (MIN / -1) wraps to MIN.
+        if rhs.0 == 0 {
+            Self(-1)
+        } else {
+            Bits::<N>(i128::wrapping_div(self.0, rhs.0) as u128 & Bits::<N>::mask().0).as_signed()
+        }
+    }
+}
+
+impl<const N: usize> DivAssign<i128> for SignedBits<N> {
+    fn div_assign(&mut self, rhs: i128) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const N: usize> DivAssign<SignedBits<N>> for SignedBits<N> {
+    fn div_assign(&mut self, rhs: SignedBits<N>) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_div_bits() {
+        let bits: Bits<8> = 0b1101_1010.into();
+        assert_eq!(bits / 3, 218 / 3);
+        assert_eq!(250 / bits, 1);
+        assert_eq!(bits / bits, 1);
+        let mut bits: Bits<54> = 0b1101_1010.into();
+        bits /= 2;
+        assert_eq!(bits.0, 109);
+    }
+
+    #[test]
+    fn test_unsigned_div_matches_built_in_behavior_for_u8() {
+        for i in 0..=u8::MAX {
+            for j in 1..=u8::MAX {
+                let k = Bits::<8>::from(i as u128) / Bits::<8>::from(j as u128);
+                assert_eq!(k.0, (i / j) as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsigned_div_by_zero_is_all_ones() {
+        let bits: Bits<8> = 0b1101_1010.into();
+        assert_eq!(bits / 0, 0xFF);
+        assert_eq!(Bits::<128>::from(5) / 0, u128::MAX);
+    }
+
+    #[test]
+    fn test_signed_div_matches_built_in_behavior_for_i8() {
+        for i in i8::MIN..=i8::MAX {
+            for j in i8::MIN..=i8::MAX {
+                if j == 0 {
+                    continue;
+                }
+                let i_as_signed = SignedBits::<8>::from(i as i128);
+                let j_as_signed = SignedBits::<8>::from(j as i128);
+                let k_as_signed = i_as_signed / j_as_signed;
+                let k = i8::wrapping_div(i, j);
+                assert_eq!(k_as_signed.0, k as i128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_div_by_zero_is_minus_one() {
+        for i in i8::MIN..=i8::MAX {
+            let x = SignedBits::<8>::from(i as i128);
+            assert_eq!(x / 0, -1);
+        }
+        assert_eq!(SignedBits::<128>::from(i128::MIN) / 0, -1);
+    }
+
+    #[test]
+    fn test_signed_div_overflow_wraps() {
+        let x = SignedBits::<128>::from(i128::MIN);
+        assert_eq!(x / -1, i128::MIN);
+        let mut x = SignedBits::<16>::from(i16::MIN as i128);
+        x /= -1;
+        assert_eq!(x, i16::MIN as i128);
+    }
+}
@@ -0,0 +1,416 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+use crate::signed_bits::SignedBits;
+
+/// The rounding mode to use when discarding fractional bits
+/// from a [Fixed] or [UFixed](crate::UFixed) value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Rounding {
+    /// Discard the extra bits.  This rounds towards negative
+    /// infinity, and is free in hardware.
+    #[default]
+    Truncate,
+    /// Round to the nearest value, with ties rounded up
+    /// (towards positive infinity).  This costs an adder.
+    RoundHalfUp,
+    /// Round to the nearest value, with ties rounded to the
+    /// nearest even value.  Also known as banker's rounding,
+    /// this avoids the DC bias of [Rounding::RoundHalfUp].
+    Convergent,
+}
+
+impl Rounding {
+    // Decide if a value should be rounded up, given the LSB of
+    // the truncated value, and the discarded bits compared with
+    // one half of an LSB.
+    pub(crate) fn round_up(self, lsb: bool, discarded: u128, half: u128) -> bool {
+        match self {
+            Rounding::Truncate => false,
+            Rounding::RoundHalfUp => discarded >= half,
+            Rounding::Convergent => discarded > half || (discarded == half && lsb),
+        }
+    }
+}
+
+/// The [Fixed] type is a signed fixed point value with `I` integer
+/// bits (including the sign bit) and `F` fractional bits.  It is stored
+/// as a 2's complement value `I + F` bits wide (which must be no more
+/// than 128 bits), and represents that value divided by `2^F`.  So a
+/// `Fixed<4, 4>` covers the range `-8.0` to `7.9375` in steps of
+/// `0.0625`.
+///
+/// As with [SignedBits], the `+` and `-` operators wrap on overflow.  To
+/// get a result that cannot overflow, use the `widening_*` methods, which
+/// produce a result with enough integer and fractional bits to hold the
+/// exact answer.  Use [Fixed::resize] and [Fixed::saturate] to get back
+/// to a narrower format.
+/// ```
+/// # use rhdl_bits::{Fixed, Rounding};
+/// let x = Fixed::<4, 4>::from_f64(1.5);
+/// let y = Fixed::<4, 4>::from_f64(-2.25);
+/// let z: Fixed<8, 8> = x.widening_mul(y);
+/// assert_eq!(z.to_f64(), -3.375);
+/// let z: Fixed<4, 2> = z.saturate(Rounding::RoundHalfUp);
+/// assert_eq!(z.to_f64(), -3.25);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct Fixed<const I: usize, const F: usize>(pub(crate) i128);
+
+impl<const I: usize, const F: usize> Fixed<I, F> {
+    /// The total number of bits in the [Fixed] value.
+    pub const WIDTH: usize = I + F;
+    /// Return the largest value that can be represented
+    /// by this [Fixed] type.
+    pub fn max_value() -> Self {
+        Self(i128::MAX >> (128 - Self::WIDTH))
+    }
+    /// Return the smallest (most negative) value that can be
+    /// represented by this [Fixed] type.
+    pub fn min_value() -> Self {
+        Self(i128::MIN >> (128 - Self::WIDTH))
+    }
+    /// Build a [Fixed] value from the raw (scaled) integer that
+    /// backs it.  Panics if the value does not fit in `I + F` bits.
+    /// ```
+    /// # use rhdl_bits::Fixed;
+    /// let x = Fixed::<4, 4>::from_raw(-24);
+    /// assert_eq!(x.to_f64(), -1.5);
+    /// ```
+    pub fn from_raw(raw: i128) -> Self {
+        const { assert!(I >= 1) }
+        const { assert!(Self::WIDTH <= 128) }
+        assert!(raw >= Self::min_value().0 && raw <= Self::max_value().0);
+        Self(raw)
+    }
+    /// Extract the raw (scaled) integer that backs this value.
+    pub fn raw(self) -> i128 {
+        self.0
+    }
+    /// Build a [Fixed] value from the [SignedBits] holding its
+    /// bits.  `N` must be equal to `I + F`.
+    pub fn from_bits<const N: usize>(bits: SignedBits<N>) -> Self {
+        const { assert!(N == Self::WIDTH) }
+        Self::from_raw(bits.raw())
+    }
+    /// Return the bits of this value as a [SignedBits] value.
+    /// `N` must be equal to `I + F`.
+    /// ```
+    /// # use rhdl_bits::{Fixed, SignedBits};
+    /// let x = Fixed::<4, 4>::from_f64(-0.5);
+    /// let y: SignedBits<8> = x.to_bits();
+    /// assert_eq!(y, -8);
+    /// ```
+    pub fn to_bits<const N: usize>(self) -> SignedBits<N> {
+        const { assert!(N == Self::WIDTH) }
+        SignedBits::from(self.0)
+    }
+    /// Convert an [f64] to the nearest [Fixed] value.  This is
+    /// meant for test benches.  Panics if the value is out of range.
+    pub fn from_f64(value: f64) -> Self {
+        let scaled = (value * 2.0_f64.powi(F as i32)).round();
+        assert!(scaled.is_finite());
+        assert!(scaled >= Self::min_value().0 as f64 && scaled <= Self::max_value().0 as f64);
+        Self::from_raw(scaled as i128)
+    }
+    /// Convert the [Fixed] value to an [f64].  Note that values
+    /// wider than 53 bits may lose precision.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / 2.0_f64.powi(F as i32)
+    }
+    // Convert to a new format without checking the range.  Returns
+    // the rescaled value (which may not fit in IO + FO bits) and a flag
+    // that is set if the rescaling overflowed the i128.
+    fn rescale<const FO: usize>(self, rounding: Rounding) -> (i128, bool) {
+        if FO >= F {
+            let shift = FO - F;
+            let shifted = self.0.wrapping_shl(shift as u32);
+            (shifted, shift >= 128 || (shifted >> shift) != self.0)
+        } else {
+            let shift = F - FO;
+            let truncated = self.0 >> shift;
+            let discarded = self.0 as u128 & ((1_u128 << shift) - 1);
+            let half = 1_u128 << (shift - 1);
+            if rounding.round_up(truncated & 1 == 1, discarded, half) {
+                (truncated + 1, false)
+            } else {
+                (truncated, false)
+            }
+        }
+    }
+    /// Convert to a different format, rounding off any discarded
+    /// fractional bits, and wrapping (discarding) any integer bits
+    /// that do not fit.  This is what a hardware assignment to a
+    /// narrower register does.
+    /// ```
+    /// # use rhdl_bits::{Fixed, Rounding};
+    /// let x = Fixed::<8, 4>::from_f64(9.75);
+    /// let y: Fixed<4, 2> = x.resize(Rounding::Truncate);
+    /// assert_eq!(y.to_f64(), -6.25); // Wrapped!
+    /// ```
+    pub fn resize<const IO: usize, const FO: usize>(self, rounding: Rounding) -> Fixed<IO, FO> {
+        const { assert!(IO >= 1) }
+        const { assert!(IO + FO <= 128) }
+        let (value, _) = self.rescale::<FO>(rounding);
+        let shift = 128 - (IO + FO);
+        Fixed((value << shift) >> shift)
+    }
+    /// Convert to a different format, rounding off any discarded
+    /// fractional bits, and clamping the result to the range of
+    /// the new format.
+    /// ```
+    /// # use rhdl_bits::{Fixed, Rounding};
+    /// let x = Fixed::<8, 4>::from_f64(9.75);
+    /// let y: Fixed<4, 2> = x.saturate(Rounding::Truncate);
+    /// assert_eq!(y.to_f64(), 7.75);
+    /// ```
+    pub fn saturate<const IO: usize, const FO: usize>(self, rounding: Rounding) -> Fixed<IO, FO> {
+        const { assert!(IO >= 1) }
+        const { assert!(IO + FO <= 128) }
+        let (value, overflow) = self.rescale::<FO>(rounding);
+        Fixed::<IO, FO>::clamped(value, overflow, self.0 >= 0)
+    }
+    /// Add two [Fixed] values, producing a result that is wide
+    /// enough to hold the exact sum.  The output must have
+    /// `IO = max(I, I2) + 1` and `FO = max(F, F2)`.
+    /// ```
+    /// # use rhdl_bits::Fixed;
+    /// let x = Fixed::<4, 4>::from_f64(7.5);
+    /// let y = Fixed::<2, 6>::from_f64(1.25);
+    /// let z: Fixed<5, 6> = x.widening_add(y);
+    /// assert_eq!(z.to_f64(), 8.75);
+    /// ```
+    pub fn widening_add<const I2: usize, const F2: usize, const IO: usize, const FO: usize>(
+        self,
+        rhs: Fixed<I2, F2>,
+    ) -> Fixed<IO, FO> {
+        const { assert!(IO == (if I > I2 { I } else { I2 }) + 1) }
+        const { assert!(FO == if F > F2 { F } else { F2 }) }
+        let lhs: Fixed<IO, FO> = self.resize(Rounding::Truncate);
+        let rhs: Fixed<IO, FO> = rhs.resize(Rounding::Truncate);
+        Fixed(lhs.0 + rhs.0)
+    }
+    /// Subtract two [Fixed] values, producing a result that is wide
+    /// enough to hold the exact difference.  The output must have
+    /// `IO = max(I, I2) + 1` and `FO = max(F, F2)`.
+    pub fn widening_sub<const I2: usize, const F2: usize, const IO: usize, const FO: usize>(
+        self,
+        rhs: Fixed<I2, F2>,
+    ) -> Fixed<IO, FO> {
+        const { assert!(IO == (if I > I2 { I } else { I2 }) + 1) }
+        const { assert!(FO == if F > F2 { F } else { F2 }) }
+        let lhs: Fixed<IO, FO> = self.resize(Rounding::Truncate);
+        let rhs: Fixed<IO, FO> = rhs.resize(Rounding::Truncate);
+        Fixed(lhs.0 - rhs.0)
+    }
+    /// Multiply two [Fixed] values, producing a result that is
+    /// wide enough to hold the exact product.  The output must
+    /// have `IO = I + I2` and `FO = F + F2`.
+    pub fn widening_mul<const I2: usize, const F2: usize, const IO: usize, const FO: usize>(
+        self,
+        rhs: Fixed<I2, F2>,
+    ) -> Fixed<IO, FO> {
+        const { assert!(IO == I + I2) }
+        const { assert!(FO == F + F2) }
+        const { assert!(IO + FO <= 128) }
+        Fixed(self.0 * rhs.0)
+    }
+    /// Add two [Fixed] values, clamping the result to the range
+    /// of the type.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        let (sum, overflow) = self.0.overflowing_add(rhs.0);
+        Self::clamped(sum, overflow, rhs.0 >= 0)
+    }
+    /// Subtract two [Fixed] values, clamping the result to the
+    /// range of the type.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        let (diff, overflow) = self.0.overflowing_sub(rhs.0);
+        Self::clamped(diff, overflow, rhs.0 < 0)
+    }
+    // Clamp a value that may have overflowed the range of the type
+    // (or the i128 that holds it).  `positive` is the direction in
+    // which the overflow could have happened.
+    fn clamped(value: i128, overflow: bool, positive: bool) -> Self {
+        if overflow || value > Self::max_value().0 || value < Self::min_value().0 {
+            if positive {
+                Self::max_value()
+            } else {
+                Self::min_value()
+            }
+        } else {
+            Self(value)
+        }
+    }
+    // Wrap a value back into the range of the type.
+    fn wrapped(value: i128) -> Self {
+        let shift = 128 - Self::WIDTH;
+        Self(value.wrapping_shl(shift as u32) >> shift)
+    }
+}
+
+impl<const I: usize, const F: usize> Add for Fixed<I, F> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::wrapped(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl<const I: usize, const F: usize> AddAssign for Fixed<I, F> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const I: usize, const F: usize> Sub for Fixed<I, F> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::wrapped(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl<const I: usize, const F: usize> SubAssign for Fixed<I, F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const I: usize, const F: usize> Neg for Fixed<I, F> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::wrapped(self.0.wrapping_neg())
+    }
+}
+
+impl<const I: usize, const F: usize> Debug for Fixed<I, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fixed::<{}, {}>({})", I, F, self.to_f64())
+    }
+}
+
+impl<const I: usize, const F: usize> Display for Fixed<I, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.to_f64(), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn all_values<const I: usize, const F: usize>() -> impl Iterator<Item = Fixed<I, F>> {
+        (Fixed::<I, F>::min_value().0..=Fixed::<I, F>::max_value().0).map(Fixed::from_raw)
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(Fixed::<4, 4>::max_value().to_f64(), 7.9375);
+        assert_eq!(Fixed::<4, 4>::min_value().to_f64(), -8.0);
+        assert_eq!(Fixed::<64, 64>::max_value().0, i128::MAX);
+        assert_eq!(Fixed::<64, 64>::min_value().0, i128::MIN);
+        assert_eq!(Fixed::<1, 0>::min_value().0, -1);
+        assert_eq!(Fixed::<1, 0>::max_value().0, 0);
+    }
+
+    #[test]
+    fn test_f64_round_trip() {
+        for x in all_values::<4, 4>() {
+            assert_eq!(Fixed::<4, 4>::from_f64(x.to_f64()), x);
+        }
+        assert_eq!(Fixed::<4, 4>::from_f64(0.03), Fixed::from_raw(0));
+        assert_eq!(Fixed::<4, 4>::from_f64(0.04), Fixed::from_raw(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_f64_out_of_range_panics() {
+        let _ = Fixed::<4, 4>::from_f64(8.0);
+    }
+
+    #[test]
+    fn test_wrapping_ops_match_signed_bits() {
+        for x in all_values::<3, 2>() {
+            for y in all_values::<3, 2>() {
+                let xb: SignedBits<5> = x.to_bits();
+                let yb: SignedBits<5> = y.to_bits();
+                assert_eq!((x + y).to_bits::<5>(), xb + yb);
+                assert_eq!((x - y).to_bits::<5>(), xb - yb);
+            }
+            assert_eq!((-x).to_bits::<5>(), -x.to_bits::<5>());
+        }
+    }
+
+    #[test]
+    fn test_widening_ops_are_exact() {
+        for x in all_values::<3, 2>() {
+            for y in all_values::<2, 3>() {
+                let sum: Fixed<4, 3> = x.widening_add(y);
+                assert_eq!(sum.to_f64(), x.to_f64() + y.to_f64());
+                let diff: Fixed<4, 3> = x.widening_sub(y);
+                assert_eq!(diff.to_f64(), x.to_f64() - y.to_f64());
+                let product: Fixed<5, 5> = x.widening_mul(y);
+                assert_eq!(product.to_f64(), x.to_f64() * y.to_f64());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let check = |value: f64, truncate: f64, half_up: f64, convergent: f64| {
+            let x = Fixed::<8, 4>::from_f64(value);
+            let t: Fixed<8, 0> = x.resize(Rounding::Truncate);
+            let h: Fixed<8, 0> = x.resize(Rounding::RoundHalfUp);
+            let c: Fixed<8, 0> = x.resize(Rounding::Convergent);
+            assert_eq!(t.to_f64(), truncate);
+            assert_eq!(h.to_f64(), half_up);
+            assert_eq!(c.to_f64(), convergent);
+        };
+        check(2.5, 2.0, 3.0, 2.0);
+        check(3.5, 3.0, 4.0, 4.0);
+        check(2.25, 2.0, 2.0, 2.0);
+        check(2.75, 2.0, 3.0, 3.0);
+        check(-2.5, -3.0, -2.0, -2.0);
+        check(-3.5, -4.0, -3.0, -4.0);
+        check(-2.75, -3.0, -3.0, -3.0);
+    }
+
+    #[test]
+    fn test_saturate_clamps() {
+        for x in all_values::<4, 4>() {
+            for rounding in [
+                Rounding::Truncate,
+                Rounding::RoundHalfUp,
+                Rounding::Convergent,
+            ] {
+                let y: Fixed<2, 2> = x.saturate(rounding);
+                let exact: Fixed<5, 2> = x.resize(rounding);
+                let expected = exact.to_f64().clamp(-2.0, 1.75);
+                assert_eq!(y.to_f64(), expected);
+            }
+        }
+        let x = Fixed::<64, 64>::max_value();
+        let y: Fixed<64, 64> = x.saturate(Rounding::Truncate);
+        assert_eq!(y, x);
+        let y: Fixed<60, 68> = x.saturate(Rounding::Truncate);
+        assert_eq!(y, Fixed::<60, 68>::max_value());
+        let y: Fixed<60, 68> = Fixed::<64, 64>::min_value().saturate(Rounding::Truncate);
+        assert_eq!(y, Fixed::<60, 68>::min_value());
+    }
+
+    #[test]
+    fn test_saturating_add_and_sub() {
+        for x in all_values::<3, 2>() {
+            for y in all_values::<3, 2>() {
+                let expected = (x.to_f64() + y.to_f64()).clamp(-4.0, 3.75);
+                assert_eq!(x.saturating_add(y).to_f64(), expected);
+                let expected = (x.to_f64() - y.to_f64()).clamp(-4.0, 3.75);
+                assert_eq!(x.saturating_sub(y).to_f64(), expected);
+            }
+        }
+        let x = Fixed::<64, 64>::max_value();
+        assert_eq!(x.saturating_add(x), x);
+        let y = Fixed::<64, 64>::min_value();
+        assert_eq!(y.saturating_add(y), y);
+        assert_eq!(y.saturating_sub(x), y);
+    }
+}
@@ -22,7 +22,8 @@
 //! the number of bits they represent, and can be used to represent any number of bits from 1
 //! to 128.  The [Bits] type is an unsigned integer type, and the [SignedBits] type is a signed
 //! integer type.  Both types implement the standard Rust traits for integer types, including
-//! [Add](std::ops::Add), [Sub](std::ops::Sub), [BitAnd](std::ops::BitAnd),
+//! [Add](std::ops::Add), [Sub](std::ops::Sub), [Mul](std::ops::Mul), [Div](std::ops::Div),
+//! [Rem](std::ops::Rem), [BitAnd](std::ops::BitAnd),
 //! [BitOr](std::ops::BitOr), [BitXor](std::ops::BitXor), [Shl](std::ops::Shl),
 //! [Shr](std::ops::Shr), [Not](std::ops::Not), [Eq], [Ord], [PartialEq], [PartialOrd],
 //! [Display](std::fmt::Display), [LowerHex](std::fmt::LowerHex),
@@ -86,11 +87,33 @@
 //!
 //! Note that the [Bits] type only supports up to 128 bit values.  Larger bit vectors
 //! can easily be constructed using data structures (arrays, structs, enums, tuples).
-//! But arithmetic on them is not supported by default.  You will need to provide your
-//! own arithmetic implementations for these types.  This is a limitation of the
-//! [Bits] type, but it is a limitation that is not likely to be a problem in practice.
-//! Practical hardware limitations can mean that performing arithmetic on very long
-//! bit vectors is likely to be very slow.
+//! If you need a single wide value (like a 256 bit data bus or a 512 bit cache line),
+//! you can use the [WideBits] and [SignedWideBits] types instead.  These hold up to
+//! [MAX_WIDE_BITS] bits, and support the same wrapping arithmetic, bitwise and shift
+//! operators as [Bits] and [SignedBits].  They are slower, so prefer [Bits] whenever
+//! 128 bits is enough.  Every wide value is stored in [MAX_WIDE_BITS] bits, whatever
+//! its width, so a 256 bit value is as expensive to copy and operate on as a 1024 bit one.
+//! ```
+//! # use rhdl_bits::WideBits;
+//! let mut line: WideBits<512> = WideBits::from(0xDEAD_BEEF);
+//! line <<= 480;
+//! assert_eq!(line.slice::<32>(480), 0xDEAD_BEEF);
+//! ```
+//!
+//! Width mistakes are caught when your design is built wherever the widths are known at
+//! compile time.  For example, `Bits<200>`, a `widening_mul` into the wrong width, or a
+//! [Bits::slice_at] that overruns the value will all fail during `cargo build` (these checks
+//! are evaluated when the code is monomorphized, so `cargo check` may not report them).  The
+//! value passed to [bits] can only be checked at compile time in a constant context, so use a
+//! `const` item or block for literals:
+//! ```
+//! # use rhdl_bits::{Bits, bits};
+//! let x: Bits<8> = const { bits(0xAB) };
+//! ```
+//! ```compile_fail
+//! # use rhdl_bits::{Bits, bits};
+//! let x: Bits<8> = const { bits(0x1AB) }; // Does not fit in 8 bits
+//! ```
 //!
 //! # Constructing [SignedBits]
 //! The [SignedBits] type can be constructed in the same way as the [Bits] type.  The
@@ -112,6 +135,40 @@
 //! get a Rust complaint about not being able to decide what type the integer literal must
 //! assume.  This is unfortunate, but unavoidable.
 //!
+//! # Parsing Verilog Literals
+//!
+//! Both [Bits] and [SignedBits] implement [FromStr](std::str::FromStr), and accept Verilog
+//! style sized literals in binary, octal, decimal or hex, with optional underscores.  This is
+//! handy when pasting values from waveform viewers or test vectors.  If the literal declares
+//! a width, it must match the width of the type.  Errors are reported as a [ParseBitsError]
+//! rather than a panic:
+//! ```
+//! # use rhdl_bits::{alias::*, ParseBitsError};
+//! let x: b8 = "8'hA5".parse().unwrap();
+//! assert_eq!(x, 0xA5);
+//! let y: s12 = "-12'sd45".parse().unwrap();
+//! assert_eq!(y, -45);
+//! assert_eq!("4'b10x1".parse::<b4>(), Err(ParseBitsError::UnknownDigit('x')));
+//! ```
+//!
+//! # Bytes and Serialization
+//!
+//! Both [Bits] and [SignedBits] can be converted to and from little or big endian byte arrays,
+//! which must be exactly `N.div_ceil(8)` bytes long.  When `N` is not a multiple of 8, the
+//! unused bits are zero filled (or sign extended for [SignedBits]), and converting back fails
+//! with a [ParseBitsError] if they are not:
+//! ```
+//! # use rhdl_bits::{alias::*, ParseBitsError};
+//! let x: b12 = 0xABC.into();
+//! assert_eq!(x.to_be_bytes(), [0x0A, 0xBC]);
+//! assert_eq!(b12::from_be_bytes([0x0A, 0xBC]), Ok(x));
+//! assert_eq!(b12::from_be_bytes([0xFA, 0xBC]), Err(ParseBitsError::Overflow { width: 12 }));
+//! ```
+//! With the `serde` feature enabled, [Bits] and [SignedBits] implement `Serialize` and
+//! `Deserialize`.  Human readable formats (like JSON) use a Verilog style hex literal
+//! such as `"8'ha5"`, and binary formats use the raw integer.  When deserializing, either
+//! an integer or a literal string is accepted, and the value is checked against the width.
+//!
 //! # Operations
 //! Only a subset of operations are defined for [Bits] and [SignedBits].  These are
 //! the operations that can be synthesized in hardware without surprises (generally
@@ -164,7 +221,7 @@
 //! assert_eq!(word, 0xFF_D6);
 //! ```
 //!
-//! * Be careful * when using the `slice` operator on [SignedBits] values.  If you
+//! **Be careful** when using the `slice` operator on [SignedBits] values.  If you
 //! slice a [SignedBits] value to a smaller size, the sign bit will be lost.  For
 //! example:
 //!
@@ -184,7 +241,7 @@
 //! * Both operands must be of the same width.
 //! * Both operands must be of the same type (e.g., [SignedBits] or [Bits]).
 //! * One of the operands may be a literal, in which case it will be converted to
-//! the appropriate type before the operator is applied.
+//!   the appropriate type before the operator is applied.
 //!
 //! These rules are entirely enforced in the Rust type system.  So there is nothing
 //! special about following these rules that you are not already accustomed to.  The
@@ -308,6 +365,74 @@
 //! assert_eq!(x, 0);
 //! ```
 //!
+//! ## Detecting Overflow
+//! The operators always wrap.  When you need to model the carry out of an adder, or
+//! clamp the result of an arithmetic operation (as is common in DSP designs), both
+//! [Bits] and [SignedBits] provide methods similar to those on the built in integers:
+//! - `overflowing_add`, `overflowing_sub` and `overflowing_mul` return the wrapped value and
+//!   a flag.  For [Bits] the flag is the carry (or borrow) out, and for [SignedBits] it is
+//!   the 2's complement overflow flag.
+//! - `add_with_carry` adds a carry in bit, and returns the same flag as `overflowing_add`.
+//! - `checked_add`, `checked_sub`, `checked_mul` and `checked_div` return `None` on overflow.
+//! - `saturating_add`, `saturating_sub` and `saturating_mul` clamp the result to the range of
+//!   the type.
+//! ```
+//! # use rhdl_bits::alias::*;
+//! let x: b8 = 0xF0.into();
+//! let (sum, carry) = x.add_with_carry(0x0F.into(), true);
+//! assert_eq!(sum, 0x00);
+//! assert!(carry);
+//! let y: s8 = 100.into();
+//! assert_eq!(y.saturating_add(100.into()), 127);
+//! assert_eq!(y.checked_add(100.into()), None);
+//! ```
+//!
+//! ## Multiplication
+//! The [Mul](std::ops::Mul) and [MulAssign](std::ops::MulAssign) traits are implemented
+//! for both [Bits] and [SignedBits].  As with addition, the product is truncated to the
+//! width of the operands, and wraps around on overflow:
+//! ```
+//! # use rhdl_bits::alias::*;
+//! let x: b8 = 0x10.into();
+//! let y: b8 = 0x11.into();
+//! assert_eq!(x * y, 0x10); // 0x110 truncated to 8 bits
+//! let x: s8 = (-3).into();
+//! assert_eq!(x * 5, -15);
+//! ```
+//!
+//! A hardware multiplier can also produce the full product, which is `N + M` bits
+//! wide for an `N` bit and an `M` bit operand.  Use `widening_mul` for this, and
+//! specify the output width (which must be `N + M`):
+//! ```
+//! # use rhdl_bits::alias::*;
+//! let x: b8 = 0xFF.into();
+//! let y: b8 = 0xFF.into();
+//! let z: b16 = x.widening_mul(y);
+//! assert_eq!(z, 0xFE01);
+//! ```
+//!
+//! ## Division and Remainder
+//! The [Div](std::ops::Div) and [Rem](std::ops::Rem) traits (and their `Assign` variants)
+//! are implemented for both [Bits] and [SignedBits].  Unsigned division truncates, and
+//! signed division rounds towards zero with the remainder taking the sign of the dividend,
+//! just like Rust and Verilog.
+//!
+//! Hardware dividers do not trap on a zero divisor, so neither do these operators.  Instead,
+//! they produce the result that a typical synthesized divider does:
+//! - Dividing by zero produces a quotient with all bits set (`-1` for [SignedBits]).
+//! - The remainder of a division by zero is the dividend.
+//! - The one signed overflow case (the most negative value divided by `-1`) wraps to the most
+//!   negative value, with a remainder of zero.
+//! ```
+//! # use rhdl_bits::alias::*;
+//! let x: b8 = 42.into();
+//! assert_eq!(x / 0, 0xFF);
+//! assert_eq!(x % 0, 42);
+//! let y: s8 = (-128).into();
+//! assert_eq!(y / -1, -128);
+//! assert_eq!(y % -1, 0);
+//! ```
+//!
 //! ## Bitwise Logical Operators
 //!
 //! All four of the standard Rust logical operators are supported for both [Bits] and [SignedBits].
@@ -317,6 +442,7 @@
 //! - [And](std::ops::BitAnd) and [AndAssign](std::ops::BitAndAssign) for `&` and `&=`
 //! - [Xor](std::ops::BitXor) and [XorAssign](std::ops::BitXorAssign) for `^` and `^=`
 //! - [Not](std::ops::Not) for `!`
+//!
 //! Other, more exotic binary operators (like Xnor or Nand) are not supported.  If you need these,
 //! you will need to implement them in terms of these more basic operators.
 //!
@@ -408,6 +534,24 @@
 //! assert_eq!(z, 0);
 //! ```
 //!
+//! ## Reductions and Bit Counting
+//!
+//! Both [Bits] and [SignedBits] provide the reduction operators from Verilog (`any`, `all`, `xor`,
+//! `nand`, `nor` and `xnor`), along with the usual bit counting and permutation helpers
+//! (`count_ones`, `leading_zeros`, `trailing_zeros`, `reverse_bits`, `rotate_left` and
+//! `rotate_right`).  These all operate on the `N` bits of the value.  For arbiters and decoders,
+//! `one_hot` converts a binary index to a one-hot value, and `priority_encode` returns the index
+//! of the lowest set bit:
+//! ```
+//! # use rhdl_bits::alias::*;
+//! let requests: b8 = 0b0110_1000.into();
+//! assert_eq!(requests.count_ones(), 3);
+//! assert_eq!(requests.leading_zeros(), 1);
+//! let grant: b3 = requests.priority_encode().unwrap();
+//! let one_hot: b8 = grant.one_hot();
+//! assert_eq!(one_hot, 0b0000_1000);
+//! ```
+//!
 //! ## Comparison Operators
 //!
 //! The standard Rust comparison operators are implemented for both [Bits] and [SignedBits].  These
@@ -433,6 +577,41 @@
 //! assert_eq!(x.as_unsigned(), 0b1111_1111);
 //! ```
 //!
+//! # Four State Logic
+//!
+//! [Bits] and [SignedBits] are strictly 2-state.  To model tri-state buses, uninitialized
+//! registers, or to compare against a Verilog simulation that propagates `X` values, use
+//! [LogicVec], where each bit is one of the [Logic] values `0`, `1`, `X` or `Z`.  The logical
+//! operators propagate `X` as Verilog does, and converting back to [Bits] fails with an
+//! [UnknownBitsError] if any bit is `X` or `Z`:
+//! ```
+//! # use rhdl_bits::{alias::*, LogicVec};
+//! let bus: LogicVec<8> = "8'bzzzz_zzzz".parse().unwrap();
+//! let driver: LogicVec<8> = "8'hzA".parse().unwrap();
+//! let bus = bus.resolve(driver);
+//! assert_eq!(format!("{}", bus), "zzzz1010");
+//! assert!(b8::try_from(bus).is_err());
+//! assert_eq!(b4::try_from(LogicVec::<4>::from(b4::from(0xA))), Ok(b4::from(0xA)));
+//! ```
+//!
+//! # Fixed Point Values
+//!
+//! The [Fixed] and [UFixed] types are signed and unsigned fixed point values built on the
+//! same representation as [SignedBits] and [Bits].  A `Fixed<I, F>` has `I` integer bits
+//! (including the sign bit) and `F` fractional bits, for a total width of `I + F` bits.  The
+//! `+` and `-` operators wrap, just like they do for [Bits].  The `widening_add`, `widening_sub`
+//! and `widening_mul` methods produce exact results in a wider format, and `resize` and
+//! `saturate` convert back to a narrower format using one of the [Rounding] modes.  For test
+//! benches, you can convert to and from [f64]:
+//! ```
+//! # use rhdl_bits::{Fixed, Rounding};
+//! let gain = Fixed::<2, 14>::from_f64(0.7071);
+//! let sample = Fixed::<1, 15>::from_f64(-0.5);
+//! let product: Fixed<3, 29> = gain.widening_mul(sample);
+//! let output: Fixed<1, 15> = product.saturate(Rounding::Convergent);
+//! assert!((output.to_f64() + 0.35355).abs() < 1e-4);
+//! ```
+//!
 #[doc(hidden)]
 pub mod add;
 #[doc(hidden)]
@@ -440,20 +619,46 @@ pub mod and;
 #[doc(hidden)]
 pub mod bits;
 #[doc(hidden)]
+pub mod bytes;
+#[doc(hidden)]
+pub mod div;
+#[doc(hidden)]
+pub mod fixed;
+#[doc(hidden)]
+pub mod logic_vec;
+#[doc(hidden)]
+pub mod mul;
+#[doc(hidden)]
 pub mod neg;
 #[doc(hidden)]
 pub mod not;
 #[doc(hidden)]
 pub mod or;
 #[doc(hidden)]
+pub mod overflow;
+#[doc(hidden)]
+pub mod parse;
+#[doc(hidden)]
+pub mod reduce;
+#[doc(hidden)]
+pub mod rem;
+#[cfg(feature = "serde")]
+mod serialize;
+#[doc(hidden)]
 pub mod shl;
 #[doc(hidden)]
 pub mod shr;
 #[doc(hidden)]
 pub mod signed_bits;
 #[doc(hidden)]
+pub mod signed_wide_bits;
+#[doc(hidden)]
 pub mod sub;
 #[doc(hidden)]
+pub mod ufixed;
+#[doc(hidden)]
+pub mod wide_bits;
+#[doc(hidden)]
 pub mod xor;
 
 pub mod alias {
@@ -487,8 +692,18 @@ pub mod alias {
 
 pub use bits::bits;
 pub use bits::Bits;
+pub use fixed::Fixed;
+pub use fixed::Rounding;
+pub use logic_vec::Logic;
+pub use logic_vec::LogicVec;
+pub use logic_vec::UnknownBitsError;
+pub use parse::ParseBitsError;
 pub use signed_bits::signed;
 pub use signed_bits::SignedBits;
+pub use signed_wide_bits::SignedWideBits;
+pub use ufixed::UFixed;
+pub use wide_bits::WideBits;
+pub use wide_bits::MAX_WIDE_BITS;
 
 #[cfg(test)]
 mod test {
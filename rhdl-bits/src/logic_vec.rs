@@ -0,0 +1,502 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Not, Sub};
+use std::str::FromStr;
+
+use crate::bits::Bits;
+use crate::parse::{Literal, ParseBitsError};
+
+/// The value of a single bit of 4-state logic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Logic {
+    /// A logic low.
+    Zero,
+    /// A logic high.
+    One,
+    /// An unknown value (like an uninitialized register, or
+    /// the result of two drivers fighting).
+    X,
+    /// A high impedance (undriven) value.
+    Z,
+}
+
+impl Logic {
+    // The (aval, bval) encoding of the bit.
+    fn planes(self) -> (bool, bool) {
+        match self {
+            Logic::Zero => (false, false),
+            Logic::One => (true, false),
+            Logic::Z => (false, true),
+            Logic::X => (true, true),
+        }
+    }
+    fn from_planes(aval: bool, bval: bool) -> Self {
+        match (aval, bval) {
+            (false, false) => Logic::Zero,
+            (true, false) => Logic::One,
+            (false, true) => Logic::Z,
+            (true, true) => Logic::X,
+        }
+    }
+    /// Convert to a `bool`, or `None` if the bit is X or Z.
+    pub fn to_bool(self) -> Option<bool> {
+        match self {
+            Logic::Zero => Some(false),
+            Logic::One => Some(true),
+            Logic::X | Logic::Z => None,
+        }
+    }
+}
+
+impl From<bool> for Logic {
+    fn from(value: bool) -> Self {
+        if value {
+            Logic::One
+        } else {
+            Logic::Zero
+        }
+    }
+}
+
+impl Display for Logic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Logic::Zero => '0',
+            Logic::One => '1',
+            Logic::X => 'x',
+            Logic::Z => 'z',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// The error returned when converting a [LogicVec] that contains
+/// X or Z bits into a [Bits] value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownBitsError {
+    /// A mask with a 1 for each bit that was X or Z.
+    pub unknown: u128,
+}
+
+impl Display for UnknownBitsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value has X or Z bits (mask 0x{:x})", self.unknown)
+    }
+}
+
+impl std::error::Error for UnknownBitsError {}
+
+/// The [LogicVec] type is a 4-state bit vector, where each bit can be
+/// `0`, `1`, `X` (unknown) or `Z` (high impedance).  It is meant for
+/// modelling tri-state buses, uninitialized registers and for comparing
+/// against Verilog simulations that propagate `X` values.  Like [Bits],
+/// it holds up to 128 bits.
+///
+/// The bits are stored as a pair of planes using the same encoding as
+/// the Verilog VPI: `0 = (0, 0)`, `1 = (1, 0)`, `Z = (0, 1)` and
+/// `X = (1, 1)`, where the pair is `(aval, bval)`.
+///
+/// The logical operators propagate `X` values the same way Verilog
+/// does (so `0 & X` is `0`, but `1 & X` is `X`), and treat a `Z` input
+/// as an `X`.  The arithmetic operators produce all `X` if any input bit
+/// is unknown.  Note that `==` compares the 4-state values exactly (like
+/// the Verilog `===` operator).  Use [LogicVec::logic_eq] to get the
+/// Verilog `==` behavior.
+/// ```
+/// # use rhdl_bits::{Bits, LogicVec, Logic};
+/// let x: LogicVec<4> = "4'b10x1".parse().unwrap();
+/// let y: LogicVec<4> = Bits::<4>::from(0b0011).into();
+/// assert_eq!(format!("{}", x & y), "00x1");
+/// assert_eq!(format!("{}", x + y), "xxxx");
+/// assert_eq!(x.logic_eq(y), Logic::Zero);
+/// assert!(Bits::<4>::try_from(x).is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LogicVec<const N: usize> {
+    pub(crate) aval: u128,
+    pub(crate) bval: u128,
+}
+
+impl<const N: usize> LogicVec<N> {
+    fn mask() -> u128 {
+        Bits::<N>::mask().0
+    }
+    /// Return a [LogicVec] with all bits set to `X`.
+    pub fn x() -> Self {
+        Self {
+            aval: Self::mask(),
+            bval: Self::mask(),
+        }
+    }
+    /// Return a [LogicVec] with all bits set to `Z`.
+    pub fn z() -> Self {
+        Self {
+            aval: 0,
+            bval: Self::mask(),
+        }
+    }
+    /// Build a [LogicVec] from the `aval` and `bval` planes.
+    /// Panics if either plane does not fit in `N` bits.
+    /// ```
+    /// # use rhdl_bits::LogicVec;
+    /// let x = LogicVec::<4>::from_raw(0b1010, 0b0011);
+    /// assert_eq!(format!("{}", x), "10xz");
+    /// ```
+    pub fn from_raw(aval: u128, bval: u128) -> Self {
+        assert!(aval <= Self::mask() && bval <= Self::mask());
+        Self { aval, bval }
+    }
+    /// Return the `aval` plane of the value.
+    pub fn aval(self) -> u128 {
+        self.aval
+    }
+    /// Return the `bval` plane of the value (a 1 for each
+    /// bit that is `X` or `Z`).
+    pub fn bval(self) -> u128 {
+        self.bval
+    }
+    /// Get the value of a specific bit.  Panics if the index
+    /// of the bit is outside the range of the value.
+    pub fn get_bit(&self, bit: usize) -> Logic {
+        assert!(bit < N);
+        Logic::from_planes(self.aval & (1 << bit) != 0, self.bval & (1 << bit) != 0)
+    }
+    /// Set a specific bit to the given value.  Panics if the index
+    /// of the bit is outside the range of the value.
+    /// ```
+    /// # use rhdl_bits::{LogicVec, Logic};
+    /// let mut x = LogicVec::<4>::z();
+    /// x.set_bit(0, Logic::One);
+    /// x.set_bit(3, Logic::X);
+    /// assert_eq!(format!("{}", x), "xzz1");
+    /// ```
+    pub fn set_bit(&mut self, bit: usize, value: Logic) {
+        assert!(bit < N);
+        let (aval, bval) = value.planes();
+        self.aval = (self.aval & !(1 << bit)) | ((aval as u128) << bit);
+        self.bval = (self.bval & !(1 << bit)) | ((bval as u128) << bit);
+    }
+    /// Returns true if none of the bits are `X` or `Z`.
+    pub fn is_known(self) -> bool {
+        self.bval == 0
+    }
+    /// Returns true if any of the bits are `X`.
+    pub fn has_x(self) -> bool {
+        self.aval & self.bval != 0
+    }
+    /// Returns true if any of the bits are `Z`.
+    pub fn has_z(self) -> bool {
+        !self.aval & self.bval != 0
+    }
+    /// Return a [Bits] value with a 1 for each bit that is `X` or `Z`.
+    pub fn unknown_mask(self) -> Bits<N> {
+        Bits(self.bval)
+    }
+    fn known_ones(self) -> u128 {
+        self.aval & !self.bval
+    }
+    fn known_zeros(self) -> u128 {
+        !self.aval & !self.bval & Self::mask()
+    }
+    // Build a value from the bits known to be 1 and 0.  All other bits
+    // are X, which is how a Z input is seen by the operators.
+    fn from_known(ones: u128, zeros: u128) -> Self {
+        let unknown = !(ones | zeros) & Self::mask();
+        Self {
+            aval: ones | unknown,
+            bval: unknown,
+        }
+    }
+    /// Compare two values as the Verilog `==` operator does.  The
+    /// result is [Logic::Zero] if any known bits differ,
+    /// [Logic::X] if the comparison depends on unknown bits,
+    /// and [Logic::One] otherwise.
+    /// ```
+    /// # use rhdl_bits::{LogicVec, Logic};
+    /// let x: LogicVec<4> = "4'b10x1".parse().unwrap();
+    /// assert_eq!(x.logic_eq("4'b1011".parse().unwrap()), Logic::X);
+    /// assert_eq!(x.logic_eq("4'b0001".parse().unwrap()), Logic::Zero);
+    /// assert_eq!(x.logic_eq(x), Logic::X);
+    /// ```
+    pub fn logic_eq(self, rhs: Self) -> Logic {
+        let known = !(self.bval | rhs.bval) & Self::mask();
+        if (self.aval ^ rhs.aval) & known != 0 {
+            Logic::Zero
+        } else if known != Self::mask() {
+            Logic::X
+        } else {
+            Logic::One
+        }
+    }
+    /// Resolve two drivers of the same tri-state bus.  A `Z` bit
+    /// yields to the other driver, and two drivers that disagree (or
+    /// an `X` on either driver) produce an `X`.
+    /// ```
+    /// # use rhdl_bits::LogicVec;
+    /// let a: LogicVec<4> = "4'b10zz".parse().unwrap();
+    /// let b: LogicVec<4> = "4'bz11z".parse().unwrap();
+    /// assert_eq!(format!("{}", a.resolve(b)), "1x1z");
+    /// ```
+    pub fn resolve(self, rhs: Self) -> Self {
+        let lhs_z = !self.aval & self.bval;
+        let rhs_z = !rhs.aval & rhs.bval;
+        let both_z = lhs_z & rhs_z;
+        // Replace each Z with the other driver, and then any
+        // bits that still differ (or are X) are X.
+        let lhs = Self {
+            aval: (self.aval & !lhs_z) | (rhs.aval & lhs_z),
+            bval: (self.bval & !lhs_z) | (rhs.bval & lhs_z),
+        };
+        let rhs = Self {
+            aval: (rhs.aval & !rhs_z) | (self.aval & rhs_z),
+            bval: (rhs.bval & !rhs_z) | (self.bval & rhs_z),
+        };
+        let ones = lhs.known_ones() & rhs.known_ones();
+        let zeros = lhs.known_zeros() & rhs.known_zeros();
+        let resolved = Self::from_known(ones, zeros | both_z);
+        Self {
+            aval: resolved.aval & !both_z,
+            bval: resolved.bval | both_z,
+        }
+    }
+}
+
+/// The default value for a [LogicVec] is all `X`, just like an
+/// uninitialized register.
+impl<const N: usize> Default for LogicVec<N> {
+    fn default() -> Self {
+        Self::x()
+    }
+}
+
+impl<const N: usize> From<Bits<N>> for LogicVec<N> {
+    fn from(value: Bits<N>) -> Self {
+        Self {
+            aval: value.0,
+            bval: 0,
+        }
+    }
+}
+
+/// Convert a [LogicVec] to a [Bits] value.  This fails if any
+/// of the bits are `X` or `Z`.
+impl<const N: usize> TryFrom<LogicVec<N>> for Bits<N> {
+    type Error = UnknownBitsError;
+    fn try_from(value: LogicVec<N>) -> Result<Self, Self::Error> {
+        if value.is_known() {
+            Ok(Bits(value.aval))
+        } else {
+            Err(UnknownBitsError {
+                unknown: value.bval,
+            })
+        }
+    }
+}
+
+impl<const N: usize> BitAnd for LogicVec<N> {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self::from_known(
+            self.known_ones() & rhs.known_ones(),
+            self.known_zeros() | rhs.known_zeros(),
+        )
+    }
+}
+
+impl<const N: usize> BitOr for LogicVec<N> {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::from_known(
+            self.known_ones() | rhs.known_ones(),
+            self.known_zeros() & rhs.known_zeros(),
+        )
+    }
+}
+
+impl<const N: usize> BitXor for LogicVec<N> {
+    type Output = Self;
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let unknown = self.bval | rhs.bval;
+        let value = (self.aval ^ rhs.aval) & !unknown;
+        Self::from_known(value, !value & !unknown)
+    }
+}
+
+impl<const N: usize> Not for LogicVec<N> {
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        Self::from_known(self.known_zeros(), self.known_ones())
+    }
+}
+
+impl<const N: usize> Add for LogicVec<N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        match (Bits::try_from(self), Bits::try_from(rhs)) {
+            (Ok(lhs), Ok(rhs)) => (lhs + rhs).into(),
+            _ => Self::x(),
+        }
+    }
+}
+
+impl<const N: usize> Sub for LogicVec<N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        match (Bits::try_from(self), Bits::try_from(rhs)) {
+            (Ok(lhs), Ok(rhs)) => (lhs - rhs).into(),
+            _ => Self::x(),
+        }
+    }
+}
+
+/// Parse a [LogicVec] from a Verilog style literal, such as
+/// `4'b10x1`, `8'hz` or `16'hxx_A5`.  Each `x`, `z` or `?` digit
+/// covers as many bits as a digit of that base, and an unknown
+/// leading digit is extended to fill the width.  A decimal
+/// literal may be a single `x` or `z`.  Negating a value with
+/// unknown bits gives all `X`.
+/// ```
+/// # use rhdl_bits::LogicVec;
+/// let x: LogicVec<8> = "8'hx5".parse().unwrap();
+/// assert_eq!(format!("{}", x), "xxxx0101");
+/// let z: LogicVec<8> = "8'dz".parse().unwrap();
+/// assert_eq!(z, LogicVec::z());
+/// ```
+impl<const N: usize> FromStr for LogicVec<N> {
+    type Err = ParseBitsError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let literal = Literal::split(s, N)?;
+        let (aval, bval) = literal.planes(N)?;
+        let value = Self { aval, bval };
+        if !literal.negative {
+            Ok(value)
+        } else if let Ok(known) = Bits::try_from(value) {
+            Ok((Bits(0) - known).into())
+        } else {
+            Ok(Self::x())
+        }
+    }
+}
+
+impl<const N: usize> Display for LogicVec<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for bit in (0..N).rev() {
+            write!(f, "{}", self.get_bit(bit))?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Debug for LogicVec<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}'b{}", N, self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ALL: [Logic; 4] = [Logic::Zero, Logic::One, Logic::X, Logic::Z];
+
+    fn bit(x: Logic) -> LogicVec<1> {
+        let mut v = LogicVec::<1>::x();
+        v.set_bit(0, x);
+        v
+    }
+
+    #[test]
+    fn test_truth_tables() {
+        use Logic::*;
+        let and = |a, b| match (a, b) {
+            (Zero, _) | (_, Zero) => Zero,
+            (One, One) => One,
+            _ => X,
+        };
+        let or = |a, b| match (a, b) {
+            (One, _) | (_, One) => One,
+            (Zero, Zero) => Zero,
+            _ => X,
+        };
+        let xor = |a: Logic, b: Logic| match (a.to_bool(), b.to_bool()) {
+            (Some(a), Some(b)) => Logic::from(a ^ b),
+            _ => X,
+        };
+        for a in ALL {
+            assert_eq!((!bit(a)).get_bit(0), xor(a, One));
+            for b in ALL {
+                assert_eq!((bit(a) & bit(b)).get_bit(0), and(a, b));
+                assert_eq!((bit(a) | bit(b)).get_bit(0), or(a, b));
+                assert_eq!((bit(a) ^ bit(b)).get_bit(0), xor(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        use Logic::*;
+        for a in ALL {
+            assert_eq!(bit(a).resolve(bit(Z)).get_bit(0), a);
+            assert_eq!(bit(Z).resolve(bit(a)).get_bit(0), a);
+            assert_eq!(bit(a).resolve(bit(X)).get_bit(0), X);
+        }
+        assert_eq!(bit(One).resolve(bit(One)).get_bit(0), One);
+        assert_eq!(bit(Zero).resolve(bit(Zero)).get_bit(0), Zero);
+        assert_eq!(bit(One).resolve(bit(Zero)).get_bit(0), X);
+    }
+
+    #[test]
+    fn test_known_values_match_bits() {
+        for i in 0..16 {
+            for j in 0..16 {
+                let (a, b) = (Bits::<4>(i), Bits::<4>(j));
+                let (x, y) = (LogicVec::from(a), LogicVec::from(b));
+                assert_eq!(Bits::try_from(x & y), Ok(a & b));
+                assert_eq!(Bits::try_from(x | y), Ok(a | b));
+                assert_eq!(Bits::try_from(x ^ y), Ok(a ^ b));
+                assert_eq!(Bits::try_from(!x), Ok(!a));
+                assert_eq!(Bits::try_from(x + y), Ok(a + b));
+                assert_eq!(Bits::try_from(x - y), Ok(a - b));
+                assert_eq!(x.logic_eq(y), Logic::from(a == b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_conversion_fails() {
+        let x: LogicVec<8> = "8'b1010_zz01".parse().unwrap();
+        assert!(x.has_z());
+        assert!(!x.has_x());
+        assert_eq!(x.unknown_mask(), 0b1100);
+        assert_eq!(
+            Bits::<8>::try_from(x),
+            Err(UnknownBitsError { unknown: 0b1100 })
+        );
+    }
+
+    #[test]
+    fn test_parse_logic_literals() {
+        let parse = |s: &str| format!("{}", s.parse::<LogicVec<8>>().unwrap());
+        assert_eq!(parse("8'hA5"), "10100101");
+        assert_eq!(parse("8'hxA"), "xxxx1010");
+        assert_eq!(parse("8'hx"), "xxxxxxxx");
+        assert_eq!(parse("8'bz1"), "zzzzzzz1");
+        assert_eq!(parse("8'b1?"), "0000001z");
+        assert_eq!(parse("8'o1x"), "00001xxx");
+        assert_eq!(parse("8'dx"), "xxxxxxxx");
+        assert_eq!(parse("-8'd1"), "11111111");
+        assert_eq!(parse("-8'b1x"), "xxxxxxxx");
+        assert_eq!(
+            "8'hx1FF".parse::<LogicVec<8>>(),
+            Err(ParseBitsError::Overflow { width: 8 })
+        );
+        assert_eq!(
+            "8'dx1".parse::<LogicVec<8>>(),
+            Err(ParseBitsError::InvalidDigit {
+                digit: '1',
+                radix: 10
+            })
+        );
+        let x: LogicVec<128> = "128'hx".parse().unwrap();
+        assert_eq!(x, LogicVec::x());
+    }
+}
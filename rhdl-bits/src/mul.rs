@@ -0,0 +1,212 @@
+use std::ops::Mul;
+use std::ops::MulAssign;
+
+use crate::bits::Bits;
+use crate::signed_bits::SignedBits;
+
+impl<const N: usize> Bits<N> {
+    /// Multiply two [Bits] values, producing a result that is wide
+    /// enough to hold the full product.  This is the behavior of a
+    /// hardware multiplier that does not discard any of its output.
+    /// The output width `P` must be equal to `N + M`, and (like all
+    /// [Bits] values) must be no more than 128 bits.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<8> = 0xFF.into();
+    /// let y: Bits<4> = 0xF.into();
+    /// let z: Bits<12> = x.widening_mul(y);
+    /// assert_eq!(z, 0xFF * 0xF);
+    /// ```
+    /// Asking for an output of the wrong width will fail to build:
+    /// ```compile_fail
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<8> = 0xFF.into();
+    /// let z: Bits<8> = x.widening_mul(x);
+    /// ```
+    pub fn widening_mul<const M: usize, const P: usize>(self, rhs: Bits<M>) -> Bits<P> {
+        const { assert!(P == N + M) }
+        const { assert!(P <= 128) }
+        Bits(self.0 * rhs.0)
+    }
+}
+
+impl<const N: usize> SignedBits<N> {
+    /// Multiply two [SignedBits] values, producing a result that is
+    /// wide enough to hold the full (signed) product.  The output width
+    /// `P` must be equal to `N + M`, and must be no more than 128 bits.
+    /// ```
+    /// # use rhdl_bits::SignedBits;
+    /// let x: SignedBits<8> = (-128).into();
+    /// let y: SignedBits<4> = (-8).into();
+    /// let z: SignedBits<12> = x.widening_mul(y);
+    /// assert_eq!(z, 1024);
+    /// ```
+    pub fn widening_mul<const M: usize, const P: usize>(self, rhs: SignedBits<M>) -> SignedBits<P> {
+        const { assert!(P == N + M) }
+        const { assert!(P <= 128) }
+        // The product of an N bit and an M bit signed value
+        // always fits in N + M bits, so this cannot overflow.
+        SignedBits(self.0 * rhs.0)
+    }
+}
+
+impl<const N: usize> Mul<u128> for Bits<N> {
+    type Output = Self;
+    fn mul(self, rhs: u128) -> Self::Output {
+        self * Bits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> Mul<Bits<N>> for u128 {
+    type Output = Bits<N>;
+    fn mul(self, rhs: Bits<N>) -> Self::Output {
+        Bits::<N>::from(self) * rhs
+    }
+}
+
+impl<const N: usize> Mul<Bits<N>> for Bits<N> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(u128::wrapping_mul(self.0, rhs.0) & Self::mask().0)
+    }
+}
+
+impl<const N: usize> MulAssign<Bits<N>> for Bits<N> {
+    fn mul_assign(&mut self, rhs: Bits<N>) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const N: usize> MulAssign<u128> for Bits<N> {
+    fn mul_assign(&mut self, rhs: u128) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const N: usize> Mul<i128> for SignedBits<N> {
+    type Output = Self;
+    fn mul(self, rhs: i128) -> Self::Output {
+        self * SignedBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> Mul<SignedBits<N>> for i128 {
+    type Output = SignedBits<N>;
+    fn mul(self, rhs: SignedBits<N>) -> Self::Output {
+        SignedBits::<N>::from(self) * rhs
+    }
+}
+
+impl<const N: usize> Mul<SignedBits<N>> for SignedBits<N> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        // The lower N bits of a 2's complement product are the
+        // same for signed and unsigned operands.  So we can
+        // multiply as unsigned and reinterpret the result.
+        (self.as_unsigned() * rhs.as_unsigned()).as_signed()
+    }
+}
+
+impl<const N: usize> MulAssign<i128> for SignedBits<N> {
+    fn mul_assign(&mut self, rhs: i128) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const N: usize> MulAssign<SignedBits<N>> for SignedBits<N> {
+    fn mul_assign(&mut self, rhs: SignedBits<N>) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mul_bits() {
+        let bits: Bits<8> = 0b1101_1010.into();
+        let result = bits * bits;
+        assert_eq!(result.0, (218 * 218) & 0xff);
+        let result = bits * 3;
+        assert_eq!(result.0, (218 * 3) & 0xff);
+        let result = 3 * bits;
+        assert_eq!(result.0, (218 * 3) & 0xff);
+        let bits = Bits::<128>::mask();
+        let result = bits * bits;
+        assert_eq!(result.0, 1);
+    }
+
+    #[test]
+    fn test_mul_assign_bits() {
+        let mut bits: Bits<8> = 0b1101_1010.into();
+        bits *= bits;
+        assert_eq!(bits.0, (218 * 218) & 0xff);
+        let mut bits: Bits<54> = 0b1101_1010.into();
+        bits *= 2;
+        assert_eq!(bits.0, 436);
+    }
+
+    #[test]
+    fn test_unsigned_mul_matches_built_in_behavior_for_u8() {
+        for i in 0..=u8::MAX {
+            for j in 0..=u8::MAX {
+                let k = Bits::<8>::from(i as u128) * Bits::<8>::from(j as u128);
+                assert_eq!(k.0, u8::wrapping_mul(i, j) as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_mul_matches_built_in_behavior_for_i8() {
+        for i in i8::MIN..=i8::MAX {
+            for j in i8::MIN..=i8::MAX {
+                let i_as_signed = SignedBits::<8>::from(i as i128);
+                let j_as_signed = SignedBits::<8>::from(j as i128);
+                let k_as_signed = i_as_signed * j_as_signed;
+                let k = i8::wrapping_mul(i, j);
+                assert_eq!(k_as_signed.0, k as i128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_mul_assign() {
+        let mut x = SignedBits::<8>::from(-3);
+        x *= 5;
+        assert_eq!(x, -15);
+        x *= SignedBits::<8>::from(-10);
+        assert_eq!(x, i8::wrapping_mul(-15, -10) as i128);
+    }
+
+    #[test]
+    fn test_widening_mul_is_exact() {
+        for i in 0..=u8::MAX {
+            for j in 0..16_u8 {
+                let x = Bits::<8>::from(i as u128);
+                let y = Bits::<4>::from(j as u128);
+                let z: Bits<12> = x.widening_mul(y);
+                assert_eq!(z.0, i as u128 * j as u128);
+            }
+        }
+        let x = Bits::<64>::mask();
+        let z: Bits<128> = x.widening_mul(x);
+        assert_eq!(z.0, u64::MAX as u128 * u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_signed_widening_mul_is_exact() {
+        for i in i8::MIN..=i8::MAX {
+            for j in -8..8_i8 {
+                let x = SignedBits::<8>::from(i as i128);
+                let y = SignedBits::<4>::from(j as i128);
+                let z: SignedBits<12> = x.widening_mul(y);
+                assert_eq!(z.0, i as i128 * j as i128);
+            }
+        }
+        let x = SignedBits::<64>::from(i64::MIN as i128);
+        let z: SignedBits<128> = x.widening_mul(x);
+        assert_eq!(z.0, i64::MIN as i128 * i64::MIN as i128);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::signed_bits::SignedBits;
+use crate::signed_wide_bits::SignedWideBits;
 use std::ops::Neg;
 
 impl<const N: usize> Neg for SignedBits<N> {
@@ -8,9 +9,17 @@ impl<const N: usize> Neg for SignedBits<N> {
     }
 }
 
+impl<const N: usize> Neg for SignedWideBits<N> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        !self + 1
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::signed_bits::SignedBits;
+    use crate::signed_wide_bits::SignedWideBits;
 
     #[test]
     fn test_neg_wrapping() {
@@ -29,4 +38,17 @@ mod test {
             assert_eq!(y_signed.0, y as i128);
         }
     }
+
+    #[test]
+    fn test_neg_operator_wide() {
+        for i in i8::MIN..i8::MAX {
+            let x = SignedWideBits::<8>::from(i as i128);
+            assert_eq!(-x, i.wrapping_neg() as i128);
+        }
+        let x = SignedWideBits::<256>::from(i128::MIN);
+        let y = -x;
+        assert!(y.is_non_negative());
+        assert_eq!(-y, x);
+        assert_eq!(-SignedWideBits::<256>::from(5), -5);
+    }
 }
@@ -1,6 +1,8 @@
 use std::ops::Not;
 
-use crate::{bits::Bits, signed_bits::SignedBits};
+use crate::{
+    bits::Bits, signed_bits::SignedBits, signed_wide_bits::SignedWideBits, wide_bits::WideBits,
+};
 
 impl<const N: usize> Not for Bits<N> {
     type Output = Self;
@@ -16,6 +18,20 @@ impl<const N: usize> Not for SignedBits<N> {
     }
 }
 
+impl<const N: usize> Not for WideBits<N> {
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        self.map2(self, |a, _| !a)
+    }
+}
+
+impl<const N: usize> Not for SignedWideBits<N> {
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        self.as_unsigned().not().as_signed()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -54,4 +70,26 @@ mod test {
         let result = !x;
         assert_eq!(result.0, 0_i128);
     }
+
+    #[test]
+    fn test_not_wide_bits() {
+        let bits = WideBits::<200>::default();
+        assert_eq!(!bits, WideBits::<200>::mask());
+        let bits = WideBits::<128>::from(0b1101_1010);
+        assert_eq!(!bits, !0b1101_1010_u128);
+        let mut bits = WideBits::<130>::default();
+        bits.set_bit(129, true);
+        let result = !bits;
+        assert!(!result.get_bit(129));
+        assert!(result.get_bit(128));
+        assert_eq!(result.limbs()[2], 0b01);
+    }
+
+    #[test]
+    fn test_not_on_signed_wide_bits() {
+        let x = SignedWideBits::<256>::from(-4);
+        assert_eq!(!x, 3);
+        let x = SignedWideBits::<256>::from(-1);
+        assert_eq!(!x, 0);
+    }
 }
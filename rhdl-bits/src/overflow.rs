@@ -0,0 +1,326 @@
+use crate::bits::Bits;
+use crate::signed_bits::SignedBits;
+
+impl<const N: usize> Bits<N> {
+    /// Add two [Bits] values, and report the carry out of the
+    /// most significant bit.  The value is the usual wrapping sum.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<8> = 0xF0.into();
+    /// assert_eq!(x.overflowing_add(0x0F.into()), (0xFF.into(), false));
+    /// assert_eq!(x.overflowing_add(0x20.into()), (0x10.into(), true));
+    /// ```
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        self.add_with_carry(rhs, false)
+    }
+    /// Add two [Bits] values and a carry in bit, and report the
+    /// carry out of the most significant bit.  This models a
+    /// full adder chain, and can be used to build wider adders
+    /// out of narrower ones.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<8> = 0xFF.into();
+    /// let y: Bits<8> = 0x00.into();
+    /// assert_eq!(x.add_with_carry(y, true), (0x00.into(), true));
+    /// assert_eq!(y.add_with_carry(y, true), (0x01.into(), false));
+    /// ```
+    pub fn add_with_carry(self, rhs: Self, carry: bool) -> (Self, bool) {
+        let (sum, c1) = self.0.overflowing_add(rhs.0);
+        let (sum, c2) = sum.overflowing_add(carry as u128);
+        let carry_out = c1 || c2 || sum > Self::mask().0;
+        (Self(sum & Self::mask().0), carry_out)
+    }
+    /// Subtract two [Bits] values, and report if a borrow
+    /// was needed (i.e., if `rhs > self`).  The value is the
+    /// usual wrapping difference.
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        (self - rhs, rhs.0 > self.0)
+    }
+    /// Multiply two [Bits] values, and report if the product
+    /// did not fit in `N` bits.  The value is the usual wrapping
+    /// (truncated) product.
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let (product, overflow) = self.0.overflowing_mul(rhs.0);
+        (self * rhs, overflow || product > Self::mask().0)
+    }
+    /// Add two [Bits] values, returning `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (value, false) => Some(value),
+            _ => None,
+        }
+    }
+    /// Subtract two [Bits] values, returning `None` on underflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (value, false) => Some(value),
+            _ => None,
+        }
+    }
+    /// Multiply two [Bits] values, returning `None` on overflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (value, false) => Some(value),
+            _ => None,
+        }
+    }
+    /// Divide two [Bits] values, returning `None` if `rhs` is zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        (rhs.0 != 0).then(|| self / rhs)
+    }
+    /// Add two [Bits] values, clamping the result to the largest
+    /// value that fits in `N` bits.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<8> = 0xF0.into();
+    /// assert_eq!(x.saturating_add(0x20.into()), 0xFF);
+    /// ```
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or(Self::mask())
+    }
+    /// Subtract two [Bits] values, clamping the result at zero.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let x: Bits<8> = 0x10.into();
+    /// assert_eq!(x.saturating_sub(0x20.into()), 0);
+    /// ```
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or_default()
+    }
+    /// Multiply two [Bits] values, clamping the result to the
+    /// largest value that fits in `N` bits.
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).unwrap_or(Self::mask())
+    }
+}
+
+impl<const N: usize> SignedBits<N> {
+    // Report if a (sign extended) i128 result does not fit in N bits.
+    fn out_of_range(value: i128, overflow: bool) -> bool {
+        overflow || value > Self::max_value() || value < Self::min_value()
+    }
+    fn saturate(positive: bool) -> Self {
+        if positive {
+            Self(Self::max_value())
+        } else {
+            Self(Self::min_value())
+        }
+    }
+    /// Add two [SignedBits] values, and report if the (signed)
+    /// result overflowed.  The value is the usual wrapping sum.
+    /// ```
+    /// # use rhdl_bits::SignedBits;
+    /// let x: SignedBits<8> = 100.into();
+    /// assert_eq!(x.overflowing_add(27.into()), (127.into(), false));
+    /// assert_eq!(x.overflowing_add(28.into()), ((-128).into(), true));
+    /// ```
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        self.add_with_carry(rhs, false)
+    }
+    /// Add two [SignedBits] values and a carry in bit, and report
+    /// if the (signed) result overflowed.  Note that for signed
+    /// values, the flag is the 2's complement overflow flag (the
+    /// `V` flag of a typical ALU), not the carry out of the MSB.
+    /// If you need the carry out, use [Bits::add_with_carry] on the
+    /// [SignedBits::as_unsigned] values.
+    /// ```
+    /// # use rhdl_bits::SignedBits;
+    /// let x: SignedBits<8> = 126.into();
+    /// assert_eq!(x.add_with_carry(0.into(), true), (127.into(), false));
+    /// assert_eq!(x.add_with_carry(1.into(), true), ((-128).into(), true));
+    /// ```
+    pub fn add_with_carry(self, rhs: Self, carry: bool) -> (Self, bool) {
+        let value = (self.as_unsigned() + rhs.as_unsigned() + carry as u128).as_signed();
+        // Overflow can only happen if the two operands have the same
+        // sign, and shows up as a result with the opposite sign.
+        let overflow =
+            self.is_negative() == rhs.is_negative() && value.is_negative() != self.is_negative();
+        (value, overflow)
+    }
+    /// Subtract two [SignedBits] values, and report if the
+    /// (signed) result overflowed.  The value is the usual
+    /// wrapping difference.
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (diff, overflow) = self.0.overflowing_sub(rhs.0);
+        (self - rhs, Self::out_of_range(diff, overflow))
+    }
+    /// Multiply two [SignedBits] values, and report if the
+    /// (signed) product did not fit in `N` bits.  The value is
+    /// the usual wrapping (truncated) product.
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let (product, overflow) = self.0.overflowing_mul(rhs.0);
+        (self * rhs, Self::out_of_range(product, overflow))
+    }
+    /// Add two [SignedBits] values, returning `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (value, false) => Some(value),
+            _ => None,
+        }
+    }
+    /// Subtract two [SignedBits] values, returning `None` on overflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (value, false) => Some(value),
+            _ => None,
+        }
+    }
+    /// Multiply two [SignedBits] values, returning `None` on overflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (value, false) => Some(value),
+            _ => None,
+        }
+    }
+    /// Divide two [SignedBits] values, returning `None` if `rhs`
+    /// is zero, or if the division overflows (`MIN / -1`).
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        (rhs.0 != 0 && !(self.0 == Self::min_value() && rhs.0 == -1)).then(|| self / rhs)
+    }
+    /// Add two [SignedBits] values, clamping the result to the
+    /// range of values that fit in `N` bits.
+    /// ```
+    /// # use rhdl_bits::SignedBits;
+    /// let x: SignedBits<8> = 100.into();
+    /// assert_eq!(x.saturating_add(100.into()), 127);
+    /// assert_eq!((-x).saturating_add((-100).into()), -128);
+    /// ```
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs)
+            .unwrap_or_else(|| Self::saturate(rhs.is_non_negative()))
+    }
+    /// Subtract two [SignedBits] values, clamping the result to
+    /// the range of values that fit in `N` bits.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs)
+            .unwrap_or_else(|| Self::saturate(rhs.is_negative()))
+    }
+    /// Multiply two [SignedBits] values, clamping the result to
+    /// the range of values that fit in `N` bits.
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs)
+            .unwrap_or_else(|| Self::saturate(self.is_negative() == rhs.is_negative()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unsigned_overflow_matches_built_in_behavior_for_u8() {
+        for i in 0..=u8::MAX {
+            for j in 0..=u8::MAX {
+                let x = Bits::<8>::from(i as u128);
+                let y = Bits::<8>::from(j as u128);
+                let (v, c) = x.overflowing_add(y);
+                assert_eq!(
+                    (v.0, c),
+                    (i.overflowing_add(j).0 as u128, i.overflowing_add(j).1)
+                );
+                let (v, c) = x.overflowing_sub(y);
+                assert_eq!(
+                    (v.0, c),
+                    (i.overflowing_sub(j).0 as u128, i.overflowing_sub(j).1)
+                );
+                let (v, c) = x.overflowing_mul(y);
+                assert_eq!(
+                    (v.0, c),
+                    (i.overflowing_mul(j).0 as u128, i.overflowing_mul(j).1)
+                );
+                assert_eq!(
+                    x.checked_add(y).map(|x| x.0),
+                    i.checked_add(j).map(u128::from)
+                );
+                assert_eq!(
+                    x.checked_sub(y).map(|x| x.0),
+                    i.checked_sub(j).map(u128::from)
+                );
+                assert_eq!(
+                    x.checked_mul(y).map(|x| x.0),
+                    i.checked_mul(j).map(u128::from)
+                );
+                assert_eq!(
+                    x.checked_div(y).map(|x| x.0),
+                    i.checked_div(j).map(u128::from)
+                );
+                assert_eq!(x.saturating_add(y).0, i.saturating_add(j) as u128);
+                assert_eq!(x.saturating_sub(y).0, i.saturating_sub(j) as u128);
+                assert_eq!(x.saturating_mul(y).0, i.saturating_mul(j) as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_overflow_matches_built_in_behavior_for_i8() {
+        for i in i8::MIN..=i8::MAX {
+            for j in i8::MIN..=i8::MAX {
+                let x = SignedBits::<8>::from(i as i128);
+                let y = SignedBits::<8>::from(j as i128);
+                let (v, o) = x.overflowing_add(y);
+                assert_eq!(
+                    (v.0, o),
+                    (i.overflowing_add(j).0 as i128, i.overflowing_add(j).1)
+                );
+                let (v, o) = x.overflowing_sub(y);
+                assert_eq!(
+                    (v.0, o),
+                    (i.overflowing_sub(j).0 as i128, i.overflowing_sub(j).1)
+                );
+                let (v, o) = x.overflowing_mul(y);
+                assert_eq!(
+                    (v.0, o),
+                    (i.overflowing_mul(j).0 as i128, i.overflowing_mul(j).1)
+                );
+                assert_eq!(
+                    x.checked_add(y).map(|x| x.0),
+                    i.checked_add(j).map(i128::from)
+                );
+                assert_eq!(
+                    x.checked_sub(y).map(|x| x.0),
+                    i.checked_sub(j).map(i128::from)
+                );
+                assert_eq!(
+                    x.checked_mul(y).map(|x| x.0),
+                    i.checked_mul(j).map(i128::from)
+                );
+                assert_eq!(
+                    x.checked_div(y).map(|x| x.0),
+                    i.checked_div(j).map(i128::from)
+                );
+                assert_eq!(x.saturating_add(y).0, i.saturating_add(j) as i128);
+                assert_eq!(x.saturating_sub(y).0, i.saturating_sub(j) as i128);
+                assert_eq!(x.saturating_mul(y).0, i.saturating_mul(j) as i128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow_at_full_width() {
+        let x = Bits::<128>::mask();
+        assert_eq!(x.overflowing_add(Bits::from(1)), (Bits::from(0), true));
+        assert_eq!(x.add_with_carry(Bits::from(0), true), (Bits::from(0), true));
+        assert_eq!(x.saturating_mul(x), x);
+        let x = SignedBits::<128>::from(i128::MAX);
+        assert!(x.overflowing_add(SignedBits::from(1)).1);
+        assert_eq!(x.saturating_add(SignedBits::from(1)), i128::MAX);
+        assert_eq!(x.add_with_carry(SignedBits::from(-1), true), (x, false));
+        let x = SignedBits::<128>::from(i128::MIN);
+        assert_eq!(x.saturating_sub(SignedBits::from(1)), i128::MIN);
+        assert_eq!(x.checked_div(SignedBits::from(-1)), None);
+    }
+
+    #[test]
+    fn test_add_with_carry_chains_into_a_wider_adder() {
+        for i in 0..=u16::MAX {
+            let j = u16::MAX - i / 3;
+            let a = Bits::<16>::from(i as u128);
+            let b = Bits::<16>::from(j as u128);
+            let (lo, carry) = a.slice::<8>(0).add_with_carry(b.slice::<8>(0), false);
+            let (hi, carry) = a.slice::<8>(8).add_with_carry(b.slice::<8>(8), carry);
+            let (sum, expected_carry) = i.overflowing_add(j);
+            assert_eq!(hi.concat::<8, 16>(lo).0, sum as u128);
+            assert_eq!(carry, expected_carry);
+        }
+    }
+}
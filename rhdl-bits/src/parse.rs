@@ -0,0 +1,360 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::bits::Bits;
+use crate::signed_bits::SignedBits;
+
+/// The error returned when parsing a [Bits] or [SignedBits]
+/// value from a string (or building one from bytes) fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseBitsError {
+    /// The string was empty.
+    Empty,
+    /// The width in front of the `'` was not a valid number.
+    InvalidWidth(String),
+    /// The width given in the literal does not match the width
+    /// of the type being parsed.
+    WidthMismatch {
+        /// The width given in the literal.
+        declared: usize,
+        /// The width of the type being parsed.
+        expected: usize,
+    },
+    /// The `'` was not followed by a base (`b`, `o`, `d` or `h`).
+    MissingBase,
+    /// The `'` was followed by something that is not a base.
+    InvalidBase(char),
+    /// There were no digits after the base.
+    NoDigits,
+    /// A digit was not valid for the base of the literal.
+    InvalidDigit {
+        /// The offending digit.
+        digit: char,
+        /// The base of the literal.
+        radix: u32,
+    },
+    /// The literal contained an `x`, `z` or `?` digit, which
+    /// cannot be represented by a 2-state value.
+    UnknownDigit(char),
+    /// The value does not fit in the width of the type.
+    Overflow {
+        /// The width of the type being parsed.
+        width: usize,
+    },
+}
+
+impl Display for ParseBitsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseBitsError::Empty => write!(f, "cannot parse a literal from an empty string"),
+            ParseBitsError::InvalidWidth(width) => write!(f, "invalid literal width '{}'", width),
+            ParseBitsError::WidthMismatch { declared, expected } => write!(
+                f,
+                "literal is declared as {} bits wide, but {} bits were expected",
+                declared, expected
+            ),
+            ParseBitsError::MissingBase => write!(f, "missing base after '"),
+            ParseBitsError::InvalidBase(base) => write!(f, "invalid base '{}'", base),
+            ParseBitsError::NoDigits => write!(f, "literal has no digits"),
+            ParseBitsError::InvalidDigit { digit, radix } => {
+                write!(f, "invalid digit '{}' for a base {} literal", digit, radix)
+            }
+            ParseBitsError::UnknownDigit(digit) => write!(
+                f,
+                "digit '{}' is not supported by a 2-state value (use LogicVec instead)",
+                digit
+            ),
+            ParseBitsError::Overflow { width } => {
+                write!(f, "literal does not fit in {} bits", width)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseBitsError {}
+
+// The pieces of a Verilog literal like `-12'sh4_5`.
+pub(crate) struct Literal<'a> {
+    pub(crate) negative: bool,
+    pub(crate) radix: u32,
+    pub(crate) digits: &'a str,
+}
+
+impl<'a> Literal<'a> {
+    // Split the literal into its parts, and check that the declared
+    // width (if any) matches the expected width.
+    pub(crate) fn split(text: &'a str, expected: usize) -> Result<Self, ParseBitsError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ParseBitsError::Empty);
+        }
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, text),
+        };
+        let Some((width, rest)) = text.split_once('\'') else {
+            // An unsized, unbased literal is a plain decimal value.
+            return Ok(Self {
+                negative,
+                radix: 10,
+                digits: text,
+            });
+        };
+        if !width.is_empty() {
+            let declared = width
+                .replace('_', "")
+                .parse::<usize>()
+                .map_err(|_| ParseBitsError::InvalidWidth(width.into()))?;
+            if declared != expected {
+                return Err(ParseBitsError::WidthMismatch { declared, expected });
+            }
+        }
+        let rest = rest.strip_prefix(['s', 'S']).unwrap_or(rest);
+        let mut chars = rest.chars();
+        let radix = match chars.next() {
+            Some('b' | 'B') => 2,
+            Some('o' | 'O') => 8,
+            Some('d' | 'D') => 10,
+            Some('h' | 'H') => 16,
+            Some(base) => return Err(ParseBitsError::InvalidBase(base)),
+            None => return Err(ParseBitsError::MissingBase),
+        };
+        Ok(Self {
+            negative,
+            radix,
+            digits: chars.as_str(),
+        })
+    }
+    // Compute the (unsigned) value of the digits, and check that it
+    // fits in `width` bits.
+    pub(crate) fn value(&self, width: usize) -> Result<u128, ParseBitsError> {
+        let mut value: u128 = 0;
+        let mut any = false;
+        for digit in self.digits.chars().filter(|c| *c != '_') {
+            if matches!(digit, 'x' | 'X' | 'z' | 'Z' | '?') {
+                return Err(ParseBitsError::UnknownDigit(digit));
+            }
+            let digit_value = digit
+                .to_digit(self.radix)
+                .ok_or(ParseBitsError::InvalidDigit {
+                    digit,
+                    radix: self.radix,
+                })?;
+            value = value
+                .checked_mul(self.radix as u128)
+                .and_then(|v| v.checked_add(digit_value as u128))
+                .ok_or(ParseBitsError::Overflow { width })?;
+            any = true;
+        }
+        if !any {
+            return Err(ParseBitsError::NoDigits);
+        }
+        if value.checked_shr(width as u32).unwrap_or(0) != 0 {
+            return Err(ParseBitsError::Overflow { width });
+        }
+        Ok(value)
+    }
+    // Compute the 4-state value of the digits as a pair of (aval, bval)
+    // planes, using the same encoding as the Verilog VPI (0 = (0, 0),
+    // 1 = (1, 0), Z = (0, 1) and X = (1, 1)).  As in Verilog, an
+    // unknown leading digit is extended to fill the width.
+    pub(crate) fn planes(&self, width: usize) -> Result<(u128, u128), ParseBitsError> {
+        let mask = u128::MAX.checked_shr(128 - width as u32).unwrap_or(0);
+        let unknown = |digit: char| match digit {
+            'x' | 'X' => Some((u128::MAX, u128::MAX)),
+            'z' | 'Z' | '?' => Some((0, u128::MAX)),
+            _ => None,
+        };
+        let mut digits = self.digits.chars().filter(|c| *c != '_').peekable();
+        let Some(&leading) = digits.peek() else {
+            return Err(ParseBitsError::NoDigits);
+        };
+        if self.radix == 10 {
+            // A decimal literal is either a number, or a single x or z
+            if let Some((aval, bval)) = unknown(leading) {
+                digits.next();
+                if let Some(digit) = digits.next() {
+                    return Err(ParseBitsError::InvalidDigit { digit, radix: 10 });
+                }
+                return Ok((aval & mask, bval & mask));
+            }
+            return Ok((self.value(width)?, 0));
+        }
+        let digit_bits = self.radix.trailing_zeros();
+        let digit_mask = (1 << digit_bits) - 1;
+        let (mut aval, mut bval) = (0_u128, 0_u128);
+        let mut used = 0;
+        for digit in digits {
+            if (aval | bval).checked_shr(128 - digit_bits).unwrap_or(0) != 0 {
+                return Err(ParseBitsError::Overflow { width });
+            }
+            let (a, b) = match unknown(digit) {
+                Some(planes) => planes,
+                None => (
+                    digit
+                        .to_digit(self.radix)
+                        .ok_or(ParseBitsError::InvalidDigit {
+                            digit,
+                            radix: self.radix,
+                        })? as u128,
+                    0,
+                ),
+            };
+            aval = (aval << digit_bits) | (a & digit_mask);
+            bval = (bval << digit_bits) | (b & digit_mask);
+            used += digit_bits;
+        }
+        if let Some((a, b)) = unknown(leading) {
+            if used - digit_bits > width as u32 {
+                return Err(ParseBitsError::Overflow { width });
+            }
+            let fill = u128::MAX.checked_shl(used).unwrap_or(0);
+            return Ok(((aval | (a & fill)) & mask, (bval | (b & fill)) & mask));
+        }
+        if (aval | bval).checked_shr(width as u32).unwrap_or(0) != 0 {
+            return Err(ParseBitsError::Overflow { width });
+        }
+        Ok((aval, bval))
+    }
+}
+
+/// Parse a [Bits] value from a Verilog style literal, such as
+/// `8'hA5`, `4'b1001`, `12'o7_77` or `16'd1234`.  The width is
+/// optional, but if it is given, it must match `N`.  A leading
+/// `-` negates the value (with the usual 2's complement wrapping),
+/// and a plain decimal number (like `165`) is also accepted.
+/// ```
+/// # use rhdl_bits::Bits;
+/// let x: Bits<8> = "8'hA5".parse().unwrap();
+/// assert_eq!(x, 0xA5);
+/// let y: Bits<8> = "-8'd1".parse().unwrap();
+/// assert_eq!(y, 0xFF);
+/// assert!("4'hA5".parse::<Bits<8>>().is_err());
+/// ```
+impl<const N: usize> FromStr for Bits<N> {
+    type Err = ParseBitsError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let literal = Literal::split(s, N)?;
+        let value = Bits::<N>(literal.value(N)?);
+        if literal.negative {
+            Ok(Bits(0) - value)
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+/// Parse a [SignedBits] value from a Verilog style literal, such
+/// as `-12'sd45` or `8'shFF`.  The digits give the 2's complement
+/// bit pattern of the value (so `8'hFF` is `-1`), and a leading `-`
+/// negates it.  The width is optional, but if it is given, it must
+/// match `N`.
+/// ```
+/// # use rhdl_bits::SignedBits;
+/// let x: SignedBits<12> = "-12'sd45".parse().unwrap();
+/// assert_eq!(x, -45);
+/// let y: SignedBits<8> = "8'shFF".parse().unwrap();
+/// assert_eq!(y, -1);
+/// ```
+impl<const N: usize> FromStr for SignedBits<N> {
+    type Err = ParseBitsError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let literal = Literal::split(s, N)?;
+        let value = Bits::<N>(literal.value(N)?).as_signed();
+        if literal.negative {
+            Ok(-value)
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_all_bases() {
+        assert_eq!("8'b1010_0101".parse::<Bits<8>>(), Ok(Bits(0xA5)));
+        assert_eq!("8'o245".parse::<Bits<8>>(), Ok(Bits(0xA5)));
+        assert_eq!("8'd165".parse::<Bits<8>>(), Ok(Bits(0xA5)));
+        assert_eq!("8'hA5".parse::<Bits<8>>(), Ok(Bits(0xA5)));
+        assert_eq!("8'Ha5".parse::<Bits<8>>(), Ok(Bits(0xA5)));
+        assert_eq!("'ha5".parse::<Bits<8>>(), Ok(Bits(0xA5)));
+        assert_eq!("165".parse::<Bits<8>>(), Ok(Bits(0xA5)));
+        assert_eq!(" 8'sh_a_5 ".parse::<Bits<8>>(), Ok(Bits(0xA5)));
+    }
+
+    #[test]
+    fn test_parse_round_trips_through_format() {
+        for i in 0..=u8::MAX as u128 {
+            let x = Bits::<8>(i);
+            assert_eq!(format!("8'h{:x}", x).parse::<Bits<8>>(), Ok(x));
+            assert_eq!(format!("8'b{:b}", x).parse::<Bits<8>>(), Ok(x));
+            assert_eq!(format!("8'd{}", x).parse::<Bits<8>>(), Ok(x));
+        }
+        let x = Bits::<128>::mask();
+        assert_eq!(format!("128'h{:x}", x).parse::<Bits<128>>(), Ok(x));
+    }
+
+    #[test]
+    fn test_parse_signed_values() {
+        for i in i8::MIN..=i8::MAX {
+            let x = SignedBits::<8>::from(i as i128);
+            let text = if i < 0 {
+                format!("-8'sd{}", -(i as i16))
+            } else {
+                format!("8'sd{}", i)
+            };
+            assert_eq!(text.parse::<SignedBits<8>>(), Ok(x));
+            assert_eq!(format!("{}", i).parse::<SignedBits<8>>(), Ok(x));
+            assert_eq!(format!("8'h{:x}", i as u8).parse::<SignedBits<8>>(), Ok(x));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<Bits<8>>(), Err(ParseBitsError::Empty));
+        assert_eq!(
+            "4'hA".parse::<Bits<8>>(),
+            Err(ParseBitsError::WidthMismatch {
+                declared: 4,
+                expected: 8
+            })
+        );
+        assert_eq!(
+            "a'hA".parse::<Bits<8>>(),
+            Err(ParseBitsError::InvalidWidth("a".into()))
+        );
+        assert_eq!("8'".parse::<Bits<8>>(), Err(ParseBitsError::MissingBase));
+        assert_eq!(
+            "8'qA".parse::<Bits<8>>(),
+            Err(ParseBitsError::InvalidBase('q'))
+        );
+        assert_eq!("8'h".parse::<Bits<8>>(), Err(ParseBitsError::NoDigits));
+        assert_eq!("8'h__".parse::<Bits<8>>(), Err(ParseBitsError::NoDigits));
+        assert_eq!(
+            "8'b102".parse::<Bits<8>>(),
+            Err(ParseBitsError::InvalidDigit {
+                digit: '2',
+                radix: 2
+            })
+        );
+        assert_eq!(
+            "4'b10x1".parse::<Bits<4>>(),
+            Err(ParseBitsError::UnknownDigit('x'))
+        );
+        assert_eq!(
+            "8'h1FF".parse::<Bits<8>>(),
+            Err(ParseBitsError::Overflow { width: 8 })
+        );
+        assert_eq!(
+            "8'd256".parse::<SignedBits<8>>(),
+            Err(ParseBitsError::Overflow { width: 8 })
+        );
+        assert_eq!(
+            "128'd340282366920938463463374607431768211456".parse::<Bits<128>>(),
+            Err(ParseBitsError::Overflow { width: 128 })
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use crate::bits::Bits;
+use crate::signed_bits::SignedBits;
+
+impl<const N: usize> Bits<N> {
+    /// Returns true if any of the bits are set to 0.  This
+    /// is the NAND reduction (`~&x` in Verilog).
+    pub fn nand(self) -> bool {
+        !self.all()
+    }
+    /// Returns true if all of the bits are set to 0.  This
+    /// is the NOR reduction (`~|x` in Verilog).
+    pub fn nor(self) -> bool {
+        !self.any()
+    }
+    /// Computes the xnor of all of the bits in the value.
+    /// This is the XNOR reduction (`~^x` in Verilog).
+    pub fn xnor(self) -> bool {
+        !self.xor()
+    }
+    /// Returns the number of bits set to 1.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let bits: Bits<8> = 0b1101_1010.into();
+    /// assert_eq!(bits.count_ones(), 5);
+    /// ```
+    pub fn count_ones(self) -> u32 {
+        self.0.count_ones()
+    }
+    /// Returns the number of bits set to 0.
+    pub fn count_zeros(self) -> u32 {
+        N as u32 - self.count_ones()
+    }
+    /// Returns the number of leading (most significant) zeros
+    /// in the `N` bit value.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let bits: Bits<12> = 0b0000_0101_1010.into();
+    /// assert_eq!(bits.leading_zeros(), 5);
+    /// ```
+    pub fn leading_zeros(self) -> u32 {
+        self.0.leading_zeros() - (128 - N as u32)
+    }
+    /// Returns the number of leading (most significant) ones
+    /// in the `N` bit value.
+    pub fn leading_ones(self) -> u32 {
+        (!self).leading_zeros()
+    }
+    /// Returns the number of trailing (least significant) zeros.
+    /// If the value is zero, this is `N`.
+    pub fn trailing_zeros(self) -> u32 {
+        self.0.trailing_zeros().min(N as u32)
+    }
+    /// Returns the number of trailing (least significant) ones.
+    pub fn trailing_ones(self) -> u32 {
+        self.0.trailing_ones()
+    }
+    /// Reverse the order of the `N` bits, so that the LSB
+    /// becomes the MSB.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let bits: Bits<6> = 0b11_0100.into();
+    /// assert_eq!(bits.reverse_bits(), 0b00_1011);
+    /// ```
+    pub fn reverse_bits(self) -> Self {
+        Self(self.0.reverse_bits() >> (128 - N))
+    }
+    /// Rotate the `N` bits to the left by `n` places.  The bits
+    /// shifted out of the MSB are shifted back in at the LSB.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let bits: Bits<6> = 0b11_0100.into();
+    /// assert_eq!(bits.rotate_left(2), 0b01_0011);
+    /// ```
+    pub fn rotate_left(self, n: u32) -> Self {
+        let n = n as usize % N;
+        if n == 0 {
+            self
+        } else {
+            Self(((self.0 << n) | (self.0 >> (N - n))) & Self::mask().0)
+        }
+    }
+    /// Rotate the `N` bits to the right by `n` places.  The bits
+    /// shifted out of the LSB are shifted back in at the MSB.
+    pub fn rotate_right(self, n: u32) -> Self {
+        self.rotate_left(N as u32 - (n as usize % N) as u32)
+    }
+    /// Returns true if exactly one bit is set.
+    pub fn is_one_hot(self) -> bool {
+        self.count_ones() == 1
+    }
+    /// Decode a binary value into a one-hot value, with
+    /// only bit `self` set.  Panics if the value is out of
+    /// range for an `M` bit result.
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let bits: Bits<3> = 5.into();
+    /// let one_hot: Bits<8> = bits.one_hot();
+    /// assert_eq!(one_hot, 0b0010_0000);
+    /// ```
+    pub fn one_hot<const M: usize>(self) -> Bits<M> {
+        const { assert!(M <= 128) }
+        assert!(self.0 < M as u128);
+        Bits(1 << self.0)
+    }
+    /// Encode the index of the least significant bit that is set,
+    /// as in a fixed priority arbiter, where bit 0 has the highest
+    /// priority.  Returns `None` if no bits are set.  For a one-hot
+    /// value, this is the inverse of [Bits::one_hot].
+    /// ```
+    /// # use rhdl_bits::Bits;
+    /// let requests: Bits<8> = 0b0110_1000.into();
+    /// let grant: Option<Bits<3>> = requests.priority_encode();
+    /// assert_eq!(grant, Some(3.into()));
+    /// ```
+    pub fn priority_encode<const M: usize>(self) -> Option<Bits<M>> {
+        self.any()
+            .then(|| Bits::<M>::from(self.trailing_zeros() as u128))
+    }
+}
+
+impl<const N: usize> SignedBits<N> {
+    /// Returns true if any of the bits are set to 1.
+    pub fn any(self) -> bool {
+        self.as_unsigned().any()
+    }
+    /// Returns true if all of the bits are set to 1.
+    pub fn all(self) -> bool {
+        self.as_unsigned().all()
+    }
+    /// Computes the xor of all of the bits in the value.
+    pub fn xor(self) -> bool {
+        self.as_unsigned().xor()
+    }
+    /// Returns true if any of the bits are set to 0.
+    pub fn nand(self) -> bool {
+        self.as_unsigned().nand()
+    }
+    /// Returns true if all of the bits are set to 0.
+    pub fn nor(self) -> bool {
+        self.as_unsigned().nor()
+    }
+    /// Computes the xnor of all of the bits in the value.
+    pub fn xnor(self) -> bool {
+        self.as_unsigned().xnor()
+    }
+    /// Returns the number of bits set to 1 in the `N` bit
+    /// (2's complement) representation of the value.
+    /// ```
+    /// # use rhdl_bits::SignedBits;
+    /// let x: SignedBits<8> = (-1).into();
+    /// assert_eq!(x.count_ones(), 8);
+    /// ```
+    pub fn count_ones(self) -> u32 {
+        self.as_unsigned().count_ones()
+    }
+    /// Returns the number of bits set to 0.
+    pub fn count_zeros(self) -> u32 {
+        self.as_unsigned().count_zeros()
+    }
+    /// Returns the number of leading (most significant) zeros.
+    pub fn leading_zeros(self) -> u32 {
+        self.as_unsigned().leading_zeros()
+    }
+    /// Returns the number of leading (most significant) ones.
+    pub fn leading_ones(self) -> u32 {
+        self.as_unsigned().leading_ones()
+    }
+    /// Returns the number of trailing (least significant) zeros.
+    /// If the value is zero, this is `N`.
+    pub fn trailing_zeros(self) -> u32 {
+        self.as_unsigned().trailing_zeros()
+    }
+    /// Returns the number of trailing (least significant) ones.
+    pub fn trailing_ones(self) -> u32 {
+        self.as_unsigned().trailing_ones()
+    }
+    /// Reverse the order of the `N` bits, so that the LSB
+    /// becomes the MSB (and thus the sign bit).
+    pub fn reverse_bits(self) -> Self {
+        self.as_unsigned().reverse_bits().as_signed()
+    }
+    /// Rotate the `N` bits to the left by `n` places.
+    pub fn rotate_left(self, n: u32) -> Self {
+        self.as_unsigned().rotate_left(n).as_signed()
+    }
+    /// Rotate the `N` bits to the right by `n` places.
+    pub fn rotate_right(self, n: u32) -> Self {
+        self.as_unsigned().rotate_right(n).as_signed()
+    }
+    /// Returns true if exactly one bit is set.
+    pub fn is_one_hot(self) -> bool {
+        self.as_unsigned().is_one_hot()
+    }
+    /// Encode the index of the least significant bit that is set.
+    /// Returns `None` if no bits are set.
+    pub fn priority_encode<const M: usize>(self) -> Option<Bits<M>> {
+        self.as_unsigned().priority_encode()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unsigned_helpers_match_built_in_behavior_for_u8() {
+        for i in 0..=u8::MAX {
+            let x = Bits::<8>::from(i as u128);
+            assert_eq!(x.count_ones(), i.count_ones());
+            assert_eq!(x.count_zeros(), i.count_zeros());
+            assert_eq!(x.leading_zeros(), i.leading_zeros());
+            assert_eq!(x.leading_ones(), i.leading_ones());
+            assert_eq!(x.trailing_zeros(), i.trailing_zeros());
+            assert_eq!(x.trailing_ones(), i.trailing_ones());
+            assert_eq!(x.reverse_bits().0, i.reverse_bits() as u128);
+            for n in 0..20 {
+                assert_eq!(x.rotate_left(n).0, i.rotate_left(n) as u128);
+                assert_eq!(x.rotate_right(n).0, i.rotate_right(n) as u128);
+            }
+            assert_eq!(x.nand(), i != u8::MAX);
+            assert_eq!(x.nor(), i == 0);
+            assert_eq!(x.xnor(), i.count_ones() % 2 == 0);
+            assert_eq!(x.is_one_hot(), i.is_power_of_two());
+            assert_eq!(
+                x.priority_encode::<3>().map(|x| x.0),
+                (i != 0).then(|| i.trailing_zeros() as u128)
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsigned_helpers_match_u128_for_odd_widths() {
+        for i in 0..(1 << 5) {
+            let x = Bits::<5>::from(i);
+            let reference = i << (128 - 5);
+            assert_eq!(x.leading_zeros(), reference.leading_zeros().min(5));
+            assert_eq!(x.leading_ones(), reference.leading_ones());
+            assert_eq!(x.trailing_zeros(), i.trailing_zeros().min(5));
+            assert_eq!(x.reverse_bits().0, i.reverse_bits() >> (128 - 5));
+            for n in 0..12 {
+                let expected = ((i | i << 5) << (n % 5)) >> 5 & 0b1_1111;
+                assert_eq!(x.rotate_left(n).0, expected);
+                assert_eq!(x.rotate_left(n).rotate_right(n), x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsigned_helpers_match_u128_at_full_width() {
+        for i in [0, 1, 0xDEAD_BEEF, u128::MAX >> 1, 1 << 127, u128::MAX] {
+            let x = Bits::<128>::from(i);
+            assert_eq!(x.count_ones(), i.count_ones());
+            assert_eq!(x.leading_zeros(), i.leading_zeros());
+            assert_eq!(x.leading_ones(), i.leading_ones());
+            assert_eq!(x.trailing_zeros(), i.trailing_zeros());
+            assert_eq!(x.trailing_ones(), i.trailing_ones());
+            assert_eq!(x.reverse_bits().0, i.reverse_bits());
+            for n in [0, 1, 63, 64, 127, 128, 200] {
+                assert_eq!(x.rotate_left(n).0, i.rotate_left(n));
+                assert_eq!(x.rotate_right(n).0, i.rotate_right(n));
+            }
+        }
+    }
+
+    #[test]
+    fn test_one_hot_round_trip() {
+        for i in 0..16 {
+            let x = Bits::<4>::from(i);
+            let one_hot: Bits<16> = x.one_hot();
+            assert!(one_hot.is_one_hot());
+            assert_eq!(one_hot.priority_encode::<4>(), Some(x));
+        }
+        assert_eq!(Bits::<16>::from(0).priority_encode::<4>(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_one_hot_out_of_range_panics() {
+        let _: Bits<4> = Bits::<3>::from(4).one_hot();
+    }
+
+    #[test]
+    fn test_signed_helpers_match_built_in_behavior_for_i8() {
+        for i in i8::MIN..=i8::MAX {
+            let x = SignedBits::<8>::from(i as i128);
+            assert_eq!(x.any(), i != 0);
+            assert_eq!(x.all(), i == -1);
+            assert_eq!(x.xor(), i.count_ones() % 2 == 1);
+            assert_eq!(x.nand(), i != -1);
+            assert_eq!(x.nor(), i == 0);
+            assert_eq!(x.xnor(), i.count_ones() % 2 == 0);
+            assert_eq!(x.count_ones(), i.count_ones());
+            assert_eq!(x.count_zeros(), i.count_zeros());
+            assert_eq!(x.leading_zeros(), i.leading_zeros());
+            assert_eq!(x.leading_ones(), i.leading_ones());
+            assert_eq!(x.trailing_zeros(), i.trailing_zeros());
+            assert_eq!(x.trailing_ones(), i.trailing_ones());
+            assert_eq!(x.reverse_bits().0, i.reverse_bits() as i128);
+            for n in 0..20 {
+                assert_eq!(x.rotate_left(n).0, i.rotate_left(n) as i128);
+                assert_eq!(x.rotate_right(n).0, i.rotate_right(n) as i128);
+            }
+            assert_eq!(x.is_one_hot(), i.count_ones() == 1);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::ops::Rem;
+use std::ops::RemAssign;
+
+use crate::bits::Bits;
+use crate::signed_bits::SignedBits;
+
+impl<const N: usize> Rem<u128> for Bits<N> {
+    type Output = Self;
+    fn rem(self, rhs: u128) -> Self::Output {
+        self % Bits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> Rem<Bits<N>> for u128 {
+    type Output = Bits<N>;
+    fn rem(self, rhs: Bits<N>) -> Self::Output {
+        Bits::<N>::from(self) % rhs
+    }
+}
+
+impl<const N: usize> Rem<Bits<N>> for Bits<N> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self::Output {
+        // The remainder of a division by zero is the dividend.
+        if rhs.0 == 0 {
+            self
+        } else {
+            Self(self.0 % rhs.0)
+        }
+    }
+}
+
+impl<const N: usize> RemAssign<Bits<N>> for Bits<N> {
+    fn rem_assign(&mut self, rhs: Bits<N>) {
+        *self = *self % rhs;
+    }
+}
+
+impl<const N: usize> RemAssign<u128> for Bits<N> {
+    fn rem_assign(&mut self, rhs: u128) {
+        *self = *self % rhs;
+    }
+}
+
+impl<const N: usize> Rem<i128> for SignedBits<N> {
+    type Output = Self;
+    fn rem(self, rhs: i128) -> Self::Output {
+        self % SignedBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> Rem<SignedBits<N>> for i128 {
+    type Output = SignedBits<N>;
+    fn rem(self, rhs: SignedBits<N>) -> Self::Output {
+        SignedBits::<N>::from(self) % rhs
+    }
+}
+
+impl<const N: usize> Rem<SignedBits<N>> for SignedBits<N> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self::Output {
+        // The remainder takes the sign of the dividend (as in Rust
+        // and Verilog).  The remainder of a division by zero is the
+        // dividend, and MIN % -1 is zero.
+        if rhs.0 == 0 {
+            self
+        } else {
+            Self(i128::wrapping_rem(self.0, rhs.0))
+        }
+    }
+}
+
+impl<const N: usize> RemAssign<i128> for SignedBits<N> {
+    fn rem_assign(&mut self, rhs: i128) {
+        *self = *self % rhs;
+    }
+}
+
+impl<const N: usize> RemAssign<SignedBits<N>> for SignedBits<N> {
+    fn rem_assign(&mut self, rhs: SignedBits<N>) {
+        *self = *self % rhs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rem_bits() {
+        let bits: Bits<8> = 0b1101_1010.into();
+        assert_eq!(bits % 5, 218 % 5);
+        assert_eq!(250 % bits, 250 % 218);
+        let mut bits: Bits<54> = 0b1101_1010.into();
+        bits %= 100;
+        assert_eq!(bits.0, 18);
+    }
+
+    #[test]
+    fn test_unsigned_rem_matches_built_in_behavior_for_u8() {
+        for i in 0..=u8::MAX {
+            for j in 1..=u8::MAX {
+                let k = Bits::<8>::from(i as u128) % Bits::<8>::from(j as u128);
+                assert_eq!(k.0, (i % j) as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_rem_matches_built_in_behavior_for_i8() {
+        for i in i8::MIN..=i8::MAX {
+            for j in i8::MIN..=i8::MAX {
+                if j == 0 {
+                    continue;
+                }
+                let i_as_signed = SignedBits::<8>::from(i as i128);
+                let j_as_signed = SignedBits::<8>::from(j as i128);
+                let k_as_signed = i_as_signed % j_as_signed;
+                let k = i8::wrapping_rem(i, j);
+                assert_eq!(k_as_signed.0, k as i128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rem_by_zero_is_dividend() {
+        let bits: Bits<8> = 0b1101_1010.into();
+        assert_eq!(bits % 0, bits);
+        let mut x = SignedBits::<8>::from(-7);
+        x %= 0;
+        assert_eq!(x, -7);
+    }
+
+    #[test]
+    fn test_div_rem_identity_holds() {
+        for i in i8::MIN..=i8::MAX {
+            for j in i8::MIN..=i8::MAX {
+                let x = SignedBits::<8>::from(i as i128);
+                let y = SignedBits::<8>::from(j as i128);
+                assert_eq!((x / y) * y + x % y, x);
+            }
+        }
+        for i in 0..=u8::MAX {
+            for j in 0..=u8::MAX {
+                let x = Bits::<8>::from(i as u128);
+                let y = Bits::<8>::from(j as u128);
+                assert_eq!((x / y) * y + x % y, x);
+            }
+        }
+    }
+}
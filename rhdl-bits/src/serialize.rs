@@ -0,0 +1,174 @@
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::bits::Bits;
+use crate::signed_bits::SignedBits;
+
+// Human readable formats (like JSON) get a Verilog style hex literal,
+// which avoids the loss of precision that comes from storing a
+// 128 bit value as a JSON number.  Binary formats get the raw integer.
+// Either form is accepted when deserializing, and checked against
+// the width of the type.
+
+impl<const N: usize> Serialize for Bits<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{}'h{:x}", N, self.0))
+        } else {
+            serializer.serialize_u128(self.0)
+        }
+    }
+}
+
+impl<const N: usize> Serialize for SignedBits<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{}'sh{:x}", N, self.as_unsigned().0))
+        } else {
+            serializer.serialize_i128(self.0)
+        }
+    }
+}
+
+struct BitsVisitor<T>(PhantomData<T>);
+
+impl<'de, const N: usize> Visitor<'de> for BitsVisitor<Bits<N>> {
+    type Value = Bits<N>;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "an unsigned integer or literal that fits in {} bits", N)
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        self.visit_u128(v as u128)
+    }
+
+    fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+        if v & !Bits::<N>::mask().0 != 0 {
+            return Err(E::custom(format!("{} does not fit in {} bits", v, N)));
+        }
+        Ok(Bits(v))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        self.visit_i128(v as i128)
+    }
+
+    fn visit_i128<E: Error>(self, v: i128) -> Result<Self::Value, E> {
+        let v = u128::try_from(v)
+            .map_err(|_| E::custom(format!("{} is negative, but Bits is unsigned", v)))?;
+        self.visit_u128(v)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+impl<'de, const N: usize> Visitor<'de> for BitsVisitor<SignedBits<N>> {
+    type Value = SignedBits<N>;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "a signed integer or literal that fits in {} bits", N)
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        self.visit_i128(v as i128)
+    }
+
+    fn visit_i128<E: Error>(self, v: i128) -> Result<Self::Value, E> {
+        if v > SignedBits::<N>::max_value() || v < SignedBits::<N>::min_value() {
+            return Err(E::custom(format!("{} does not fit in {} bits", v, N)));
+        }
+        Ok(SignedBits(v))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        self.visit_i128(v as i128)
+    }
+
+    fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+        let v = i128::try_from(v)
+            .map_err(|_| E::custom(format!("{} does not fit in {} bits", v, N)))?;
+        self.visit_i128(v)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Bits<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BitsVisitor::<Self>(PhantomData))
+        } else {
+            deserializer.deserialize_u128(BitsVisitor::<Self>(PhantomData))
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for SignedBits<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BitsVisitor::<Self>(PhantomData))
+        } else {
+            deserializer.deserialize_i128(BitsVisitor::<Self>(PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    #[test]
+    fn test_readable_round_trip() {
+        assert_tokens(&Bits::<8>(0xA5).readable(), &[Token::Str("8'ha5")]);
+        assert_tokens(&SignedBits::<8>(-2).readable(), &[Token::Str("8'shfe")]);
+        assert_tokens(&SignedBits::<8>(5).readable(), &[Token::Str("8'sh5")]);
+    }
+
+    #[test]
+    fn test_compact_integers() {
+        assert_de_tokens(&Bits::<8>(0xA5).compact(), &[Token::U64(0xA5)]);
+        assert_de_tokens(&SignedBits::<8>(-2).compact(), &[Token::I64(-2)]);
+    }
+
+    #[test]
+    fn test_integers_are_accepted() {
+        assert_de_tokens(&Bits::<8>(0xA5).readable(), &[Token::U64(0xA5)]);
+        assert_de_tokens(&Bits::<8>(0xA5).readable(), &[Token::I32(0xA5)]);
+        assert_de_tokens(&SignedBits::<8>(-2).readable(), &[Token::I8(-2)]);
+        assert_de_tokens(&SignedBits::<8>(127).readable(), &[Token::U8(127)]);
+        assert_de_tokens(&Bits::<8>(0xA5).readable(), &[Token::Str("'hA5")]);
+    }
+
+    #[test]
+    fn test_widths_are_checked() {
+        assert_de_tokens_error::<serde_test::Readable<Bits<8>>>(
+            &[Token::U64(256)],
+            "256 does not fit in 8 bits",
+        );
+        assert_de_tokens_error::<serde_test::Readable<Bits<8>>>(
+            &[Token::I64(-1)],
+            "-1 is negative, but Bits is unsigned",
+        );
+        assert_de_tokens_error::<serde_test::Readable<SignedBits<8>>>(
+            &[Token::I64(-129)],
+            "-129 does not fit in 8 bits",
+        );
+        assert_de_tokens_error::<serde_test::Readable<Bits<8>>>(
+            &[Token::Str("4'hA")],
+            "literal is declared as 4 bits wide, but 8 bits were expected",
+        );
+        assert_de_tokens_error::<serde_test::Compact<Bits<4>>>(
+            &[Token::U64(16)],
+            "16 does not fit in 4 bits",
+        );
+    }
+}
@@ -3,6 +3,8 @@ use std::ops::ShlAssign;
 
 use crate::bits::Bits;
 use crate::signed_bits::SignedBits;
+use crate::signed_wide_bits::SignedWideBits;
+use crate::wide_bits::WideBits;
 
 impl<const N: usize> Shl<u128> for Bits<N> {
     type Output = Self;
@@ -14,15 +16,16 @@ impl<const N: usize> Shl<u128> for Bits<N> {
 impl<const N: usize> Shl<Bits<N>> for u128 {
     type Output = Bits<N>;
     fn shl(self, rhs: Bits<N>) -> Self::Output {
-        assert!(N <= 8, "Shift amount must be less than 8 bits");
+        const { assert!(N <= 8, "Shift amount must be less than 8 bits") }
         Bits::<N>::from(self) << rhs
     }
 }
 
 impl<const M: usize, const N: usize> Shl<Bits<M>> for Bits<N> {
     type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn shl(self, rhs: Bits<M>) -> Self::Output {
-        assert!(M <= 8, "Shift amount must be less than 8 bits");
+        const { assert!(M <= 8, "Shift amount must be less than 8 bits") }
         Self(u128::wrapping_shl(self.0, rhs.0 as u32) & Self::mask().0)
     }
 }
@@ -49,7 +52,7 @@ impl<const N: usize> Shl<u128> for SignedBits<N> {
 impl<const N: usize> Shl<Bits<N>> for i128 {
     type Output = SignedBits<N>;
     fn shl(self, rhs: Bits<N>) -> Self::Output {
-        assert!(N <= 8, "Shift amount must be less than 8 bits");
+        const { assert!(N <= 8, "Shift amount must be less than 8 bits") }
         SignedBits::<N>::from(self) << rhs
     }
 }
@@ -57,7 +60,7 @@ impl<const N: usize> Shl<Bits<N>> for i128 {
 impl<const M: usize, const N: usize> Shl<Bits<M>> for SignedBits<N> {
     type Output = Self;
     fn shl(self, rhs: Bits<M>) -> Self::Output {
-        assert!(M <= 8, "Shift amount must be less than 8 bits");
+        const { assert!(M <= 8, "Shift amount must be less than 8 bits") }
         (self.as_unsigned() << rhs).as_signed()
     }
 }
@@ -74,6 +77,58 @@ impl<const N: usize> ShlAssign<u128> for SignedBits<N> {
     }
 }
 
+impl<const M: usize, const N: usize> Shl<Bits<M>> for WideBits<N> {
+    type Output = Self;
+    fn shl(self, rhs: Bits<M>) -> Self::Output {
+        self << rhs.0
+    }
+}
+
+impl<const N: usize> Shl<u128> for WideBits<N> {
+    type Output = Self;
+    fn shl(self, rhs: u128) -> Self::Output {
+        self.shl_by(rhs.min(N as u128) as usize)
+    }
+}
+
+impl<const M: usize, const N: usize> ShlAssign<Bits<M>> for WideBits<N> {
+    fn shl_assign(&mut self, rhs: Bits<M>) {
+        *self = *self << rhs;
+    }
+}
+
+impl<const N: usize> ShlAssign<u128> for WideBits<N> {
+    fn shl_assign(&mut self, rhs: u128) {
+        *self = *self << rhs;
+    }
+}
+
+impl<const M: usize, const N: usize> Shl<Bits<M>> for SignedWideBits<N> {
+    type Output = Self;
+    fn shl(self, rhs: Bits<M>) -> Self::Output {
+        (self.as_unsigned() << rhs).as_signed()
+    }
+}
+
+impl<const N: usize> Shl<u128> for SignedWideBits<N> {
+    type Output = Self;
+    fn shl(self, rhs: u128) -> Self::Output {
+        (self.as_unsigned() << rhs).as_signed()
+    }
+}
+
+impl<const M: usize, const N: usize> ShlAssign<Bits<M>> for SignedWideBits<N> {
+    fn shl_assign(&mut self, rhs: Bits<M>) {
+        *self = *self << rhs;
+    }
+}
+
+impl<const N: usize> ShlAssign<u128> for SignedWideBits<N> {
+    fn shl_assign(&mut self, rhs: u128) {
+        *self = *self << rhs;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -121,4 +176,39 @@ mod test {
             assert_eq!(bits.0, ((-38_i128 << shift) as i8).into());
         }
     }
+
+    #[test]
+    fn test_shl_wide_bits_matches_bits() {
+        let value = 0xDEAD_BEEF_CAFE_BABE_1234_5678_9ABC_DEF0_u128;
+        for shift in 0..128 {
+            let wide = WideBits::<128>::from(value) << shift;
+            let narrow = Bits::<128>::from(value) << shift;
+            assert_eq!(wide, narrow.raw());
+        }
+    }
+
+    #[test]
+    fn test_shl_wide_bits_across_limbs() {
+        let mut bits = WideBits::<512>::from(1);
+        bits <<= 300;
+        assert!(bits.get_bit(300));
+        let shift: Bits<9> = 211.into();
+        bits <<= shift;
+        assert!(bits.get_bit(511));
+        assert_eq!(bits << 1, 0);
+        assert_eq!(WideBits::<512>::mask() << 1000, 0);
+    }
+
+    #[test]
+    fn test_shl_signed_wide_bits() {
+        for shift in 0..10 {
+            let bits = SignedWideBits::<8>::from(-38);
+            let result = bits << shift;
+            assert_eq!(result, ((-38_i128 << shift) as i8) as i128);
+        }
+        let mut bits = SignedWideBits::<256>::from(-1);
+        bits <<= 255;
+        assert!(bits.is_negative());
+        assert_eq!(bits.slice::<8>(0), 0);
+    }
 }
@@ -3,6 +3,8 @@ use std::ops::ShrAssign;
 
 use crate::bits::Bits;
 use crate::signed_bits::SignedBits;
+use crate::signed_wide_bits::SignedWideBits;
+use crate::wide_bits::WideBits;
 
 impl<const N: usize> Shr<u128> for Bits<N> {
     type Output = Self;
@@ -14,15 +16,16 @@ impl<const N: usize> Shr<u128> for Bits<N> {
 impl<const N: usize> Shr<Bits<N>> for u128 {
     type Output = Bits<N>;
     fn shr(self, rhs: Bits<N>) -> Self::Output {
-        assert!(N <= 8, "Shift amount must be less than 8 bits");
+        const { assert!(N <= 8, "Shift amount must be less than 8 bits") }
         Bits::<N>::from(self) >> rhs
     }
 }
 
 impl<const M: usize, const N: usize> Shr<Bits<M>> for Bits<N> {
     type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn shr(self, rhs: Bits<M>) -> Self::Output {
-        assert!(M <= 8, "Shift amount must be less than 8 bits");
+        const { assert!(M <= 8, "Shift amount must be less than 8 bits") }
         Self(u128::wrapping_shr(self.0, rhs.0 as u32) & Self::mask().0)
     }
 }
@@ -42,7 +45,7 @@ impl<const N: usize> ShrAssign<u128> for Bits<N> {
 impl<const M: usize, const N: usize> Shr<Bits<M>> for SignedBits<N> {
     type Output = Self;
     fn shr(self, rhs: Bits<M>) -> Self::Output {
-        assert!(M <= 8, "Shift amount must be less than 8 bits");
+        const { assert!(M <= 8, "Shift amount must be less than 8 bits") }
         Self(i128::wrapping_shr(self.0, rhs.0 as u32))
     }
 }
@@ -66,6 +69,58 @@ impl<const N: usize> ShrAssign<u128> for SignedBits<N> {
     }
 }
 
+impl<const M: usize, const N: usize> Shr<Bits<M>> for WideBits<N> {
+    type Output = Self;
+    fn shr(self, rhs: Bits<M>) -> Self::Output {
+        self >> rhs.0
+    }
+}
+
+impl<const N: usize> Shr<u128> for WideBits<N> {
+    type Output = Self;
+    fn shr(self, rhs: u128) -> Self::Output {
+        self.shr_by(rhs.min(N as u128) as usize)
+    }
+}
+
+impl<const M: usize, const N: usize> ShrAssign<Bits<M>> for WideBits<N> {
+    fn shr_assign(&mut self, rhs: Bits<M>) {
+        *self = *self >> rhs;
+    }
+}
+
+impl<const N: usize> ShrAssign<u128> for WideBits<N> {
+    fn shr_assign(&mut self, rhs: u128) {
+        *self = *self >> rhs;
+    }
+}
+
+impl<const M: usize, const N: usize> Shr<Bits<M>> for SignedWideBits<N> {
+    type Output = Self;
+    fn shr(self, rhs: Bits<M>) -> Self::Output {
+        self >> rhs.0
+    }
+}
+
+impl<const N: usize> Shr<u128> for SignedWideBits<N> {
+    type Output = Self;
+    fn shr(self, rhs: u128) -> Self::Output {
+        self.shr_by(rhs.min(N as u128) as usize)
+    }
+}
+
+impl<const M: usize, const N: usize> ShrAssign<Bits<M>> for SignedWideBits<N> {
+    fn shr_assign(&mut self, rhs: Bits<M>) {
+        *self = *self >> rhs;
+    }
+}
+
+impl<const N: usize> ShrAssign<u128> for SignedWideBits<N> {
+    fn shr_assign(&mut self, rhs: u128) {
+        *self = *self >> rhs;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -114,4 +169,43 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_shr_wide_bits_matches_bits() {
+        let value = 0xDEAD_BEEF_CAFE_BABE_1234_5678_9ABC_DEF0_u128;
+        for shift in 0..128 {
+            let wide = WideBits::<128>::from(value) >> shift;
+            let narrow = Bits::<128>::from(value) >> shift;
+            assert_eq!(wide, narrow.raw());
+        }
+    }
+
+    #[test]
+    fn test_shr_wide_bits_across_limbs() {
+        let mut bits = WideBits::<512>::default();
+        bits.set_bit(511, true);
+        bits >>= 300;
+        assert!(bits.get_bit(211));
+        let shift: Bits<8> = 211.into();
+        bits >>= shift;
+        assert_eq!(bits, 1);
+        assert_eq!(WideBits::<512>::mask() >> 512, 0);
+    }
+
+    #[test]
+    fn test_shr_signed_wide_bits() {
+        for i in i8::MIN..i8::MAX {
+            for shift in 0..10_u32 {
+                let bits = SignedWideBits::<8>::from(i as i128);
+                let result = bits >> (shift as u128);
+                assert_eq!(result, i128::wrapping_shr(i as i128, shift));
+            }
+        }
+        let bits = SignedWideBits::<300>::from(-2);
+        assert_eq!(bits >> 1, -1);
+        assert_eq!(bits >> 1000, -1);
+        let mut bits = SignedWideBits::<300>::from(i128::MIN);
+        bits >>= 100;
+        assert_eq!(bits, i128::MIN >> 100);
+    }
 }
@@ -89,6 +89,7 @@ impl<const N: usize> SignedBits<N> {
     /// Note that for a [SignedBits] value, the mask is the same
     /// as a representation of -1.
     pub const fn mask() -> Self {
+        const { assert!(N <= 128, "SignedBits<N> only supports N <= 128") }
         // Do not compute this as you will potentially
         // cause overflow.
         if N < 128 {
@@ -146,7 +147,7 @@ impl<const N: usize> SignedBits<N> {
         } else {
             self.0 &= !(1 << bit);
         }
-        *self = self.sign_extend();
+        *self = self.sign_extended();
     }
     /// Get the value of a specific bit of a [SignedBits] value.
     /// ```
@@ -193,6 +194,47 @@ impl<const N: usize> SignedBits<N> {
     pub fn slice<const M: usize>(&self, start: usize) -> Bits<M> {
         Bits::from(((self.0 >> start) as u128) & Bits::<M>::mask().0)
     }
+    /// Extracts `M` bits starting at bit `START` from the
+    /// [SignedBits] value.  A range that does not fit inside
+    /// the `N` bits of the value is a compile time error.
+    pub fn slice_at<const START: usize, const M: usize>(&self) -> Bits<M> {
+        self.as_unsigned().slice_at::<START, M>()
+    }
+    /// Convert the [SignedBits] value to a different width.  If
+    /// `M` is smaller than `N`, the upper bits are discarded (and
+    /// the result reinterpreted as a signed value).  If `M` is
+    /// larger than `N`, the value is sign extended.
+    /// ```
+    /// # use rhdl_bits::{SignedBits, signed};
+    /// let x = signed::<8>(-14); // In binary: 1111_0010
+    /// assert_eq!(x.resize::<16>(), -14);
+    /// assert_eq!(x.resize::<4>(), 2);
+    /// ```
+    pub fn resize<const M: usize>(self) -> SignedBits<M> {
+        Bits::<M>(self.0 as u128 & Bits::<M>::mask().0).as_signed()
+    }
+    /// Sign extend the [SignedBits] value to a wider value.  An
+    /// `M` smaller than `N` is a compile time error.  Use
+    /// [SignedBits::resize] if you want to truncate the value.
+    /// ```
+    /// # use rhdl_bits::{SignedBits, signed};
+    /// let x = signed::<4>(-2);
+    /// let y: SignedBits<8> = x.sign_extend();
+    /// assert_eq!(y, -2);
+    /// assert_eq!(y.as_unsigned(), 0b1111_1110);
+    /// ```
+    /// Extending to a narrower value will fail to build:
+    /// ```compile_fail
+    /// # use rhdl_bits::{SignedBits, signed};
+    /// let x = signed::<8>(-2);
+    /// let y: SignedBits<4> = x.sign_extend();
+    /// ```
+    pub fn sign_extend<const M: usize>(self) -> SignedBits<M> {
+        const { assert!(M >= N) }
+        const { assert!(M <= 128) }
+        // The value is kept sign extended internally.
+        SignedBits(self.0)
+    }
     /// Reinterpret the [SignedBits] value as an unsigned
     /// [Bits] value.  This is useful for performing
     /// bit manipulations on the value that may or not
@@ -214,7 +256,7 @@ impl<const N: usize> SignedBits<N> {
     // This is a helper function for sign extending a value.
     // It is used internally by the [SignedBits] type.
     // It is not meant to be used externally.
-    fn sign_extend(self) -> Self {
+    fn sign_extended(self) -> Self {
         // Need to a sign extension here.
         if self.get_bit(N - 1) {
             SignedBits(self.0 | !(Self::mask().0))
@@ -247,7 +289,7 @@ impl<const N: usize> PartialEq<i128> for SignedBits<N> {
 // is larger than the [SignedBits] value can hold.
 impl<const N: usize> From<i128> for SignedBits<N> {
     fn from(value: i128) -> Self {
-        assert!(N <= 128);
+        const { assert!(N <= 128) }
         assert!(value <= Self::max_value());
         assert!(value >= Self::min_value());
         Self(value)
@@ -307,6 +349,16 @@ mod test {
         assert_eq!(SignedBits::<12>::min_value(), -0b1000_0000_0000);
     }
 
+    #[test]
+    fn test_resize_matches_built_in_casts() {
+        for i in i16::MIN..=i16::MAX {
+            let x = SignedBits::<16>::from(i as i128);
+            assert_eq!(x.resize::<8>().0, i as i8 as i128);
+            assert_eq!(x.resize::<32>().0, i as i128);
+            assert_eq!(x.sign_extend::<64>().0, i as i128);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_overflow_causes_panic() {
@@ -0,0 +1,231 @@
+use std::cmp::Ordering;
+use std::fmt::{Binary, Debug, Display, Formatter, LowerHex, UpperHex};
+
+use crate::bits::Bits;
+use crate::wide_bits::{WideBits, MAX_WIDE_BITS, WIDE_LIMBS};
+
+/// The [SignedWideBits] type is the signed counterpart of [WideBits].
+/// It holds up to [MAX_WIDE_BITS](crate::MAX_WIDE_BITS) bits, and is
+/// meant to imitate the behavior of wide signed bit vectors in hardware.
+///
+/// Signed arithmetic is performed using 2's complement arithmetic, and
+/// comparisons are performed using signed arithmetic.  As with
+/// [SignedBits](crate::SignedBits), the right shift operator sign extends
+/// the value.
+/// ```
+/// # use rhdl_bits::SignedWideBits;
+/// let x = SignedWideBits::<256>::from(-4);
+/// let y = x >> 1;
+/// assert_eq!(y, -2);
+/// assert!(y < SignedWideBits::<256>::from(0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct SignedWideBits<const N: usize>(pub(crate) WideBits<N>);
+
+impl<const N: usize> SignedWideBits<N> {
+    /// Return a [SignedWideBits] value with all bits set to 1.
+    pub const MASK: Self = Self::mask();
+    /// Return a [SignedWideBits] value with all bits set to 1.
+    /// Note that for a [SignedWideBits] value, the mask is the same
+    /// as a representation of -1.
+    /// ```
+    /// # use rhdl_bits::SignedWideBits;
+    /// assert_eq!(SignedWideBits::<200>::mask(), -1);
+    /// ```
+    pub const fn mask() -> Self {
+        Self(WideBits::mask())
+    }
+    /// Extract the sign bit from the [SignedWideBits] value.  By
+    /// convention for 2's complement arithmetic, this is
+    /// true if the value is negative.
+    pub fn sign_bit(&self) -> bool {
+        self.get_bit(N - 1)
+    }
+    /// Set a specific bit of a [SignedWideBits] value to 1 or 0.
+    /// Note that changing the MSB of a signed bit vector changes
+    /// the sign of that vector.
+    pub fn set_bit(&mut self, bit: usize, value: bool) {
+        self.0.set_bit(bit, value)
+    }
+    /// Get the value of a specific bit of a [SignedWideBits] value.
+    pub fn get_bit(&self, bit: usize) -> bool {
+        self.0.get_bit(bit)
+    }
+    /// Test if the value is negative.
+    pub fn is_negative(&self) -> bool {
+        self.sign_bit()
+    }
+    /// Test if the value is positive or zero.
+    pub fn is_non_negative(&self) -> bool {
+        !self.sign_bit()
+    }
+    /// Extracts a range of bits from the [SignedWideBits] value.
+    /// As with [SignedBits::slice](crate::SignedBits::slice), the
+    /// result is an (unsigned) [Bits] vector.  Bits past the end of
+    /// the value are sign extended.
+    /// ```
+    /// # use rhdl_bits::SignedWideBits;
+    /// let x = SignedWideBits::<256>::from(-14); // ..1111_0010
+    /// assert_eq!(x.slice::<8>(0), 0b1111_0010);
+    /// assert_eq!(x.slice::<8>(252), 0xFF);
+    /// ```
+    pub fn slice<const M: usize>(&self, start: usize) -> Bits<M> {
+        Bits(self.shr_by(start).0.low_u128() & Bits::<M>::mask().0)
+    }
+    /// Reinterpret the [SignedWideBits] value as an unsigned
+    /// [WideBits] value.
+    pub fn as_unsigned(self) -> WideBits<N> {
+        self.0
+    }
+    /// Build a (dynamic, stack allocated) vector
+    /// containing the bits that make up this value.
+    /// This will be slow.
+    pub fn to_bools(self) -> Vec<bool> {
+        self.0.to_bools()
+    }
+    // Arithmetic right shift.  The vacated bits are filled
+    // with copies of the sign bit.
+    pub(crate) fn shr_by(self, amount: usize) -> Self {
+        let shifted = self.0.shr_by(amount);
+        if self.is_negative() {
+            let fill = !WideBits::<N>::mask().shr_by(amount);
+            Self(shifted.map2(fill, |a, b| a | b))
+        } else {
+            Self(shifted)
+        }
+    }
+}
+
+// Provide conversion from a `i128` to a [SignedWideBits] value.
+// This will panic if you try to convert a value that
+// is larger than the [SignedWideBits] value can hold.
+impl<const N: usize> From<i128> for SignedWideBits<N> {
+    fn from(value: i128) -> Self {
+        const { assert!(N <= MAX_WIDE_BITS) }
+        if N < 128 {
+            assert!(value <= ((1_i128 << (N - 1)) - 1));
+            assert!(value >= -(1_i128 << (N - 1)));
+        }
+        let fill = if value < 0 { u64::MAX } else { 0 };
+        let mut limbs = [fill; WIDE_LIMBS];
+        limbs[0] = value as u64;
+        limbs[1] = (value >> 64) as u64;
+        Self(WideBits(limbs).masked())
+    }
+}
+
+impl<const N: usize> PartialEq<i128> for SignedWideBits<N> {
+    fn eq(&self, other: &i128) -> bool {
+        self == &Self::from(*other)
+    }
+}
+
+impl<const N: usize> PartialOrd for SignedWideBits<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for SignedWideBits<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl<const N: usize> Debug for SignedWideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SignedWideBits::<{}>({})", N, self)
+    }
+}
+
+impl<const N: usize> Display for SignedWideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_negative() {
+            let magnitude = (!self.0).wrapping_add(WideBits::from(1));
+            f.pad_integral(false, "", &magnitude.to_decimal_string())
+        } else {
+            f.pad_integral(true, "", &self.0.to_decimal_string())
+        }
+    }
+}
+
+impl<const N: usize> LowerHex for SignedWideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl<const N: usize> UpperHex for SignedWideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl<const N: usize> Binary for SignedWideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Binary::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_negative_sign_extends() {
+        let x = SignedWideBits::<200>::from(-1);
+        assert_eq!(x, SignedWideBits::<200>::mask());
+        assert!(x.is_negative());
+        assert!(x.get_bit(199));
+        let x = SignedWideBits::<200>::from(i128::MIN);
+        assert!(x.get_bit(127));
+        assert!(x.get_bit(199));
+        assert!(!x.get_bit(126));
+    }
+
+    #[test]
+    fn test_ordering_is_signed() {
+        let values = [i128::MIN, -5, -1, 0, 1, 5, i128::MAX];
+        for a in values {
+            for b in values {
+                let x = SignedWideBits::<256>::from(a);
+                let y = SignedWideBits::<256>::from(b);
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_matches_i128() {
+        for value in [i128::MIN, -1234567890123456789012345, -1, 0, 42, i128::MAX] {
+            assert_eq!(
+                format!("{}", SignedWideBits::<128>::from(value)),
+                format!("{}", value)
+            );
+            assert_eq!(
+                format!("{}", SignedWideBits::<300>::from(value)),
+                format!("{}", value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_slice_sign_extends() {
+        let x = SignedWideBits::<256>::from(-2);
+        assert_eq!(x.slice::<4>(0), 0b1110);
+        assert_eq!(x.slice::<8>(255), 0xFF);
+        let x = SignedWideBits::<256>::from(2);
+        assert_eq!(x.slice::<8>(255), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_overflow_causes_panic() {
+        let _ = SignedWideBits::<8>::from(128);
+    }
+}
@@ -3,6 +3,8 @@ use std::ops::SubAssign;
 
 use crate::bits::Bits;
 use crate::signed_bits::SignedBits;
+use crate::signed_wide_bits::SignedWideBits;
+use crate::wide_bits::WideBits;
 
 impl<const N: usize> Sub<Bits<N>> for Bits<N> {
     type Output = Self;
@@ -64,6 +66,65 @@ impl<const N: usize> SubAssign<SignedBits<N>> for SignedBits<N> {
     }
 }
 
+impl<const N: usize> Sub<WideBits<N>> for WideBits<N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.wrapping_sub(rhs)
+    }
+}
+
+impl<const N: usize> Sub<WideBits<N>> for u128 {
+    type Output = WideBits<N>;
+    fn sub(self, rhs: WideBits<N>) -> Self::Output {
+        WideBits::<N>::from(self) - rhs
+    }
+}
+
+impl<const N: usize> Sub<u128> for WideBits<N> {
+    type Output = Self;
+    fn sub(self, rhs: u128) -> Self::Output {
+        self - WideBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> SubAssign<u128> for WideBits<N> {
+    fn sub_assign(&mut self, rhs: u128) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> SubAssign<WideBits<N>> for WideBits<N> {
+    fn sub_assign(&mut self, rhs: WideBits<N>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Sub<SignedWideBits<N>> for SignedWideBits<N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        (self.as_unsigned() - rhs.as_unsigned()).as_signed()
+    }
+}
+
+impl<const N: usize> Sub<i128> for SignedWideBits<N> {
+    type Output = Self;
+    fn sub(self, rhs: i128) -> Self::Output {
+        self - SignedWideBits::<N>::from(rhs)
+    }
+}
+
+impl<const N: usize> SubAssign<i128> for SignedWideBits<N> {
+    fn sub_assign(&mut self, rhs: i128) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> SubAssign<SignedWideBits<N>> for SignedWideBits<N> {
+    fn sub_assign(&mut self, rhs: SignedWideBits<N>) {
+        *self = *self - rhs;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -135,4 +196,45 @@ mod test {
         x -= SignedBits::<8>::from(-2);
         assert_eq!(x.0, 3);
     }
+
+    #[test]
+    fn test_wide_subtraction_matches_bits() {
+        for i in [0, 1, 0xDEAD_BEEF, u64::MAX as u128, u128::MAX] {
+            for j in [0, 1, 0xCAFE, u64::MAX as u128 + 1, u128::MAX] {
+                let wide = WideBits::<128>::from(i) - WideBits::<128>::from(j);
+                let narrow = Bits::<128>::from(i) - Bits::<128>::from(j);
+                assert_eq!(wide, narrow.raw());
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_subtraction_borrows_across_limbs() {
+        let x = WideBits::<256>::default();
+        let y = x - 1;
+        assert_eq!(y, WideBits::<256>::mask());
+        let mut z = WideBits::<256>::from(u128::MAX) + 1;
+        z -= 1;
+        assert_eq!(z, u128::MAX);
+        assert_eq!(1 - WideBits::<300>::from(2), WideBits::<300>::mask());
+    }
+
+    #[test]
+    fn test_wide_signed_subtraction_i8() {
+        for i in i8::MIN..i8::MAX {
+            for j in i8::MIN..i8::MAX {
+                let k = SignedWideBits::<8>::from(i as i128) - SignedWideBits::<8>::from(j as i128);
+                assert_eq!(k, i8::wrapping_sub(i, j) as i128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_signed_subassign() {
+        let mut x = SignedWideBits::<512>::from(1);
+        x -= SignedWideBits::<512>::from(3);
+        assert_eq!(x, -2);
+        x -= 1;
+        assert_eq!(x, -3);
+    }
 }
@@ -0,0 +1,339 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::bits::Bits;
+use crate::fixed::Rounding;
+
+/// The [UFixed] type is an unsigned fixed point value with `I`
+/// integer bits and `F` fractional bits.  It is stored as an
+/// unsigned value `I + F` bits wide (which must be no more than
+/// 128 bits), and represents that value divided by `2^F`.  So a
+/// `UFixed<4, 4>` covers the range `0.0` to `15.9375` in steps of
+/// `0.0625`.
+///
+/// It behaves like [Fixed](crate::Fixed), but without a sign bit.
+/// As with [Bits], the `+` and `-` operators wrap on overflow.
+/// ```
+/// # use rhdl_bits::{UFixed, Rounding};
+/// let x = UFixed::<4, 4>::from_f64(1.5);
+/// let y = UFixed::<4, 4>::from_f64(2.25);
+/// let z: UFixed<8, 8> = x.widening_mul(y);
+/// assert_eq!(z.to_f64(), 3.375);
+/// let z: UFixed<4, 2> = z.saturate(Rounding::Convergent);
+/// assert_eq!(z.to_f64(), 3.5);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct UFixed<const I: usize, const F: usize>(pub(crate) u128);
+
+impl<const I: usize, const F: usize> UFixed<I, F> {
+    /// The total number of bits in the [UFixed] value.
+    pub const WIDTH: usize = I + F;
+    /// Return the largest value that can be represented
+    /// by this [UFixed] type.
+    pub fn max_value() -> Self {
+        Self(u128::MAX >> (128 - Self::WIDTH))
+    }
+    /// Return the smallest value that can be represented
+    /// by this [UFixed] type (which is zero).
+    pub fn min_value() -> Self {
+        Self(0)
+    }
+    /// Build a [UFixed] value from the raw (scaled) integer that
+    /// backs it.  Panics if the value does not fit in `I + F` bits.
+    pub fn from_raw(raw: u128) -> Self {
+        const { assert!(Self::WIDTH >= 1) }
+        const { assert!(Self::WIDTH <= 128) }
+        assert!(raw <= Self::max_value().0);
+        Self(raw)
+    }
+    /// Extract the raw (scaled) integer that backs this value.
+    pub fn raw(self) -> u128 {
+        self.0
+    }
+    /// Build a [UFixed] value from the [Bits] holding its
+    /// bits.  `N` must be equal to `I + F`.
+    pub fn from_bits<const N: usize>(bits: Bits<N>) -> Self {
+        const { assert!(N == Self::WIDTH) }
+        Self::from_raw(bits.raw())
+    }
+    /// Return the bits of this value as a [Bits] value.
+    /// `N` must be equal to `I + F`.
+    pub fn to_bits<const N: usize>(self) -> Bits<N> {
+        const { assert!(N == Self::WIDTH) }
+        Bits::from(self.0)
+    }
+    /// Convert an [f64] to the nearest [UFixed] value.  This is
+    /// meant for test benches.  Panics if the value is out of range.
+    pub fn from_f64(value: f64) -> Self {
+        let scaled = (value * 2.0_f64.powi(F as i32)).round();
+        assert!(scaled.is_finite());
+        assert!(scaled >= 0.0 && scaled <= Self::max_value().0 as f64);
+        Self::from_raw(scaled as u128)
+    }
+    /// Convert the [UFixed] value to an [f64].  Note that values
+    /// wider than 53 bits may lose precision.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / 2.0_f64.powi(F as i32)
+    }
+    // Convert to a new format without checking the range.  Returns
+    // the rescaled value (which may not fit in IO + FO bits) and a flag
+    // that is set if the rescaling overflowed the u128.
+    fn rescale<const FO: usize>(self, rounding: Rounding) -> (u128, bool) {
+        if FO >= F {
+            let shift = FO - F;
+            let shifted = self.0.checked_shl(shift as u32).unwrap_or(0);
+            (
+                shifted,
+                shifted.checked_shr(shift as u32).unwrap_or(0) != self.0,
+            )
+        } else {
+            let shift = F - FO;
+            let truncated = self.0.checked_shr(shift as u32).unwrap_or(0);
+            let discarded = self.0 & (u128::MAX >> (128 - shift));
+            let half = 1_u128 << (shift - 1);
+            if rounding.round_up(truncated & 1 == 1, discarded, half) {
+                truncated.overflowing_add(1)
+            } else {
+                (truncated, false)
+            }
+        }
+    }
+    /// Convert to a different format, rounding off any discarded
+    /// fractional bits, and wrapping (discarding) any integer bits
+    /// that do not fit.
+    pub fn resize<const IO: usize, const FO: usize>(self, rounding: Rounding) -> UFixed<IO, FO> {
+        const { assert!(IO + FO >= 1) }
+        const { assert!(IO + FO <= 128) }
+        let (value, _) = self.rescale::<FO>(rounding);
+        UFixed(value & UFixed::<IO, FO>::max_value().0)
+    }
+    /// Convert to a different format, rounding off any discarded
+    /// fractional bits, and clamping the result to the range of
+    /// the new format.
+    /// ```
+    /// # use rhdl_bits::{UFixed, Rounding};
+    /// let x = UFixed::<8, 4>::from_f64(17.75);
+    /// let y: UFixed<4, 2> = x.saturate(Rounding::Truncate);
+    /// assert_eq!(y.to_f64(), 15.75);
+    /// ```
+    pub fn saturate<const IO: usize, const FO: usize>(self, rounding: Rounding) -> UFixed<IO, FO> {
+        const { assert!(IO + FO >= 1) }
+        const { assert!(IO + FO <= 128) }
+        let (value, overflow) = self.rescale::<FO>(rounding);
+        UFixed::<IO, FO>::clamped(value, overflow)
+    }
+    /// Add two [UFixed] values, producing a result that is wide
+    /// enough to hold the exact sum.  The output must have
+    /// `IO = max(I, I2) + 1` and `FO = max(F, F2)`.
+    pub fn widening_add<const I2: usize, const F2: usize, const IO: usize, const FO: usize>(
+        self,
+        rhs: UFixed<I2, F2>,
+    ) -> UFixed<IO, FO> {
+        const { assert!(IO == (if I > I2 { I } else { I2 }) + 1) }
+        const { assert!(FO == if F > F2 { F } else { F2 }) }
+        let lhs: UFixed<IO, FO> = self.resize(Rounding::Truncate);
+        let rhs: UFixed<IO, FO> = rhs.resize(Rounding::Truncate);
+        UFixed(lhs.0 + rhs.0)
+    }
+    /// Subtract two [UFixed] values.  The difference of two unsigned
+    /// values can be negative, so the result is a signed
+    /// [Fixed](crate::Fixed) value that is wide enough to hold the
+    /// exact difference.  The output must have `IO = max(I, I2) + 1`
+    /// and `FO = max(F, F2)`.
+    /// ```
+    /// # use rhdl_bits::{Fixed, UFixed};
+    /// let x = UFixed::<4, 4>::from_f64(1.5);
+    /// let y = UFixed::<4, 4>::from_f64(15.0);
+    /// let z: Fixed<5, 4> = x.widening_sub(y);
+    /// assert_eq!(z.to_f64(), -13.5);
+    /// ```
+    pub fn widening_sub<const I2: usize, const F2: usize, const IO: usize, const FO: usize>(
+        self,
+        rhs: UFixed<I2, F2>,
+    ) -> crate::Fixed<IO, FO> {
+        const { assert!(IO == (if I > I2 { I } else { I2 }) + 1) }
+        const { assert!(FO == if F > F2 { F } else { F2 }) }
+        let lhs: UFixed<IO, FO> = self.resize(Rounding::Truncate);
+        let rhs: UFixed<IO, FO> = rhs.resize(Rounding::Truncate);
+        crate::Fixed(lhs.0.wrapping_sub(rhs.0) as i128)
+    }
+    /// Multiply two [UFixed] values, producing a result that is
+    /// wide enough to hold the exact product.  The output must
+    /// have `IO = I + I2` and `FO = F + F2`.
+    pub fn widening_mul<const I2: usize, const F2: usize, const IO: usize, const FO: usize>(
+        self,
+        rhs: UFixed<I2, F2>,
+    ) -> UFixed<IO, FO> {
+        const { assert!(IO == I + I2) }
+        const { assert!(FO == F + F2) }
+        const { assert!(IO + FO <= 128) }
+        UFixed(self.0 * rhs.0)
+    }
+    /// Add two [UFixed] values, clamping the result to the
+    /// largest value of the type.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        let (sum, overflow) = self.0.overflowing_add(rhs.0);
+        Self::clamped(sum, overflow)
+    }
+    /// Subtract two [UFixed] values, clamping the result at zero.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+    // Clamp a value that may have overflowed the range of the type
+    // (or the u128 that holds it).
+    fn clamped(value: u128, overflow: bool) -> Self {
+        if overflow || value > Self::max_value().0 {
+            Self::max_value()
+        } else {
+            Self(value)
+        }
+    }
+}
+
+impl<const I: usize, const F: usize> Add for UFixed<I, F> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.wrapping_add(rhs.0) & Self::max_value().0)
+    }
+}
+
+impl<const I: usize, const F: usize> AddAssign for UFixed<I, F> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const I: usize, const F: usize> Sub for UFixed<I, F> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.wrapping_sub(rhs.0) & Self::max_value().0)
+    }
+}
+
+impl<const I: usize, const F: usize> SubAssign for UFixed<I, F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const I: usize, const F: usize> Debug for UFixed<I, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UFixed::<{}, {}>({})", I, F, self.to_f64())
+    }
+}
+
+impl<const I: usize, const F: usize> Display for UFixed<I, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.to_f64(), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn all_values<const I: usize, const F: usize>() -> impl Iterator<Item = UFixed<I, F>> {
+        (0..=UFixed::<I, F>::max_value().0).map(UFixed::from_raw)
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(UFixed::<4, 4>::max_value().to_f64(), 15.9375);
+        assert_eq!(UFixed::<0, 128>::max_value().0, u128::MAX);
+        assert_eq!(UFixed::<0, 1>::max_value().to_f64(), 0.5);
+    }
+
+    #[test]
+    fn test_f64_round_trip() {
+        for x in all_values::<4, 4>() {
+            assert_eq!(UFixed::<4, 4>::from_f64(x.to_f64()), x);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_f64_negative_panics() {
+        let _ = UFixed::<4, 4>::from_f64(-1.0);
+    }
+
+    #[test]
+    fn test_wrapping_ops_match_bits() {
+        for x in all_values::<3, 2>() {
+            for y in all_values::<3, 2>() {
+                let xb: Bits<5> = x.to_bits();
+                let yb: Bits<5> = y.to_bits();
+                assert_eq!((x + y).to_bits::<5>(), xb + yb);
+                assert_eq!((x - y).to_bits::<5>(), xb - yb);
+            }
+        }
+    }
+
+    #[test]
+    fn test_widening_ops_are_exact() {
+        for x in all_values::<3, 2>() {
+            for y in all_values::<2, 3>() {
+                let sum: UFixed<4, 3> = x.widening_add(y);
+                assert_eq!(sum.to_f64(), x.to_f64() + y.to_f64());
+                let diff: crate::Fixed<4, 3> = x.widening_sub(y);
+                assert_eq!(diff.to_f64(), x.to_f64() - y.to_f64());
+                let product: UFixed<5, 5> = x.widening_mul(y);
+                assert_eq!(product.to_f64(), x.to_f64() * y.to_f64());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let check = |value: f64, truncate: f64, half_up: f64, convergent: f64| {
+            let x = UFixed::<8, 4>::from_f64(value);
+            let t: UFixed<8, 0> = x.resize(Rounding::Truncate);
+            let h: UFixed<8, 0> = x.resize(Rounding::RoundHalfUp);
+            let c: UFixed<8, 0> = x.resize(Rounding::Convergent);
+            assert_eq!(t.to_f64(), truncate);
+            assert_eq!(h.to_f64(), half_up);
+            assert_eq!(c.to_f64(), convergent);
+        };
+        check(2.5, 2.0, 3.0, 2.0);
+        check(3.5, 3.0, 4.0, 4.0);
+        check(2.25, 2.0, 2.0, 2.0);
+        check(2.75, 2.0, 3.0, 3.0);
+    }
+
+    #[test]
+    fn test_saturate_clamps() {
+        for x in all_values::<4, 4>() {
+            for rounding in [
+                Rounding::Truncate,
+                Rounding::RoundHalfUp,
+                Rounding::Convergent,
+            ] {
+                let y: UFixed<2, 2> = x.saturate(rounding);
+                let exact: UFixed<5, 2> = x.resize(rounding);
+                let expected = exact.to_f64().min(3.75);
+                assert_eq!(y.to_f64(), expected);
+            }
+        }
+        let x = UFixed::<0, 128>::max_value();
+        let y: UFixed<0, 127> = x.saturate(Rounding::RoundHalfUp);
+        assert_eq!(y, UFixed::<0, 127>::max_value());
+        let y: UFixed<1, 127> = x.resize(Rounding::RoundHalfUp);
+        assert_eq!(y.to_f64(), 1.0);
+    }
+
+    #[test]
+    fn test_saturating_add_and_sub() {
+        for x in all_values::<3, 2>() {
+            for y in all_values::<3, 2>() {
+                let expected = (x.to_f64() + y.to_f64()).min(7.75);
+                assert_eq!(x.saturating_add(y).to_f64(), expected);
+                let expected = (x.to_f64() - y.to_f64()).max(0.0);
+                assert_eq!(x.saturating_sub(y).to_f64(), expected);
+            }
+        }
+        let x = UFixed::<64, 64>::max_value();
+        assert_eq!(x.saturating_add(x), x);
+    }
+}
@@ -0,0 +1,454 @@
+use std::cmp::Ordering;
+use std::fmt::{Binary, Debug, Display, Formatter, LowerHex, UpperHex};
+
+use crate::bits::Bits;
+use crate::signed_wide_bits::SignedWideBits;
+
+// The number of 64 bit limbs used to store a [WideBits] value.
+pub(crate) const WIDE_LIMBS: usize = 16;
+
+/// The largest number of bits that can be held in a [WideBits]
+/// or [SignedWideBits] value.
+pub const MAX_WIDE_BITS: usize = 64 * WIDE_LIMBS;
+
+/// The [WideBits] type is a fixed-size bit vector for widths that
+/// do not fit into the 128 bit limit of the [Bits] type.  It is
+/// meant for things like wide data busses (e.g., 256 bit AXI data
+/// paths or 512 bit cache lines), and can hold up to [MAX_WIDE_BITS]
+/// bits.
+///
+/// The value is stored as a fixed array of 64 bit limbs (least
+/// significant limb first), so it is still [Copy], but it is larger
+/// and slower to operate on than a [Bits] value.  Bits above the
+/// width `N` are always kept at zero.
+///
+/// Note that the storage does not depend on `N`.  Every value holds
+/// [MAX_WIDE_BITS] bits (128 bytes), and most operations touch all
+/// of the limbs, so a `WideBits<256>` costs the same to copy and to
+/// add as a `WideBits<1024>`.  Sizing the array from `N` would need
+/// const generic expressions, which stable Rust does not support.
+///
+/// The [WideBits] type implements the same wrapping 2's complement
+/// arithmetic as [Bits], and is treated as an unsigned value for
+/// the purposes of comparisons.  If you need signed semantics, use
+/// [SignedWideBits] instead.
+/// ```
+/// # use rhdl_bits::WideBits;
+/// let x: WideBits<256> = WideBits::mask();
+/// let y = x + 1;
+/// assert_eq!(y, 0);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct WideBits<const N: usize>(pub(crate) [u64; WIDE_LIMBS]);
+
+impl<const N: usize> WideBits<N> {
+    /// Defines a constant WideBits value with all bits set to 1.
+    pub const MASK: Self = Self::mask();
+    /// Return a [WideBits] value with all bits set to 1.
+    /// ```
+    /// # use rhdl_bits::WideBits;
+    /// let bits = WideBits::<130>::mask();
+    /// assert!(bits.get_bit(129));
+    /// assert_eq!(bits.slice::<8>(128), 0b11);
+    /// ```
+    pub const fn mask() -> Self {
+        const {
+            assert!(
+                N <= MAX_WIDE_BITS,
+                "WideBits<N> only supports N <= MAX_WIDE_BITS"
+            )
+        }
+        let mut limbs = [0; WIDE_LIMBS];
+        let mut ndx = 0;
+        while ndx < WIDE_LIMBS {
+            let low = ndx * 64;
+            limbs[ndx] = if N >= low + 64 {
+                u64::MAX
+            } else if N > low {
+                (1 << (N - low)) - 1
+            } else {
+                0
+            };
+            ndx += 1;
+        }
+        Self(limbs)
+    }
+    // Clear any bits above N.  All operations that may
+    // disturb those bits must call this before returning.
+    pub(crate) fn masked(self) -> Self {
+        let mask = Self::mask();
+        let mut limbs = self.0;
+        for (limb, mask) in limbs.iter_mut().zip(mask.0) {
+            *limb &= mask;
+        }
+        Self(limbs)
+    }
+    /// Set a specific bit of a [WideBits] value to 1 or 0.
+    /// Panics if the index of the bit is outside the range
+    /// of the [WideBits] value.
+    /// ```
+    /// # use rhdl_bits::WideBits;
+    /// let mut bits = WideBits::<256>::default();
+    /// bits.set_bit(200, true);
+    /// assert!(bits.get_bit(200));
+    /// bits.set_bit(200, false);
+    /// assert_eq!(bits, 0);
+    /// ```
+    pub fn set_bit(&mut self, bit: usize, value: bool) {
+        assert!(bit < N);
+        if value {
+            self.0[bit / 64] |= 1 << (bit % 64);
+        } else {
+            self.0[bit / 64] &= !(1 << (bit % 64));
+        }
+    }
+    /// Get the value of a specific bit of a [WideBits] value.
+    /// Panics if the index of the bit is outside the range
+    /// of the [WideBits] value.
+    pub fn get_bit(&self, bit: usize) -> bool {
+        assert!(bit < N);
+        (self.0[bit / 64] & (1 << (bit % 64))) != 0
+    }
+    /// Returns true if any of the bits are set to 1.
+    pub fn any(self) -> bool {
+        self.0.iter().any(|limb| *limb != 0)
+    }
+    /// Returns true if all of the bits are set to 1.
+    pub fn all(self) -> bool {
+        self == Self::mask()
+    }
+    /// Computes the xor of all of the bits in the value.
+    pub fn xor(self) -> bool {
+        self.0.iter().map(|limb| limb.count_ones()).sum::<u32>() & 1 == 1
+    }
+    /// Extracts a range of bits from the [WideBits] value.  As
+    /// with [Bits::slice], bits past the end of the value are
+    /// read as zero.
+    /// ```
+    /// # use rhdl_bits::WideBits;
+    /// let mut bits = WideBits::<256>::default();
+    /// bits.set_bit(131, true);
+    /// assert_eq!(bits.slice::<8>(128), 0b1000);
+    /// ```
+    pub fn slice<const M: usize>(&self, start: usize) -> Bits<M> {
+        Bits(self.shr_by(start).low_u128() & Bits::<M>::mask().0)
+    }
+    /// Extracts a range of bits from the [WideBits] value into
+    /// another [WideBits] value.
+    /// ```
+    /// # use rhdl_bits::WideBits;
+    /// let line = WideBits::<512>::mask();
+    /// let upper: WideBits<256> = line.slice_wide(256);
+    /// assert!(upper.all());
+    /// ```
+    pub fn slice_wide<const M: usize>(&self, start: usize) -> WideBits<M> {
+        WideBits::<M>(self.shr_by(start).0).masked()
+    }
+    /// Reinterpret the [WideBits] value as a [SignedWideBits] value.
+    pub fn as_signed(self) -> SignedWideBits<N> {
+        SignedWideBits(self)
+    }
+    /// Returns the limbs (least significant first) that hold
+    /// the bits of this value.  There are `ceil(N/64)` of them.
+    pub fn limbs(&self) -> &[u64] {
+        &self.0[..N.div_ceil(64)]
+    }
+    /// Build a (dynamic, stack allocated) vector containing
+    /// the bits that make up this value.  This will be slow.
+    pub fn to_bools(self) -> Vec<bool> {
+        (0..N).map(|bit| self.get_bit(bit)).collect()
+    }
+    pub(crate) fn low_u128(self) -> u128 {
+        self.0[0] as u128 | (self.0[1] as u128) << 64
+    }
+    pub(crate) fn shl_by(self, amount: usize) -> Self {
+        if amount >= N {
+            return Self::default();
+        }
+        let limb_shift = amount / 64;
+        let bit_shift = amount % 64;
+        let mut limbs = [0; WIDE_LIMBS];
+        for ndx in (limb_shift..WIDE_LIMBS).rev() {
+            let src = ndx - limb_shift;
+            limbs[ndx] = self.0[src] << bit_shift;
+            if bit_shift != 0 && src > 0 {
+                limbs[ndx] |= self.0[src - 1] >> (64 - bit_shift);
+            }
+        }
+        Self(limbs).masked()
+    }
+    pub(crate) fn shr_by(self, amount: usize) -> Self {
+        if amount >= N {
+            return Self::default();
+        }
+        let limb_shift = amount / 64;
+        let bit_shift = amount % 64;
+        let mut limbs = [0; WIDE_LIMBS];
+        for (ndx, limb) in limbs.iter_mut().take(WIDE_LIMBS - limb_shift).enumerate() {
+            let src = ndx + limb_shift;
+            *limb = self.0[src] >> bit_shift;
+            if bit_shift != 0 && src + 1 < WIDE_LIMBS {
+                *limb |= self.0[src + 1] << (64 - bit_shift);
+            }
+        }
+        Self(limbs)
+    }
+    pub(crate) fn wrapping_add(self, rhs: Self) -> Self {
+        let mut limbs = [0; WIDE_LIMBS];
+        let mut carry = false;
+        for (ndx, limb) in limbs.iter_mut().enumerate() {
+            let (sum, c1) = self.0[ndx].overflowing_add(rhs.0[ndx]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        Self(limbs).masked()
+    }
+    pub(crate) fn wrapping_sub(self, rhs: Self) -> Self {
+        let mut limbs = [0; WIDE_LIMBS];
+        let mut borrow = false;
+        for (ndx, limb) in limbs.iter_mut().enumerate() {
+            let (diff, b1) = self.0[ndx].overflowing_sub(rhs.0[ndx]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+        Self(limbs).masked()
+    }
+    pub(crate) fn map2(self, rhs: Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        let mut limbs = [0; WIDE_LIMBS];
+        for (ndx, limb) in limbs.iter_mut().enumerate() {
+            *limb = op(self.0[ndx], rhs.0[ndx]);
+        }
+        Self(limbs).masked()
+    }
+    // Divide the value by a small divisor in place, returning the
+    // remainder.  Used for decimal formatting.
+    fn div_rem_small(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0_u128;
+        for limb in self.0.iter_mut().rev() {
+            let value = (remainder << 64) | *limb as u128;
+            *limb = (value / divisor as u128) as u64;
+            remainder = value % divisor as u128;
+        }
+        remainder as u64
+    }
+    pub(crate) fn to_decimal_string(self) -> String {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut value = self;
+        let mut chunks = vec![];
+        loop {
+            chunks.push(value.div_rem_small(CHUNK));
+            if !value.any() {
+                break;
+            }
+        }
+        let mut result = format!("{}", chunks.pop().unwrap_or(0));
+        for chunk in chunks.iter().rev() {
+            result.push_str(&format!("{:019}", chunk));
+        }
+        result
+    }
+    pub(crate) fn to_radix_string(self, bits_per_digit: usize, upper: bool) -> String {
+        let digits = N.div_ceil(bits_per_digit).max(1);
+        let digit_mask = (1 << bits_per_digit) - 1;
+        let result: String = (0..digits)
+            .rev()
+            .map(|digit| {
+                let value = self.shr_by(digit * bits_per_digit).0[0] & digit_mask;
+                let ch = std::char::from_digit(value as u32, 1 << bits_per_digit).unwrap();
+                if upper {
+                    ch.to_ascii_uppercase()
+                } else {
+                    ch
+                }
+            })
+            .collect();
+        let trimmed = result.trim_start_matches('0');
+        if trimmed.is_empty() {
+            "0".to_string()
+        } else {
+            trimmed.to_string()
+        }
+    }
+}
+
+/// The default value for a [WideBits] value is 0.
+impl<const N: usize> Default for WideBits<N> {
+    fn default() -> Self {
+        Self([0; WIDE_LIMBS])
+    }
+}
+
+/// Provide conversion from a `u128` to a [WideBits] value.
+/// This will panic if you try to convert a value that
+/// is larger than the [WideBits] value can hold.
+impl<const N: usize> From<u128> for WideBits<N> {
+    fn from(value: u128) -> Self {
+        const { assert!(N <= MAX_WIDE_BITS) }
+        assert!(value.checked_shr(N as u32).unwrap_or(0) == 0);
+        let mut limbs = [0; WIDE_LIMBS];
+        limbs[0] = value as u64;
+        limbs[1] = (value >> 64) as u64;
+        Self(limbs)
+    }
+}
+
+impl<const N: usize> PartialEq<u128> for WideBits<N> {
+    fn eq(&self, other: &u128) -> bool {
+        self == &Self::from(*other)
+    }
+}
+
+impl<const N: usize> PartialOrd for WideBits<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for WideBits<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const N: usize> Debug for WideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WideBits::<{}>(0x{:x})", N, self)
+    }
+}
+
+impl<const N: usize> Display for WideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "", &self.to_decimal_string())
+    }
+}
+
+impl<const N: usize> LowerHex for WideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0x", &self.to_radix_string(4, false))
+    }
+}
+
+impl<const N: usize> UpperHex for WideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0x", &self.to_radix_string(4, true))
+    }
+}
+
+impl<const N: usize> Binary for WideBits<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0b", &self.to_radix_string(1, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask() {
+        let bits = WideBits::<256>::mask();
+        assert_eq!(bits.limbs(), &[u64::MAX; 4]);
+        let bits = WideBits::<130>::mask();
+        assert_eq!(bits.limbs(), &[u64::MAX, u64::MAX, 0b11]);
+        let bits = WideBits::<1024>::mask();
+        assert!(bits.0.iter().all(|x| *x == u64::MAX));
+    }
+
+    #[test]
+    fn test_set_and_get_bit() {
+        let mut bits = WideBits::<512>::default();
+        for bit in [0, 63, 64, 127, 128, 255, 256, 511] {
+            bits.set_bit(bit, true);
+            assert!(bits.get_bit(bit));
+            bits.set_bit(bit, false);
+            assert!(!bits.any());
+        }
+    }
+
+    #[test]
+    fn test_reductions() {
+        let bits = WideBits::<300>::mask();
+        assert!(bits.all());
+        assert!(bits.any());
+        assert!(!bits.xor());
+        let mut bits = WideBits::<300>::default();
+        assert!(!bits.any());
+        bits.set_bit(299, true);
+        assert!(bits.xor());
+        assert!(!bits.all());
+    }
+
+    #[test]
+    fn test_slice_matches_bits_behavior() {
+        let value = 0xDEAD_BEEF_CAFE_BABE_1234_5678_9ABC_DEF0_u128;
+        let wide = WideBits::<128>::from(value);
+        let narrow = Bits::<128>::from(value);
+        for start in [0, 3, 17, 64, 100, 127] {
+            assert_eq!(wide.slice::<16>(start), narrow.slice::<16>(start));
+        }
+    }
+
+    #[test]
+    fn test_slice_across_limbs() {
+        let mut bits = WideBits::<512>::default();
+        bits.set_bit(255, true);
+        bits.set_bit(256, true);
+        assert_eq!(bits.slice::<4>(254), 0b0110);
+        let upper: WideBits<256> = bits.slice_wide(256);
+        assert_eq!(upper, 1);
+        assert_eq!(bits.slice::<8>(600), 0);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut a = WideBits::<256>::default();
+        let b = WideBits::<256>::from(u128::MAX);
+        a.set_bit(200, true);
+        assert!(a > b);
+        assert!(b < a);
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_formatting() {
+        let mut bits = WideBits::<200>::default();
+        assert_eq!(format!("{}", bits), "0");
+        assert_eq!(format!("{:x}", bits), "0");
+        bits.set_bit(128, true);
+        assert_eq!(
+            format!("{}", bits),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(format!("{:x}", bits), "100000000000000000000000000000000");
+        assert_eq!(format!("{:#X}", WideBits::<8>::from(0xab)), "0xAB");
+        assert_eq!(format!("{:b}", WideBits::<8>::from(0b1101)), "1101");
+        assert_eq!(format!("{:08b}", WideBits::<8>::from(0b1101)), "00001101");
+    }
+
+    #[test]
+    fn test_display_matches_u128() {
+        for value in [0, 1, 9, 10, 12345678901234567890, u128::MAX] {
+            assert_eq!(
+                format!("{}", WideBits::<128>::from(value)),
+                format!("{}", value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_bools() {
+        let bits = WideBits::<130>::from(0b1101);
+        let result = bits.to_bools();
+        assert_eq!(result.len(), 130);
+        assert_eq!(&result[0..5], &[true, false, true, true, false]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_overflow_causes_panic() {
+        let _ = WideBits::<8>::from(256);
+    }
+}
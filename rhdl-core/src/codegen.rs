@@ -0,0 +1,28 @@
+//! Generate declarations in other languages from a [Kind](crate::Kind),
+//! so that firmware and RTL can share the packed formats that are
+//! defined with `#[derive(Digital)]`.
+//!
+//! A [Kind](crate::Kind) does not record the names of the types it
+//! was built from, so the generators take a name for the top level
+//! type, and derive the names of nested types from it and the path
+//! to them (e.g., the payload of the `Color` variant of `packet` is
+//! named `packet_Color`).
+pub mod c;
+pub mod systemverilog;
+
+// A type, along with the array dimensions that apply to it,
+// outermost first.
+#[derive(Clone, Debug)]
+struct TypeRef {
+    base: String,
+    dims: Vec<usize>,
+}
+
+impl TypeRef {
+    fn named(base: impl Into<String>) -> Self {
+        Self {
+            base: base.into(),
+            dims: vec![],
+        }
+    }
+}
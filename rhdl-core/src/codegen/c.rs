@@ -0,0 +1,582 @@
+//! A C header for a [Kind].
+//!
+//! The header declares a plain (unpacked) C type for the value, along
+//! with `{name}_pack` and `{name}_unpack` functions that convert it to
+//! and from the packed binary representation.  The packed form is a
+//! little endian byte buffer, with bit `n` of the value stored in bit
+//! `n % 8` of byte `n / 8` - the same order as `to_le_bytes` in
+//! `rhdl-bits`.
+//!
+//! Bits are stored in the smallest unsigned integer that holds them,
+//! or in a little endian `uint8_t` array when they are wider than 64
+//! bits.  An enum is a struct holding a C `enum` for the discriminant
+//! and a union of the variant payloads.  Only the widest field of a
+//! union (the first one, if there is a tie) is packed and unpacked,
+//! since the fields of a C union do not overlap bit for bit.
+use super::TypeRef;
+use crate::kind::{DiscriminantAlignment, Enum, Kind};
+
+const HELPERS: &str = "\
+#ifndef RHDL_PACK_HELPERS
+#define RHDL_PACK_HELPERS
+static inline void rhdl_put_bits(uint8_t *buf, size_t offset, size_t width, uint64_t value) {
+    for (size_t i = 0; i < width; i++) {
+        size_t bit = offset + i;
+        if ((value >> i) & 1) buf[bit / 8] |= (uint8_t) (1u << (bit % 8));
+    }
+}
+
+static inline uint64_t rhdl_get_bits(const uint8_t *buf, size_t offset, size_t width) {
+    uint64_t value = 0;
+    for (size_t i = 0; i < width; i++) {
+        size_t bit = offset + i;
+        value |= (uint64_t) ((buf[bit / 8] >> (bit % 8)) & 1) << i;
+    }
+    return value;
+}
+
+static inline void rhdl_put_bytes(uint8_t *buf, size_t offset, size_t width, const uint8_t *value) {
+    for (size_t i = 0; i < width; i++) {
+        size_t bit = offset + i;
+        if ((value[i / 8] >> (i % 8)) & 1) buf[bit / 8] |= (uint8_t) (1u << (bit % 8));
+    }
+}
+
+static inline void rhdl_get_bytes(const uint8_t *buf, size_t offset, size_t width, uint8_t *value) {
+    for (size_t i = 0; i < width; i++) {
+        size_t bit = offset + i;
+        if ((buf[bit / 8] >> (bit % 8)) & 1) value[i / 8] |= (uint8_t) (1u << (i % 8));
+    }
+}
+
+static inline int64_t rhdl_sign_extend(uint64_t value, size_t width) {
+    if (width < 64 && ((value >> (width - 1)) & 1)) value |= ~(uint64_t) 0 << width;
+    return (int64_t) value;
+}
+#endif
+";
+
+fn render_member(ty: &TypeRef, name: &str) -> String {
+    let dims = ty.dims.iter().map(|d| format!("[{d}]")).collect::<String>();
+    format!("{} {}{}", ty.base, name, dims)
+}
+
+fn integer(width: usize) -> TypeRef {
+    match width {
+        0..=8 => TypeRef::named("uint8_t"),
+        9..=16 => TypeRef::named("uint16_t"),
+        17..=32 => TypeRef::named("uint32_t"),
+        33..=64 => TypeRef::named("uint64_t"),
+        _ => TypeRef {
+            base: "uint8_t".into(),
+            dims: vec![width.div_ceil(8)],
+        },
+    }
+}
+
+fn enum_constant(hint: &str, variant: &str) -> String {
+    format!("{}_{}", hint.to_uppercase(), variant.to_uppercase())
+}
+
+// The widest field of a union, which is the one that gets packed.
+fn widest<'a, T>(fields: impl Iterator<Item = (&'a Kind, T)>) -> Option<(&'a Kind, T)> {
+    let mut best: Option<(&Kind, T)> = None;
+    for (kind, tag) in fields {
+        if kind.bits() > best.as_ref().map(|x| x.0.bits()).unwrap_or(0) {
+            best = Some((kind, tag));
+        }
+    }
+    best
+}
+
+#[derive(Default)]
+struct Declarations {
+    typedefs: Vec<String>,
+}
+
+impl Declarations {
+    fn aggregate(
+        &mut self,
+        keyword: &str,
+        name: String,
+        members: Vec<(String, TypeRef)>,
+    ) -> TypeRef {
+        let mut body = format!("typedef {keyword} {{\n");
+        for (member, ty) in &members {
+            body += &format!("    {};\n", render_member(ty, member));
+        }
+        body += &format!("}} {name};");
+        self.typedefs.push(body);
+        TypeRef::named(name)
+    }
+    fn declare_enum(&mut self, e: &Enum, hint: &str) -> Option<TypeRef> {
+        let mut members = vec![];
+        if e.discriminant_width > 0 {
+            let constants = e
+                .variants
+                .iter()
+                .map(|v| format!("    {} = {}", enum_constant(hint, &v.name), v.discriminant))
+                .collect::<Vec<_>>();
+            self.typedefs.push(format!(
+                "typedef enum {{\n{}\n}} {hint}_e;",
+                constants.join(",\n")
+            ));
+            members.push(("discriminant".into(), TypeRef::named(format!("{hint}_e"))));
+        }
+        let payloads = e
+            .variants
+            .iter()
+            .filter_map(|v| {
+                let ty = self.declare(&v.kind, &format!("{hint}_{}", v.name))?;
+                Some((v.name.clone(), ty))
+            })
+            .collect::<Vec<_>>();
+        if !payloads.is_empty() {
+            let payload = self.aggregate("union", format!("{hint}_payload_t"), payloads);
+            members.push(("payload".into(), payload));
+        }
+        if members.is_empty() {
+            return None;
+        }
+        Some(self.aggregate("struct", format!("{hint}_t"), members))
+    }
+    fn declare(&mut self, kind: &Kind, hint: &str) -> Option<TypeRef> {
+        if kind.bits() == 0 {
+            return None;
+        }
+        match kind {
+            Kind::Empty => None,
+            Kind::Bits(digits) => Some(integer(*digits)),
+            Kind::Array(array) => {
+                let mut ty = self.declare(&array.base, hint)?;
+                ty.dims.insert(0, array.size);
+                Some(ty)
+            }
+            Kind::Tuple(tuple) => {
+                let members = tuple
+                    .elements
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(ndx, element)| {
+                        let ty = self.declare(element, &format!("{hint}_{ndx}"))?;
+                        Some((format!("_{ndx}"), ty))
+                    })
+                    .collect();
+                Some(self.aggregate("struct", format!("{hint}_t"), members))
+            }
+            Kind::Struct(structure) => {
+                let members = structure
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        let ty = self.declare(&field.kind, &format!("{hint}_{}", field.name))?;
+                        Some((field.name.clone(), ty))
+                    })
+                    .collect();
+                Some(self.aggregate("struct", format!("{hint}_t"), members))
+            }
+            Kind::Union(union) => {
+                let members = union
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        let ty = self.declare(&field.kind, &format!("{hint}_{}", field.name))?;
+                        Some((field.name.clone(), ty))
+                    })
+                    .collect();
+                Some(self.aggregate("union", format!("{hint}_t"), members))
+            }
+            Kind::Enum(e) => self.declare_enum(e, hint),
+        }
+    }
+}
+
+// A bit offset into the packed buffer, which depends on the indices
+// of the loops over any enclosing arrays.
+#[derive(Clone, Default)]
+struct Offset {
+    constant: usize,
+    terms: Vec<(String, usize)>,
+}
+
+impl Offset {
+    fn add(&self, bits: usize) -> Self {
+        Self {
+            constant: self.constant + bits,
+            terms: self.terms.clone(),
+        }
+    }
+    fn render(&self) -> String {
+        let mut parts = self
+            .terms
+            .iter()
+            .map(|(index, stride)| format!("{index} * {stride}"))
+            .collect::<Vec<_>>();
+        if self.constant != 0 || parts.is_empty() {
+            parts.push(self.constant.to_string());
+        }
+        parts.join(" + ")
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Pack,
+    Unpack,
+}
+
+// The statements of the body of the pack (or unpack) function.
+struct Body {
+    direction: Direction,
+    lines: Vec<String>,
+    depth: usize,
+    loops: usize,
+}
+
+fn member(expr: &str, name: &str) -> String {
+    match expr.strip_prefix('*') {
+        Some(pointer) => format!("{pointer}->{name}"),
+        None => format!("{expr}.{name}"),
+    }
+}
+
+fn element(expr: &str, index: &str) -> String {
+    if expr.starts_with('*') {
+        format!("({expr})[{index}]")
+    } else {
+        format!("{expr}[{index}]")
+    }
+}
+
+impl Body {
+    fn line(&mut self, text: String) {
+        self.lines
+            .push(format!("{}{}", "    ".repeat(self.depth), text));
+    }
+    fn bits(&mut self, expr: &str, digits: usize, offset: &Offset) {
+        let offset = offset.render();
+        let text = match (self.direction, digits > 64) {
+            (Direction::Pack, false) => {
+                format!("rhdl_put_bits(buf, {offset}, {digits}, (uint64_t) {expr});")
+            }
+            (Direction::Pack, true) => format!("rhdl_put_bytes(buf, {offset}, {digits}, {expr});"),
+            (Direction::Unpack, false) => format!(
+                "{expr} = ({}) rhdl_get_bits(buf, {offset}, {digits});",
+                integer(digits).base
+            ),
+            (Direction::Unpack, true) => {
+                format!("rhdl_get_bytes(buf, {offset}, {digits}, {expr});")
+            }
+        };
+        self.line(text);
+    }
+    fn discriminant(&mut self, e: &Enum, hint: &str, expr: &str, offset: &Offset) {
+        let width = e.discriminant_width;
+        let offset = offset.render();
+        let text = match self.direction {
+            Direction::Pack => format!("rhdl_put_bits(buf, {offset}, {width}, (uint64_t) {expr});"),
+            Direction::Unpack if e.variants.iter().any(|v| v.discriminant < 0) => format!(
+                "{expr} = ({hint}_e) rhdl_sign_extend(rhdl_get_bits(buf, {offset}, {width}), {width});"
+            ),
+            Direction::Unpack => {
+                format!("{expr} = ({hint}_e) rhdl_get_bits(buf, {offset}, {width});")
+            }
+        };
+        self.line(text);
+    }
+    fn enumeration(&mut self, kind: &Kind, e: &Enum, hint: &str, expr: &str, offset: &Offset) {
+        let width = e.discriminant_width;
+        let (discriminant_offset, payload_offset) = match e.discriminant_alignment {
+            DiscriminantAlignment::Lsb => (offset.clone(), offset.add(width)),
+            DiscriminantAlignment::Msb => (offset.add(kind.bits() - width), offset.clone()),
+        };
+        let payloads = e
+            .variants
+            .iter()
+            .filter(|v| v.kind.bits() > 0)
+            .collect::<Vec<_>>();
+        let payload = member(expr, "payload");
+        if width == 0 {
+            // Without a discriminant, there is at most one variant.
+            for variant in payloads {
+                self.value(
+                    &variant.kind,
+                    &format!("{hint}_{}", variant.name),
+                    &member(&payload, &variant.name),
+                    &payload_offset,
+                );
+            }
+            return;
+        }
+        let discriminant = member(expr, "discriminant");
+        self.discriminant(e, hint, &discriminant, &discriminant_offset);
+        if payloads.is_empty() {
+            return;
+        }
+        self.line(format!("switch ({discriminant}) {{"));
+        for variant in payloads {
+            self.line(format!("case {}:", enum_constant(hint, &variant.name)));
+            self.depth += 1;
+            self.value(
+                &variant.kind,
+                &format!("{hint}_{}", variant.name),
+                &member(&payload, &variant.name),
+                &payload_offset,
+            );
+            self.line("break;".into());
+            self.depth -= 1;
+        }
+        self.line("default:".into());
+        self.line("    break;".into());
+        self.line("}".into());
+    }
+    fn value(&mut self, kind: &Kind, hint: &str, expr: &str, offset: &Offset) {
+        if kind.bits() == 0 {
+            return;
+        }
+        match kind {
+            Kind::Empty => {}
+            Kind::Bits(digits) => self.bits(expr, *digits, offset),
+            Kind::Array(array) => {
+                let index = format!("i{}", self.loops);
+                self.loops += 1;
+                self.line(format!(
+                    "for (size_t {index} = 0; {index} < {}; {index}++) {{",
+                    array.size
+                ));
+                self.depth += 1;
+                let mut offset = offset.clone();
+                offset.terms.push((index.clone(), array.base.bits()));
+                self.value(&array.base, hint, &element(expr, &index), &offset);
+                self.depth -= 1;
+                self.line("}".into());
+                self.loops -= 1;
+            }
+            Kind::Tuple(tuple) => {
+                let mut offset = offset.clone();
+                for (ndx, element) in tuple.elements.iter().enumerate() {
+                    self.value(
+                        element,
+                        &format!("{hint}_{ndx}"),
+                        &member(expr, &format!("_{ndx}")),
+                        &offset,
+                    );
+                    offset = offset.add(element.bits());
+                }
+            }
+            Kind::Struct(structure) => {
+                let mut offset = offset.clone();
+                for field in &structure.fields {
+                    self.value(
+                        &field.kind,
+                        &format!("{hint}_{}", field.name),
+                        &member(expr, &field.name),
+                        &offset,
+                    );
+                    offset = offset.add(field.kind.bits());
+                }
+            }
+            Kind::Union(union) => {
+                if let Some((kind, name)) = widest(union.fields.iter().map(|f| (&f.kind, &f.name)))
+                {
+                    self.value(kind, &format!("{hint}_{name}"), &member(expr, name), offset);
+                }
+            }
+            Kind::Enum(e) => self.enumeration(kind, e, hint, expr, offset),
+        }
+    }
+}
+
+fn function(kind: &Kind, name: &str, direction: Direction) -> Vec<String> {
+    let mut body = Body {
+        direction,
+        lines: vec![],
+        depth: 1,
+        loops: 0,
+    };
+    let upper = name.to_uppercase();
+    match direction {
+        Direction::Pack => body.line(format!("memset(buf, 0, {upper}_BYTES);")),
+        Direction::Unpack => body.line("memset(value, 0, sizeof(*value));".into()),
+    }
+    body.value(kind, name, "*value", &Offset::default());
+    body.lines
+}
+
+/// Generate a C header declaring `{name}_t`, the `{NAME}_BITS` and
+/// `{NAME}_BYTES` constants, and the functions
+/// ```c
+/// void {name}_pack(const {name}_t *value, uint8_t *buf);
+/// void {name}_unpack(const uint8_t *buf, {name}_t *value);
+/// ```
+/// where `buf` holds `{NAME}_BYTES` bytes.
+pub fn header(kind: &Kind, name: &str) -> String {
+    let upper = name.to_uppercase();
+    let mut declarations = Declarations::default();
+    let top = format!("{name}_t");
+    match declarations.declare(kind, name) {
+        Some(ty) if ty.base == top && ty.dims.is_empty() => {}
+        Some(ty) => declarations
+            .typedefs
+            .push(format!("typedef {};", render_member(&ty, &top))),
+        // C does not allow empty structs, so a kind with no bits is
+        // declared as a byte that is never packed.
+        None => declarations
+            .typedefs
+            .push(format!("typedef uint8_t {top};")),
+    }
+    let mut text = format!(
+        "#ifndef {upper}_H\n#define {upper}_H\n\n#include <stddef.h>\n#include <stdint.h>\n#include <string.h>\n\n"
+    );
+    text += HELPERS;
+    text += "\n";
+    for typedef in &declarations.typedefs {
+        text += typedef;
+        text += "\n\n";
+    }
+    let bits = kind.bits();
+    text += &format!("#define {upper}_BITS {bits}\n");
+    text += &format!("#define {upper}_BYTES {}\n\n", bits.div_ceil(8));
+    text += &format!("static inline void {name}_pack(const {top} *value, uint8_t *buf) {{\n");
+    for line in function(kind, name, Direction::Pack) {
+        text += &format!("{line}\n");
+    }
+    text += "}\n\n";
+    text += &format!("static inline void {name}_unpack(const uint8_t *buf, {top} *value) {{\n");
+    for line in function(kind, name, Direction::Unpack) {
+        text += &format!("{line}\n");
+    }
+    text += "}\n\n";
+    text += &format!("#endif /* {upper}_H */\n");
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn make_packet(alignment: DiscriminantAlignment) -> Kind {
+        Kind::make_enum(
+            vec![
+                Kind::make_variant("Idle", Kind::Empty, 0),
+                Kind::make_variant(
+                    "Color",
+                    Kind::make_struct(vec![
+                        Kind::make_field("r", Kind::make_bits(8)),
+                        Kind::make_field("g", Kind::make_array(Kind::make_bits(3), 2)),
+                    ]),
+                    1,
+                ),
+                Kind::make_variant("Level", Kind::make_tuple(vec![Kind::make_bits(4)]), -2),
+            ],
+            2,
+            alignment,
+        )
+    }
+
+    fn section<'a>(text: &'a str, start: &str) -> &'a str {
+        let begin = text.find(start).unwrap();
+        let end = begin + text[begin..].find("\n}\n").unwrap() + 3;
+        &text[begin..end]
+    }
+
+    #[test]
+    fn test_enum_declarations() {
+        let header = header(&make_packet(DiscriminantAlignment::Msb), "packet");
+        let begin = header.find("typedef enum").unwrap();
+        let end = header.find("#define PACKET_BITS").unwrap();
+        assert_eq!(
+            &header[begin..end],
+            "\
+typedef enum {
+    PACKET_IDLE = 0,
+    PACKET_COLOR = 1,
+    PACKET_LEVEL = -2
+} packet_e;
+
+typedef struct {
+    uint8_t r;
+    uint8_t g[2];
+} packet_Color_t;
+
+typedef struct {
+    uint8_t _0;
+} packet_Level_t;
+
+typedef union {
+    packet_Color_t Color;
+    packet_Level_t Level;
+} packet_payload_t;
+
+typedef struct {
+    packet_e discriminant;
+    packet_payload_t payload;
+} packet_t;
+
+"
+        );
+        assert!(header.contains("#define PACKET_BITS 16\n#define PACKET_BYTES 2\n"));
+    }
+
+    #[test]
+    fn test_enum_pack_msb() {
+        let header = header(&make_packet(DiscriminantAlignment::Msb), "packet");
+        assert_eq!(
+            section(&header, "static inline void packet_pack"),
+            "\
+static inline void packet_pack(const packet_t *value, uint8_t *buf) {
+    memset(buf, 0, PACKET_BYTES);
+    rhdl_put_bits(buf, 14, 2, (uint64_t) value->discriminant);
+    switch (value->discriminant) {
+    case PACKET_COLOR:
+        rhdl_put_bits(buf, 0, 8, (uint64_t) value->payload.Color.r);
+        for (size_t i0 = 0; i0 < 2; i0++) {
+            rhdl_put_bits(buf, i0 * 3 + 8, 3, (uint64_t) value->payload.Color.g[i0]);
+        }
+        break;
+    case PACKET_LEVEL:
+        rhdl_put_bits(buf, 0, 4, (uint64_t) value->payload.Level._0);
+        break;
+    default:
+        break;
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_enum_unpack_lsb() {
+        let header = header(&make_packet(DiscriminantAlignment::Lsb), "packet");
+        let unpack = section(&header, "static inline void packet_unpack");
+        assert!(unpack.contains(
+            "value->discriminant = (packet_e) rhdl_sign_extend(rhdl_get_bits(buf, 0, 2), 2);"
+        ));
+        assert!(unpack.contains("value->payload.Level._0 = (uint8_t) rhdl_get_bits(buf, 2, 4);"));
+    }
+
+    #[test]
+    fn test_wide_and_top_level_types() {
+        let header = header(&Kind::make_array(Kind::make_bits(100), 2), "wide");
+        assert!(header.contains("typedef uint8_t wide_t[2][13];\n"));
+        assert!(header.contains("rhdl_put_bytes(buf, i0 * 100, 100, (*value)[i0]);"));
+        assert!(header.contains("rhdl_get_bytes(buf, i0 * 100, 100, (*value)[i0]);"));
+        let header = super::header(&Kind::make_bits(12), "word");
+        assert!(header.contains("typedef uint16_t word_t;\n"));
+        assert!(header.contains("*value = (uint16_t) rhdl_get_bits(buf, 0, 12);"));
+        let header = super::header(&Kind::Empty, "nothing");
+        assert!(header.contains("typedef uint8_t nothing_t;\n"));
+        assert!(header.contains("#define NOTHING_BYTES 0\n"));
+    }
+
+    #[test]
+    fn test_union_packs_widest_field() {
+        let kind = Kind::make_union(vec![
+            Kind::make_field("lo", Kind::make_bits(4)),
+            Kind::make_field("all", Kind::make_bits(12)),
+        ]);
+        let header = header(&kind, "reg");
+        assert!(header.contains("rhdl_put_bits(buf, 0, 12, (uint64_t) value->all);"));
+        assert!(!header.contains("value->lo"));
+    }
+}
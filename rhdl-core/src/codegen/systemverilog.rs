@@ -0,0 +1,346 @@
+//! SystemVerilog `typedef`s for a [Kind].
+//!
+//! Packed structs list their most significant member first, so the
+//! members are declared in the reverse of the [Kind] order (where the
+//! first field occupies the lowest bits).  Packed unions require every
+//! member to have the same width, so narrower members are wrapped in a
+//! `_view_t` struct that pads them at the top.  An enum becomes a
+//! `typedef enum` for the discriminant, plus a packed struct holding
+//! the discriminant and a union of the variant payloads, ordered
+//! according to the [DiscriminantAlignment].
+use super::TypeRef;
+use crate::kind::{DiscriminantAlignment, Enum, Field, Kind};
+
+#[derive(Default)]
+struct Generator {
+    typedefs: Vec<String>,
+}
+
+fn render(ty: &TypeRef) -> String {
+    let dims = ty
+        .dims
+        .iter()
+        .map(|d| format!("[{}:0]", d - 1))
+        .collect::<String>();
+    if dims.is_empty() {
+        ty.base.clone()
+    } else {
+        format!("{} {}", ty.base, dims)
+    }
+}
+
+fn logic(width: usize) -> TypeRef {
+    if width == 1 {
+        TypeRef::named("logic")
+    } else {
+        TypeRef {
+            base: "logic".into(),
+            dims: vec![width],
+        }
+    }
+}
+
+impl Generator {
+    // Declare a packed struct from members listed LSB first.
+    fn packed_struct(&mut self, name: String, members: Vec<(String, TypeRef)>) -> TypeRef {
+        let mut body = "typedef struct packed {\n".to_string();
+        for (member, ty) in members.iter().rev() {
+            body += &format!("    {} {};\n", render(ty), member);
+        }
+        body += &format!("}} {};", name);
+        self.typedefs.push(body);
+        TypeRef::named(name)
+    }
+    // Pad a member of a union at the top so that it is `width` bits wide.
+    fn view(&mut self, hint: &str, ty: TypeRef, bits: usize, width: usize) -> TypeRef {
+        if bits == width {
+            return ty;
+        }
+        self.packed_struct(
+            format!("{hint}_view_t"),
+            vec![("value".into(), ty), ("_pad".into(), logic(width - bits))],
+        )
+    }
+    fn packed_union(&mut self, name: String, hint: &str, fields: &[Field]) -> Option<TypeRef> {
+        let width = fields.iter().map(|f| f.kind.bits()).max().unwrap_or(0);
+        let mut members = vec![];
+        for field in fields {
+            let member_hint = format!("{hint}_{}", field.name);
+            if let Some(ty) = self.declare(&field.kind, &member_hint) {
+                let ty = self.view(&member_hint, ty, field.kind.bits(), width);
+                members.push((field.name.clone(), ty));
+            }
+        }
+        if members.is_empty() {
+            return None;
+        }
+        let mut body = "typedef union packed {\n".to_string();
+        for (member, ty) in &members {
+            body += &format!("    {} {};\n", render(ty), member);
+        }
+        body += &format!("}} {};", name);
+        self.typedefs.push(body);
+        Some(TypeRef::named(name))
+    }
+    fn declare_enum(&mut self, e: &Enum, hint: &str) -> Option<TypeRef> {
+        let width = e.discriminant_width;
+        let discriminant = (width > 0).then(|| {
+            let prefix = hint.to_uppercase();
+            let mask = if width >= 64 {
+                u64::MAX
+            } else {
+                (1 << width) - 1
+            };
+            let mut body = format!("typedef enum {} {{\n", render(&logic(width)));
+            let constants = e
+                .variants
+                .iter()
+                .map(|v| {
+                    format!(
+                        "    {}_{} = {}'h{:x}",
+                        prefix,
+                        v.name.to_uppercase(),
+                        width,
+                        v.discriminant as u64 & mask
+                    )
+                })
+                .collect::<Vec<_>>();
+            body += &constants.join(",\n");
+            body += &format!("\n}} {hint}_e;");
+            self.typedefs.push(body);
+            TypeRef::named(format!("{hint}_e"))
+        });
+        let payloads = e
+            .variants
+            .iter()
+            .map(|v| Field {
+                name: v.name.clone(),
+                kind: v.kind.clone(),
+            })
+            .collect::<Vec<_>>();
+        let payload = self.packed_union(format!("{hint}_payload_t"), hint, &payloads);
+        let (discriminant, payload) = match (discriminant, payload) {
+            (Some(discriminant), Some(payload)) => (discriminant, payload),
+            (discriminant, payload) => return discriminant.or(payload),
+        };
+        let members = match e.discriminant_alignment {
+            DiscriminantAlignment::Lsb => vec![
+                ("discriminant".into(), discriminant),
+                ("payload".into(), payload),
+            ],
+            DiscriminantAlignment::Msb => vec![
+                ("payload".into(), payload),
+                ("discriminant".into(), discriminant),
+            ],
+        };
+        Some(self.packed_struct(format!("{hint}_t"), members))
+    }
+    fn declare(&mut self, kind: &Kind, hint: &str) -> Option<TypeRef> {
+        if kind.bits() == 0 {
+            return None;
+        }
+        match kind {
+            Kind::Empty => None,
+            Kind::Bits(digits) => Some(logic(*digits)),
+            Kind::Array(array) => {
+                let mut ty = self.declare(&array.base, hint)?;
+                ty.dims.insert(0, array.size);
+                Some(ty)
+            }
+            Kind::Tuple(tuple) => {
+                let members = tuple
+                    .elements
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(ndx, element)| {
+                        let ty = self.declare(element, &format!("{hint}_{ndx}"))?;
+                        Some((format!("_{ndx}"), ty))
+                    })
+                    .collect();
+                Some(self.packed_struct(format!("{hint}_t"), members))
+            }
+            Kind::Struct(structure) => {
+                let members = structure
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        let ty = self.declare(&field.kind, &format!("{hint}_{}", field.name))?;
+                        Some((field.name.clone(), ty))
+                    })
+                    .collect();
+                Some(self.packed_struct(format!("{hint}_t"), members))
+            }
+            Kind::Union(union) => self.packed_union(format!("{hint}_t"), hint, &union.fields),
+            Kind::Enum(e) => self.declare_enum(e, hint),
+        }
+    }
+}
+
+/// Generate the `typedef`s needed to declare `kind` in SystemVerilog.
+/// The top level type is named `{name}_t`, and the declarations are
+/// listed so that every type is declared before it is used.
+/// ```
+/// # use rhdl_core::{codegen::systemverilog, Kind};
+/// let kind = Kind::make_struct(vec![
+///     Kind::make_field("valid", Kind::make_bits(1)),
+///     Kind::make_field("data", Kind::make_array(Kind::make_bits(8), 4)),
+/// ]);
+/// assert_eq!(
+///     systemverilog::declarations(&kind, "beat"),
+///     "\
+/// typedef struct packed {
+///     logic [3:0][7:0] data;
+///     logic valid;
+/// } beat_t;
+/// "
+/// );
+/// ```
+pub fn declarations(kind: &Kind, name: &str) -> String {
+    let mut generator = Generator::default();
+    if let Some(ty) = generator.declare(kind, name) {
+        let top = format!("{name}_t");
+        if ty.base != top || !ty.dims.is_empty() {
+            generator
+                .typedefs
+                .push(format!("typedef {} {};", render(&ty), top));
+        }
+    }
+    generator
+        .typedefs
+        .iter()
+        .map(|x| format!("{x}\n"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn make_packet(alignment: DiscriminantAlignment) -> Kind {
+        Kind::make_enum(
+            vec![
+                Kind::make_variant("Idle", Kind::Empty, 0),
+                Kind::make_variant(
+                    "Color",
+                    Kind::make_struct(vec![
+                        Kind::make_field("r", Kind::make_bits(8)),
+                        Kind::make_field("g", Kind::make_bits(8)),
+                    ]),
+                    1,
+                ),
+                Kind::make_variant("Level", Kind::make_tuple(vec![Kind::make_bits(4)]), -2),
+            ],
+            2,
+            alignment,
+        )
+    }
+
+    #[test]
+    fn test_enum_msb() {
+        let expected = "\
+typedef enum logic [1:0] {
+    PACKET_IDLE = 2'h0,
+    PACKET_COLOR = 2'h1,
+    PACKET_LEVEL = 2'h2
+} packet_e;
+
+typedef struct packed {
+    logic [7:0] g;
+    logic [7:0] r;
+} packet_Color_t;
+
+typedef struct packed {
+    logic [3:0] _0;
+} packet_Level_t;
+
+typedef struct packed {
+    logic [11:0] _pad;
+    packet_Level_t value;
+} packet_Level_view_t;
+
+typedef union packed {
+    packet_Color_t Color;
+    packet_Level_view_t Level;
+} packet_payload_t;
+
+typedef struct packed {
+    packet_e discriminant;
+    packet_payload_t payload;
+} packet_t;
+";
+        assert_eq!(
+            declarations(&make_packet(DiscriminantAlignment::Msb), "packet"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_enum_lsb() {
+        let sv = declarations(&make_packet(DiscriminantAlignment::Lsb), "packet");
+        assert!(sv.ends_with(
+            "\
+typedef struct packed {
+    packet_payload_t payload;
+    packet_e discriminant;
+} packet_t;
+"
+        ));
+    }
+
+    #[test]
+    fn test_simple_kinds() {
+        assert_eq!(
+            declarations(&Kind::make_bits(12), "word"),
+            "typedef logic [11:0] word_t;\n"
+        );
+        assert_eq!(
+            declarations(&Kind::make_array(Kind::make_bits(1), 3), "flags"),
+            "typedef logic [2:0] flags_t;\n"
+        );
+        assert_eq!(declarations(&Kind::Empty, "nothing"), "");
+        let state = Kind::make_enum(
+            vec![
+                Kind::make_variant("Off", Kind::Empty, 0),
+                Kind::make_variant("On", Kind::Empty, 1),
+            ],
+            1,
+            DiscriminantAlignment::Msb,
+        );
+        assert_eq!(
+            declarations(&state, "state"),
+            "\
+typedef enum logic {
+    STATE_OFF = 1'h0,
+    STATE_ON = 1'h1
+} state_e;
+
+typedef state_e state_t;
+"
+        );
+    }
+
+    #[test]
+    fn test_union_views() {
+        let kind = Kind::make_union(vec![
+            Kind::make_field("word", Kind::make_bits(16)),
+            Kind::make_field("bytes", Kind::make_array(Kind::make_bits(8), 2)),
+            Kind::make_field("nibble", Kind::make_bits(4)),
+        ]);
+        assert_eq!(
+            declarations(&kind, "reg"),
+            "\
+typedef struct packed {
+    logic [11:0] _pad;
+    logic [3:0] value;
+} reg_nibble_view_t;
+
+typedef union packed {
+    logic [15:0] word;
+    logic [1:0][7:0] bytes;
+    reg_nibble_view_t nibble;
+} reg_t;
+"
+        );
+    }
+}
@@ -1,9 +1,11 @@
-use rhdl_bits::{Bits, SignedBits};
+use std::{collections::HashMap, ops::Range};
+
+use rhdl_bits::{Bits, Fixed, LogicVec, SignedBits, SignedWideBits, UFixed, WideBits};
 
 use crate::{
     logger::LoggerImpl,
-    path::{bit_range, Path},
-    Kind, LogBuilder, TagID,
+    path::{bit_range, path_string, Path},
+    DiscriminantAlignment, Kind, LogBuilder, TagID, TypedBits,
 };
 
 /// This is the core trait for all of `RHDL` data elements.  If you
@@ -17,19 +19,27 @@ use crate::{
 ///
 /// # Primitive types
 ///
-/// Only the `bool` type is directly supported.  Otherwise,
-/// use [Bits] or [SignedBits] to ensure that the arithmetic
-/// operations model the behavior of the hardware.
+/// The `bool` type and the fixed width integers (`u8` through
+/// `u128` and `i8` through `i128`) are supported, and are
+/// represented by their two's complement bits.  `usize` and
+/// `isize` are not supported, since their width depends on
+/// the host.  For arithmetic, prefer [Bits] or [SignedBits] to
+/// ensure that the operations model the behavior of the hardware.
+///
+/// # Unit
+///
+/// The unit type `()` is supported, and occupies no bits.
 ///
-/// # String, Byte Array, Unit, Unit Struct, Sequence, Map
+/// # String, Byte Array, Unit Struct, Sequence, Map
 ///
 /// These are all unsupported on a hardware target.  They either
 /// have variable size or no size at all.
 ///
-/// # Option
+/// # Option, Result
 ///
 /// The option _is_ supported in `RHDL`.  It is represented as
 /// an enum with two variants, precisely as it is in Rust.
+/// The same is true of `Result`.
 ///
 /// # Enum Variants
 ///
@@ -40,7 +50,8 @@ use crate::{
 ///
 /// # Structs, Tuples, Arrays, Unions
 ///
-/// These are all supported in `RHDL`.
+/// These are all supported in `RHDL`.  Tuples are supported up
+/// to 12 elements.
 ///
 pub trait Digital: Copy + PartialEq + Sized + Clone {
     fn static_kind() -> Kind;
@@ -48,6 +59,10 @@ pub trait Digital: Copy + PartialEq + Sized + Clone {
         Self::static_kind()
     }
     fn bin(self) -> Vec<bool>;
+    /// Reconstruct a value from its binary representation (the
+    /// inverse of [Digital::bin]).  The slice must be exactly
+    /// as wide as the type.
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError>;
     fn binary_string(self) -> String {
         self.bin()
             .iter()
@@ -55,17 +70,157 @@ pub trait Digital: Copy + PartialEq + Sized + Clone {
             .map(|b| if *b { '1' } else { '0' })
             .collect()
     }
+    /// Pair the binary representation of the value with its [Kind].
+    fn typed_bits(self) -> TypedBits {
+        TypedBits::new(self.kind(), self.bin()).expect("bin() is as wide as the kind")
+    }
     fn path(self, path: &[Path]) -> anyhow::Result<(Vec<bool>, Kind)> {
         let (range, kind) = bit_range(self.kind(), path)?;
-        dbg!(self.binary_string());
-        dbg!(&range);
         Ok((self.bin()[range].to_vec(), kind))
     }
+    /// Replace the bits at the end of a path (the inverse of
+    /// [Digital::path]).  Writing to the payload of an enum
+    /// variant that is not active is an error.  So is writing to a
+    /// value that its bits cannot rebuild (such as one holding a
+    /// [LogicVec] with `X` or `Z` bits), since the rest of the value
+    /// would be lost.
+    fn with_path(self, path: &[Path], bits: &[bool]) -> anyhow::Result<Self> {
+        if Self::from_bin(&self.bin()).ok() != Some(self) {
+            anyhow::bail!(
+                "Cannot write to {} of a value that cannot be rebuilt from its bits",
+                path_string(path)
+            );
+        }
+        let value = self.typed_bits().with_path(path, bits)?;
+        Ok(Self::from_bin(value.bits())?)
+    }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder);
     fn record<T: Digital>(&self, tag: TagID<T>, logger: impl LoggerImpl);
     fn skip<T: Digital>(tag: TagID<T>, logger: impl LoggerImpl);
 }
 
+/// The error returned when a value cannot be reconstructed
+/// from its binary representation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromBinError {
+    /// The number of bits does not match the width of the type.
+    WrongLength { expected: usize, actual: usize },
+    /// The discriminant does not match any variant of the enum.
+    InvalidDiscriminant { value: i64 },
+}
+
+impl std::fmt::Display for FromBinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FromBinError::WrongLength { expected, actual } => {
+                write!(f, "expected {} bits, but got {}", expected, actual)
+            }
+            FromBinError::InvalidDiscriminant { value } => {
+                write!(f, "invalid discriminant {}", value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromBinError {}
+
+/// Check that a slice of bits is exactly as wide as the type `T`.
+pub fn check_bin_len<T: Digital>(bits: &[bool]) -> Result<(), FromBinError> {
+    let expected = T::static_kind().bits();
+    if bits.len() != expected {
+        return Err(FromBinError::WrongLength {
+            expected,
+            actual: bits.len(),
+        });
+    }
+    Ok(())
+}
+
+/// The ranges of bits occupied by the named fields of a struct
+/// kind, in the order of the names.  This is used by the derived
+/// impls of structs with layout attributes, so that their binary
+/// representation always agrees with their [Kind].  The fields are
+/// located in a single pass over the struct.
+pub fn field_ranges(kind: &Kind, names: &[&str]) -> Vec<Range<usize>> {
+    let Kind::Struct(structure) = kind else {
+        panic!("field_ranges called on a non-struct kind {:?}", kind);
+    };
+    let slots = names
+        .iter()
+        .enumerate()
+        .map(|(slot, name)| (*name, slot))
+        .collect::<HashMap<_, _>>();
+    let mut ranges = vec![None; names.len()];
+    let mut offset = 0;
+    for field in &structure.fields {
+        let width = field.kind.bits();
+        if let Some(slot) = slots.get(field.name.as_str()) {
+            ranges[*slot] = Some(offset..offset + width);
+        }
+        offset += width;
+    }
+    ranges
+        .into_iter()
+        .map(|range| range.expect("field is not part of the kind"))
+        .collect()
+}
+
+/// Decode a value of type `T` from the front of a slice
+/// of bits, and advance the slice past it.
+pub fn take_bin<T: Digital>(bits: &mut &[bool]) -> Result<T, FromBinError> {
+    let expected = T::static_kind().bits();
+    if bits.len() < expected {
+        return Err(FromBinError::WrongLength {
+            expected,
+            actual: bits.len(),
+        });
+    }
+    let (value, rest) = bits.split_at(expected);
+    *bits = rest;
+    T::from_bin(value)
+}
+
+/// Split the binary representation of an enum into the value of the
+/// discriminant and the bits of the payload.  The discriminant is
+/// signed if any of the variants have a negative discriminant.
+pub fn split_discriminant<'a>(
+    kind: &Kind,
+    bits: &'a [bool],
+) -> Result<(i64, &'a [bool]), FromBinError> {
+    let Kind::Enum(e) = kind else {
+        panic!("split_discriminant called on a non-enum kind {:?}", kind);
+    };
+    if bits.len() != kind.bits() {
+        return Err(FromBinError::WrongLength {
+            expected: kind.bits(),
+            actual: bits.len(),
+        });
+    }
+    let width = e.discriminant_width;
+    let (discriminant, payload) = match e.discriminant_alignment {
+        DiscriminantAlignment::Lsb => {
+            let (discriminant, payload) = bits.split_at(width);
+            (discriminant, payload)
+        }
+        DiscriminantAlignment::Msb => {
+            let (payload, discriminant) = bits.split_at(bits.len() - width);
+            (discriminant, payload)
+        }
+    };
+    let mut value = bin_to_u128(discriminant) as i64;
+    let signed = e.variants.iter().any(|x| x.discriminant < 0);
+    if signed && width > 0 && width < 64 && value & (1 << (width - 1)) != 0 {
+        value |= -1 << width;
+    }
+    Ok((value, payload))
+}
+
+fn bin_to_u128(bits: &[bool]) -> u128 {
+    bits.iter()
+        .rev()
+        .fold(0, |acc, bit| (acc << 1) | (*bit as u128))
+}
+
 impl Digital for bool {
     fn static_kind() -> Kind {
         Kind::make_bits(1)
@@ -73,6 +228,10 @@ impl Digital for bool {
     fn bin(self) -> Vec<bool> {
         vec![self]
     }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)?;
+        Ok(bits[0])
+    }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
         builder.allocate(tag, 1);
     }
@@ -84,169 +243,296 @@ impl Digital for bool {
     }
 }
 
-impl Digital for u8 {
+// The primitive integers are represented by their two's complement
+// bits, and behave like the [Bits] or [SignedBits] of the same width.
+macro_rules! impl_digital_for_unsigned {
+    ($($t:ty => $n:expr),* $(,)?) => {
+        $(
+            impl Digital for $t {
+                fn static_kind() -> Kind {
+                    Kind::make_bits($n)
+                }
+                fn bin(self) -> Vec<bool> {
+                    Bits::<$n>::from(self as u128).to_bools()
+                }
+                fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+                    check_bin_len::<Self>(bits)?;
+                    Ok(bin_to_u128(bits) as $t)
+                }
+                fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
+                    builder.allocate(tag, $n);
+                }
+                fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
+                    logger.write_bits(tag, *self as u128);
+                }
+                fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
+                    logger.skip(tag);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_digital_for_signed {
+    ($($t:ty => $n:expr),* $(,)?) => {
+        $(
+            impl Digital for $t {
+                fn static_kind() -> Kind {
+                    Kind::make_bits($n)
+                }
+                fn bin(self) -> Vec<bool> {
+                    SignedBits::<$n>::from(self as i128).as_unsigned().to_bools()
+                }
+                fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+                    check_bin_len::<Self>(bits)?;
+                    Ok(bin_to_u128(bits) as $t)
+                }
+                fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
+                    builder.allocate(tag, $n);
+                }
+                fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
+                    logger.write_bits(tag, SignedBits::<$n>::from(*self as i128).as_unsigned().raw());
+                }
+                fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
+                    logger.skip(tag);
+                }
+            }
+        )*
+    };
+}
+
+impl_digital_for_unsigned!(u8 => 8, u16 => 16, u32 => 32, u64 => 64, u128 => 128);
+impl_digital_for_signed!(i8 => 8, i16 => 16, i32 => 32, i64 => 64, i128 => 128);
+
+impl<const N: usize> Digital for Bits<N> {
     fn static_kind() -> Kind {
-        Kind::make_bits(8)
+        Kind::make_bits(N)
     }
     fn bin(self) -> Vec<bool> {
-        Bits::<8>::from(self as u128).to_bools()
+        self.to_bools()
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)?;
+        Ok(Bits::from(bin_to_u128(bits)))
     }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
-        builder.allocate(tag, 8);
+        builder.allocate(tag, N);
     }
     fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
-        logger.write_bits(tag, Bits::<8>::from(*self as u128).raw());
+        logger.write_bits(tag, self.raw());
     }
     fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
         logger.skip(tag);
     }
 }
 
-impl Digital for u16 {
+impl<const N: usize> Digital for SignedBits<N> {
     fn static_kind() -> Kind {
-        Kind::make_bits(16)
+        Kind::make_bits(N)
     }
     fn bin(self) -> Vec<bool> {
-        Bits::<16>::from(self as u128).to_bools()
+        self.as_unsigned().to_bools()
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)?;
+        Ok(Bits::<N>::from(bin_to_u128(bits)).as_signed())
     }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
-        builder.allocate(tag, 16);
+        builder.allocate(tag, N);
     }
     fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
-        logger.write_bits(tag, Bits::<16>::from(*self as u128).raw());
+        logger.write_bits(tag, self.as_unsigned().raw());
     }
     fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
         logger.skip(tag);
     }
 }
 
-impl<const N: usize> Digital for Bits<N> {
+impl<const N: usize> Digital for WideBits<N> {
     fn static_kind() -> Kind {
         Kind::make_bits(N)
     }
     fn bin(self) -> Vec<bool> {
         self.to_bools()
     }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)?;
+        let mut value = WideBits::default();
+        for (ndx, bit) in bits.iter().enumerate() {
+            value.set_bit(ndx, *bit);
+        }
+        Ok(value)
+    }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
         builder.allocate(tag, N);
     }
     fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
-        logger.write_bits(tag, self.raw());
+        logger.write_wide_bits(tag, self.limbs());
     }
     fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
         logger.skip(tag);
     }
 }
 
-impl<const N: usize> Digital for SignedBits<N> {
+impl<const N: usize> Digital for SignedWideBits<N> {
     fn static_kind() -> Kind {
         Kind::make_bits(N)
     }
     fn bin(self) -> Vec<bool> {
-        self.as_unsigned().to_bools()
+        self.to_bools()
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        Ok(WideBits::<N>::from_bin(bits)?.as_signed())
     }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
         builder.allocate(tag, N);
     }
     fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
-        logger.write_bits(tag, self.as_unsigned().raw());
+        logger.write_wide_bits(tag, self.as_unsigned().limbs());
     }
     fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
         logger.skip(tag);
     }
 }
 
-// Add blanket implementation for tuples up to size 4.
-impl<T0: Digital, T1: Digital> Digital for (T0, T1) {
+impl<const I: usize, const F: usize> Digital for Fixed<I, F> {
     fn static_kind() -> Kind {
-        Kind::make_tuple(vec![T0::static_kind(), T1::static_kind()])
+        Kind::make_bits(I + F)
     }
     fn bin(self) -> Vec<bool> {
-        let mut v = self.0.bin();
-        v.extend(self.1.bin());
-        v
+        (0..I + F).map(|bit| self.raw() & (1 << bit) != 0).collect()
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)?;
+        let shift = 128 - I - F;
+        Ok(Self::from_raw(
+            ((bin_to_u128(bits) << shift) as i128) >> shift,
+        ))
     }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
-        T0::allocate(tag, builder.namespace("0"));
-        T1::allocate(tag, builder.namespace("1"));
+        builder.allocate(tag, I + F);
     }
     fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
-        self.0.record(tag, &mut logger);
-        self.1.record(tag, &mut logger);
+        logger.write_bits(tag, self.raw() as u128 & (u128::MAX >> (128 - I - F)));
     }
     fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
-        T0::skip(tag, &mut logger);
-        T1::skip(tag, &mut logger);
+        logger.skip(tag);
     }
 }
 
-impl<T0: Digital, T1: Digital, T2: Digital> Digital for (T0, T1, T2) {
+impl<const I: usize, const F: usize> Digital for UFixed<I, F> {
     fn static_kind() -> Kind {
-        Kind::make_tuple(vec![
-            T0::static_kind(),
-            T1::static_kind(),
-            T2::static_kind(),
-        ])
+        Kind::make_bits(I + F)
     }
     fn bin(self) -> Vec<bool> {
-        let mut v = self.0.bin();
-        v.extend(self.1.bin());
-        v.extend(self.2.bin());
-        v
+        (0..I + F).map(|bit| self.raw() & (1 << bit) != 0).collect()
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)?;
+        Ok(Self::from_raw(bin_to_u128(bits)))
     }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
-        T0::allocate(tag, builder.namespace("0"));
-        T1::allocate(tag, builder.namespace("1"));
-        T2::allocate(tag, builder.namespace("2"));
+        builder.allocate(tag, I + F);
     }
     fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
-        self.0.record(tag, &mut logger);
-        self.1.record(tag, &mut logger);
-        self.2.record(tag, &mut logger);
+        logger.write_bits(tag, self.raw());
     }
     fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
-        T0::skip(tag, &mut logger);
-        T1::skip(tag, &mut logger);
-        T2::skip(tag, &mut logger);
+        logger.skip(tag);
     }
 }
 
-impl<T0: Digital, T1: Digital, T2: Digital, T3: Digital> Digital for (T0, T1, T2, T3) {
+// The binary form of a LogicVec only has room for the aval
+// plane.  The full 4-state value is kept in the log.
+impl<const N: usize> Digital for LogicVec<N> {
     fn static_kind() -> Kind {
-        Kind::make_tuple(vec![
-            T0::static_kind(),
-            T1::static_kind(),
-            T2::static_kind(),
-            T3::static_kind(),
-        ])
+        Kind::make_bits(N)
     }
     fn bin(self) -> Vec<bool> {
-        let mut v = self.0.bin();
-        v.extend(self.1.bin());
-        v.extend(self.2.bin());
-        v.extend(self.3.bin());
-        v
+        (0..N).map(|bit| self.aval() & (1 << bit) != 0).collect()
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)?;
+        Ok(Self::from_raw(bin_to_u128(bits), 0))
     }
     fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
-        T0::allocate(tag, builder.namespace("0"));
-        T1::allocate(tag, builder.namespace("1"));
-        T2::allocate(tag, builder.namespace("2"));
-        T3::allocate(tag, builder.namespace("3"));
+        builder.allocate_logic(tag, N);
     }
     fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
-        self.0.record(tag, &mut logger);
-        self.1.record(tag, &mut logger);
-        self.2.record(tag, &mut logger);
-        self.3.record(tag, &mut logger);
+        logger.write_logic(tag, self.aval(), self.bval());
     }
     fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
-        T0::skip(tag, &mut logger);
-        T1::skip(tag, &mut logger);
-        T2::skip(tag, &mut logger);
-        T3::skip(tag, &mut logger);
+        logger.skip(tag);
     }
 }
 
+impl Digital for () {
+    fn static_kind() -> Kind {
+        Kind::Empty
+    }
+    fn bin(self) -> Vec<bool> {
+        vec![]
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)
+    }
+    fn allocate<T: Digital>(_tag: TagID<T>, _builder: impl LogBuilder) {}
+    fn record<T: Digital>(&self, _tag: TagID<T>, _logger: impl LoggerImpl) {}
+    fn skip<T: Digital>(_tag: TagID<T>, _logger: impl LoggerImpl) {}
+}
+
+// Add blanket implementation for tuples up to size 12.
+macro_rules! impl_digital_for_tuple {
+    ($($t:ident => $ndx:tt),+) => {
+        impl<$($t: Digital),+> Digital for ($($t,)+) {
+            fn static_kind() -> Kind {
+                Kind::make_tuple(vec![$($t::static_kind()),+])
+            }
+            fn bin(self) -> Vec<bool> {
+                let mut v = Vec::new();
+                $(v.extend(self.$ndx.bin());)+
+                v
+            }
+            fn from_bin(mut bits: &[bool]) -> Result<Self, FromBinError> {
+                check_bin_len::<Self>(bits)?;
+                Ok(($(take_bin::<$t>(&mut bits)?,)+))
+            }
+            fn allocate<T: Digital>(tag: TagID<T>, builder: impl LogBuilder) {
+                $($t::allocate(tag, builder.namespace(stringify!($ndx)));)+
+            }
+            fn record<T: Digital>(&self, tag: TagID<T>, mut logger: impl LoggerImpl) {
+                $(self.$ndx.record(tag, &mut logger);)+
+            }
+            fn skip<T: Digital>(tag: TagID<T>, mut logger: impl LoggerImpl) {
+                $($t::skip(tag, &mut logger);)+
+            }
+        }
+    };
+}
+
+impl_digital_for_tuple!(T0 => 0);
+impl_digital_for_tuple!(T0 => 0, T1 => 1);
+impl_digital_for_tuple!(T0 => 0, T1 => 1, T2 => 2);
+impl_digital_for_tuple!(T0 => 0, T1 => 1, T2 => 2, T3 => 3);
+impl_digital_for_tuple!(T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4);
+impl_digital_for_tuple!(T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5);
+impl_digital_for_tuple!(T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5, T6 => 6);
+impl_digital_for_tuple!(T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5, T6 => 6, T7 => 7);
+impl_digital_for_tuple!(
+    T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5, T6 => 6, T7 => 7, T8 => 8
+);
+impl_digital_for_tuple!(
+    T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5, T6 => 6, T7 => 7, T8 => 8, T9 => 9
+);
+impl_digital_for_tuple!(
+    T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5, T6 => 6, T7 => 7, T8 => 8, T9 => 9,
+    T10 => 10
+);
+impl_digital_for_tuple!(
+    T0 => 0, T1 => 1, T2 => 2, T3 => 3, T4 => 4, T5 => 5, T6 => 6, T7 => 7, T8 => 8, T9 => 9,
+    T10 => 10, T11 => 11
+);
+
 impl<T: Digital, const N: usize> Digital for [T; N] {
     fn static_kind() -> Kind {
         Kind::make_array(T::static_kind(), N)
@@ -258,6 +544,13 @@ impl<T: Digital, const N: usize> Digital for [T; N] {
         }
         v
     }
+    fn from_bin(mut bits: &[bool]) -> Result<Self, FromBinError> {
+        check_bin_len::<Self>(bits)?;
+        let elements = (0..N)
+            .map(|_| take_bin(&mut bits))
+            .collect::<Result<Vec<T>, _>>()?;
+        Ok(std::array::from_fn(|ndx| elements[ndx]))
+    }
     fn allocate<U: Digital>(tag: TagID<U>, builder: impl LogBuilder) {
         for i in 0..N {
             T::allocate(tag, builder.namespace(&format!("{}", i)));
@@ -275,9 +568,120 @@ impl<T: Digital, const N: usize> Digital for [T; N] {
     }
 }
 
+// Option and Result are laid out exactly as the derive macro lays
+// out the equivalent enums, with a one bit discriminant in the most
+// significant position.
+impl<T: Digital> Digital for Option<T> {
+    fn static_kind() -> Kind {
+        Kind::make_enum(
+            vec![
+                Kind::make_variant(stringify!(None), Kind::Empty, 0),
+                Kind::make_variant(
+                    stringify!(Some),
+                    Kind::make_tuple(vec![T::static_kind()]),
+                    1,
+                ),
+            ],
+            1,
+            DiscriminantAlignment::Msb,
+        )
+    }
+    fn bin(self) -> Vec<bool> {
+        self.kind().pad(match self {
+            None => vec![false],
+            Some(x) => {
+                let mut v = vec![true];
+                v.extend(x.bin());
+                v
+            }
+        })
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        let (discriminant, mut bits) = split_discriminant(&Self::static_kind(), bits)?;
+        match discriminant {
+            0 => Ok(None),
+            _ => Ok(Some(take_bin(&mut bits)?)),
+        }
+    }
+    fn allocate<L: Digital>(tag: TagID<L>, builder: impl LogBuilder) {
+        builder.namespace("$disc").allocate(tag, 0);
+        let builder = builder.namespace(stringify!(Some));
+        T::allocate(tag, builder.namespace("0"));
+    }
+    fn record<L: Digital>(&self, tag: TagID<L>, mut logger: impl LoggerImpl) {
+        match self {
+            None => {
+                logger.write_string(tag, stringify!(None));
+                T::skip(tag, &mut logger);
+            }
+            Some(x) => {
+                logger.write_string(tag, stringify!(Some));
+                x.record(tag, &mut logger);
+            }
+        }
+    }
+    fn skip<L: Digital>(tag: TagID<L>, mut logger: impl LoggerImpl) {
+        logger.skip(tag);
+        T::skip(tag, &mut logger);
+    }
+}
+
+impl<T: Digital, E: Digital> Digital for Result<T, E> {
+    fn static_kind() -> Kind {
+        Kind::make_enum(
+            vec![
+                Kind::make_variant(stringify!(Ok), Kind::make_tuple(vec![T::static_kind()]), 0),
+                Kind::make_variant(stringify!(Err), Kind::make_tuple(vec![E::static_kind()]), 1),
+            ],
+            1,
+            DiscriminantAlignment::Msb,
+        )
+    }
+    fn bin(self) -> Vec<bool> {
+        let (discriminant, payload) = match self {
+            Ok(x) => (false, x.bin()),
+            Err(e) => (true, e.bin()),
+        };
+        let mut v = vec![discriminant];
+        v.extend(payload);
+        self.kind().pad(v)
+    }
+    fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+        let (discriminant, mut bits) = split_discriminant(&Self::static_kind(), bits)?;
+        match discriminant {
+            0 => Ok(Ok(take_bin(&mut bits)?)),
+            _ => Ok(Err(take_bin(&mut bits)?)),
+        }
+    }
+    fn allocate<L: Digital>(tag: TagID<L>, builder: impl LogBuilder) {
+        builder.namespace("$disc").allocate(tag, 0);
+        T::allocate(tag, builder.namespace(stringify!(Ok)).namespace("0"));
+        E::allocate(tag, builder.namespace(stringify!(Err)).namespace("0"));
+    }
+    fn record<L: Digital>(&self, tag: TagID<L>, mut logger: impl LoggerImpl) {
+        match self {
+            Ok(x) => {
+                logger.write_string(tag, stringify!(Ok));
+                x.record(tag, &mut logger);
+                E::skip(tag, &mut logger);
+            }
+            Err(e) => {
+                logger.write_string(tag, stringify!(Err));
+                T::skip(tag, &mut logger);
+                e.record(tag, &mut logger);
+            }
+        }
+    }
+    fn skip<L: Digital>(tag: TagID<L>, mut logger: impl LoggerImpl) {
+        logger.skip(tag);
+        T::skip(tag, &mut logger);
+        E::skip(tag, &mut logger);
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use std::iter::repeat;
+    use std::iter::repeat_n;
 
     use super::*;
     use crate::kind::{DiscriminantAlignment, Variant};
@@ -361,11 +765,27 @@ mod test {
                 };
                 if raw.len() < self.kind().bits() {
                     let missing = self.kind().bits() - raw.len();
-                    raw.into_iter().chain(repeat(false).take(missing)).collect()
+                    raw.into_iter().chain(repeat_n(false, missing)).collect()
                 } else {
                     raw
                 }
             }
+            fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+                let (discriminant, mut bits) = split_discriminant(&Self::static_kind(), bits)?;
+                match discriminant {
+                    0 => Ok(Self::None),
+                    1 => Ok(Self::Bool(take_bin(&mut bits)?)),
+                    2 => Ok(Self::Tuple(take_bin(&mut bits)?, take_bin(&mut bits)?)),
+                    3 => Ok(Self::Array(take_bin(&mut bits)?)),
+                    4 => Ok(Self::Strct {
+                        a: take_bin(&mut bits)?,
+                        b: take_bin(&mut bits)?,
+                    }),
+                    _ => Err(FromBinError::InvalidDiscriminant {
+                        value: discriminant,
+                    }),
+                }
+            }
             fn allocate<L: Digital>(tag: TagID<L>, builder: impl LogBuilder) {
                 builder.allocate(tag, 0);
                 <bool as Digital>::allocate(tag, builder.namespace("Bool"));
@@ -429,6 +849,18 @@ mod test {
                 <(bool, Bits<3>) as Digital>::skip(tag, &mut logger);
             }
         }
+        for value in [
+            Mixed::None,
+            Mixed::Bool(true),
+            Mixed::Tuple(true, Bits::from(5)),
+            Mixed::Array([true, false, true]),
+            Mixed::Strct {
+                a: false,
+                b: Bits::from(3),
+            },
+        ] {
+            assert!(Mixed::from_bin(&value.bin()) == Ok(value));
+        }
         println!("{:?}", Mixed::None.bin());
         println!("{:?}", Mixed::Bool(true).bin());
         println!("{}", crate::text_grid(&Mixed::static_kind(), "val"));
@@ -493,6 +925,19 @@ mod test {
                     Self::Boom => rhdl_bits::bits::<3>(4).to_bools(),
                 }
             }
+            fn from_bin(bits: &[bool]) -> Result<Self, FromBinError> {
+                let (discriminant, _) = split_discriminant(&Self::static_kind(), bits)?;
+                match discriminant {
+                    0 => Ok(Self::Init),
+                    1 => Ok(Self::Boot),
+                    2 => Ok(Self::Running),
+                    3 => Ok(Self::Stop),
+                    4 => Ok(Self::Boom),
+                    _ => Err(FromBinError::InvalidDiscriminant {
+                        value: discriminant,
+                    }),
+                }
+            }
             fn allocate<L: Digital>(tag: TagID<L>, builder: impl LogBuilder) {
                 builder.allocate(tag, 0);
             }
@@ -511,6 +956,18 @@ mod test {
         }
         let val = State::Boom;
         assert_eq!(val.bin(), rhdl_bits::bits::<3>(4).to_bools());
+        assert!(State::from_bin(&val.bin()) == Ok(val));
+        assert!(
+            State::from_bin(&rhdl_bits::bits::<3>(6).to_bools())
+                == Err(FromBinError::InvalidDiscriminant { value: 6 })
+        );
+        assert!(
+            State::from_bin(&[true])
+                == Err(FromBinError::WrongLength {
+                    expected: 3,
+                    actual: 1
+                })
+        );
         assert_eq!(
             val.kind(),
             Kind::make_enum(
@@ -546,4 +1003,115 @@ mod test {
             )
         );
     }
+
+    fn round_trip<T: Digital + std::fmt::Debug>(x: T) {
+        assert_eq!(x.bin().len(), T::static_kind().bits());
+        assert_eq!(T::from_bin(&x.bin()), Ok(x));
+    }
+
+    #[test]
+    fn test_primitive_integers() {
+        for x in [0_u32, 1, 0xDEAD_BEEF, u32::MAX] {
+            round_trip(x);
+        }
+        for x in [0_i8, 1, -1, i8::MIN, i8::MAX] {
+            round_trip(x);
+        }
+        round_trip(u64::MAX);
+        round_trip(u128::MAX);
+        round_trip(i16::MIN);
+        round_trip(i32::MIN);
+        round_trip(i64::MIN);
+        round_trip(i128::MIN);
+        assert_eq!((-2_i8).bin(), SignedBits::<8>::from(-2).bin());
+        assert_eq!(0xABCD_u16.bin(), Bits::<16>::from(0xABCD).bin());
+        assert_eq!(i64::static_kind(), Kind::make_bits(64));
+    }
+
+    #[test]
+    fn test_unit_and_large_tuples() {
+        round_trip(());
+        assert_eq!(<()>::static_kind(), Kind::Empty);
+        round_trip((true,));
+        let x = (
+            true,
+            1_u8,
+            -2_i16,
+            Bits::<3>::from(5),
+            (),
+            [false, true],
+            7_u32,
+            SignedBits::<5>::from(-3),
+            8_u64,
+            false,
+            -1_i128,
+            (1_u8, 2_u8),
+        );
+        round_trip(x);
+        assert_eq!(
+            x.kind().bits(),
+            1 + 8 + 16 + 3 + 2 + 32 + 5 + 64 + 1 + 128 + 16
+        );
+    }
+
+    #[test]
+    fn test_option_and_result() {
+        round_trip(None::<Bits<4>>);
+        round_trip(Some(Bits::<4>::from(0xA)));
+        round_trip(Ok::<u8, (bool, i8)>(0x5A));
+        round_trip(Err::<u8, (bool, i8)>((true, -3)));
+        let (range, kind) = bit_range(
+            Option::<u8>::static_kind(),
+            &[Path::EnumPayload("Some".into()), Path::Index(0)],
+        )
+        .unwrap();
+        assert_eq!(range, 0..8);
+        assert_eq!(kind, Kind::make_bits(8));
+        let x = Some(0xA5_u8).bin();
+        assert!(x[8]);
+        assert_eq!(&x[0..8], &0xA5_u8.bin()[..]);
+        assert_eq!(None::<u8>.bin(), vec![false; 9]);
+        assert!(Err::<u16, u8>(3).bin()[16]);
+    }
+
+    #[test]
+    fn test_split_zero_width_signed_discriminant() {
+        // A single variant needs no discriminant bits, even if its
+        // discriminant is negative
+        let kind = Kind::make_enum(
+            vec![Kind::make_variant("Only", Kind::make_bits(4), -1)],
+            0,
+            DiscriminantAlignment::Lsb,
+        );
+        let bits = vec![true, false, true, false];
+        let (discriminant, payload) = split_discriminant(&kind, &bits).unwrap();
+        assert_eq!(discriminant, 0);
+        assert_eq!(payload, &bits[..]);
+    }
+
+    #[test]
+    fn test_with_path_keeps_logic_values() {
+        let path = [Path::Field("0".into())];
+        let known = (
+            Bits::<4>::from(3),
+            "4'b1100".parse::<LogicVec<4>>().unwrap(),
+        );
+        assert_eq!(
+            known.with_path(&path, &Bits::<4>::from(5).bin()).unwrap(),
+            (Bits::<4>::from(5), known.1)
+        );
+        // The X and Z bits cannot be rebuilt from the binary form, so
+        // the write is refused rather than losing them
+        let unknown = (
+            Bits::<4>::from(3),
+            "4'b1xz0".parse::<LogicVec<4>>().unwrap(),
+        );
+        let error = unknown
+            .with_path(&path, &Bits::<4>::from(5).bin())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot write to .0 of a value that cannot be rebuilt from its bits"
+        );
+    }
 }
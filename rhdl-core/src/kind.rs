@@ -1,6 +1,24 @@
-use std::{iter::repeat, ops::Range};
+use std::{
+    iter::{repeat, repeat_n},
+    ops::Range,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+mod diff;
+mod layout;
+mod leaves;
+mod schema;
+mod validate;
+
+pub use diff::{KindChange, KindDiff, KindDifference};
+pub use layout::{Layout, LayoutEntry};
+pub use leaves::LeafPath;
+pub use schema::{KindSchema, SchemaError, SCHEMA_VERSION};
+pub use validate::{KindDiagnostic, KindProblem};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Kind {
     Array(Array),
     Tuple(Tuple),
@@ -11,53 +29,73 @@ pub enum Kind {
     Empty,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Array {
     pub base: Box<Kind>,
     pub size: usize,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Tuple {
     pub elements: Vec<Kind>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Struct {
     pub fields: Vec<Field>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Union {
     pub fields: Vec<Field>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DiscriminantAlignment {
     Msb,
     Lsb,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Enum {
     pub variants: Vec<Variant>,
     pub discriminant_width: usize,
     pub discriminant_alignment: DiscriminantAlignment,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Field {
     pub name: String,
     pub kind: Kind,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Variant {
     pub name: String,
     pub discriminant: i64,
     pub kind: Kind,
 }
 
+/// A piece of a struct with an explicit layout, as passed to
+/// [Kind::make_placed_struct].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldPlacement {
+    /// A field that starts right after the previous piece.
+    Packed(Field),
+    /// Reserved bits, which are always zero.
+    Pad(usize),
+    /// A field that occupies exactly the given range of bits.
+    At(Field, Range<usize>),
+}
+
+// The names `_pad0`, `_pad1`, etc. are given to the reserved bits of
+// a placed struct, so no field may use them.
+fn is_pad_name(name: &str) -> bool {
+    name.strip_prefix("_pad")
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
 impl Variant {
     pub fn with_discriminant(self, discriminant: i64) -> Variant {
         Variant {
@@ -93,6 +131,115 @@ impl Kind {
     pub fn make_struct(fields: Vec<Field>) -> Self {
         Self::Struct(Struct { fields })
     }
+    /// Make a struct from fields and reserved bits, listed from the
+    /// LSB up.  The reserved bits become fields named `_pad0`, `_pad1`,
+    /// etc., so that [Kind::layout] and [bit_range](crate::path::bit_range)
+    /// account for them.  Panics if a field placed at a range is not as
+    /// wide as the range, would overlap the pieces before it, or has a
+    /// name reserved for padding.
+    pub fn make_placed_struct(pieces: Vec<FieldPlacement>) -> Self {
+        let mut fields = vec![];
+        let mut offset = 0;
+        let mut pads = 0;
+        let mut pad = |fields: &mut Vec<Field>, width: usize| {
+            if width > 0 {
+                fields.push(Self::make_field(&format!("_pad{pads}"), Kind::Bits(width)));
+                pads += 1;
+            }
+        };
+        for piece in pieces {
+            if let FieldPlacement::Packed(field) | FieldPlacement::At(field, _) = &piece {
+                if is_pad_name(&field.name) {
+                    panic!("Field name {} is reserved for padding", field.name);
+                }
+            }
+            match piece {
+                FieldPlacement::Packed(field) => {
+                    offset += field.kind.bits();
+                    fields.push(field);
+                }
+                FieldPlacement::Pad(width) => {
+                    pad(&mut fields, width);
+                    offset += width;
+                }
+                FieldPlacement::At(field, range) => {
+                    if range.start < offset {
+                        panic!(
+                            "Field {} is placed at bit {}, but bit {} is already in use",
+                            field.name,
+                            range.start,
+                            offset - 1
+                        );
+                    }
+                    if range.len() != field.kind.bits() {
+                        panic!(
+                            "Field {} is {} bits wide, but is placed in {} bits",
+                            field.name,
+                            field.kind.bits(),
+                            range.len()
+                        );
+                    }
+                    pad(&mut fields, range.start - offset);
+                    offset = range.end;
+                    fields.push(field);
+                }
+            }
+        }
+        Self::make_struct(fields)
+    }
+    /// Make a struct from fields and reserved bits, listed from the
+    /// MSB down (as for `#[rhdl(msb_first)]`).  The pieces before the
+    /// first field placed at a range sit directly above it, and the
+    /// pieces after a placed field fill the bits directly below it.
+    /// Panics if those pieces run past bit 0, or if a field placed at
+    /// a range overlaps the pieces above it.
+    pub fn make_placed_struct_msb_first(mut pieces: Vec<FieldPlacement>) -> Self {
+        let Some(first_at) = pieces
+            .iter()
+            .position(|piece| matches!(piece, FieldPlacement::At(..)))
+        else {
+            pieces.reverse();
+            return Self::make_placed_struct(pieces);
+        };
+        let above = pieces.drain(..first_at).rev().collect::<Vec<_>>();
+        // Everything from the first placed field down is given an
+        // explicit range, working down from the top
+        let mut placed = vec![];
+        let mut cursor = usize::MAX;
+        for piece in pieces {
+            match piece {
+                FieldPlacement::At(field, range) => {
+                    if range.end > cursor {
+                        panic!(
+                            "Field {} is placed at bit {}, but bit {} is already in use",
+                            field.name,
+                            range.end - 1,
+                            cursor
+                        );
+                    }
+                    cursor = range.start;
+                    placed.push(FieldPlacement::At(field, range));
+                }
+                FieldPlacement::Packed(field) => {
+                    let width = field.kind.bits();
+                    if width > cursor {
+                        panic!("Field {} does not fit below bit {}", field.name, cursor);
+                    }
+                    cursor -= width;
+                    placed.push(FieldPlacement::At(field, cursor..cursor + width));
+                }
+                FieldPlacement::Pad(width) => {
+                    if width > cursor {
+                        panic!("{} bits of padding do not fit below bit {}", width, cursor);
+                    }
+                    cursor -= width;
+                }
+            }
+        }
+        placed.reverse();
+        placed.extend(above);
+        Self::make_placed_struct(placed)
+    }
     pub fn make_union(fields: Vec<Field>) -> Self {
         Self::Union(Union { fields })
     }
@@ -129,12 +276,42 @@ impl Kind {
             Kind::Empty => 0,
         }
     }
+    /// Check the kind for problems that would make its binary
+    /// representation ambiguous, such as discriminants that do
+    /// not fit in the discriminant width, or duplicate variants
+    /// and fields.  An empty list means the kind is valid.
+    pub fn validate(&self) -> Vec<KindDiagnostic> {
+        let mut diagnostics = vec![];
+        validate::validate("", self, &mut diagnostics);
+        diagnostics
+    }
+    /// Compare the kind with a newer version of it, and report the
+    /// fields that were added, removed or moved, the widths and
+    /// discriminants that changed, and whether values encoded with
+    /// this kind still decode identically with the new one (see
+    /// [KindDiff::is_compatible]).
+    pub fn diff(&self, new: &Kind) -> KindDiff {
+        diff::diff(self, new)
+    }
+    /// List every leaf of the kind along with the absolute range
+    /// of bits it occupies.  The [Display](std::fmt::Display) form
+    /// of the result is a table suitable for documenting a packed
+    /// format.
+    pub fn layout(&self) -> Layout {
+        layout::layout(self)
+    }
+    /// List the path to every leaf of the kind, with the range of
+    /// bits and the kind found at the end of it.  The discriminant
+    /// and the payload of every variant of an enum are included.
+    pub fn leaf_paths(&self) -> Vec<LeafPath> {
+        leaves::leaf_paths(self)
+    }
     pub fn pad(&self, bits: Vec<bool>) -> Vec<bool> {
         if bits.len() > self.bits() {
             panic!("Too many bits for kind!");
         }
         let pad_len = self.bits() - bits.len();
-        let bits = bits.into_iter().chain(repeat(false).take(pad_len));
+        let bits = bits.into_iter().chain(repeat_n(false, pad_len));
         match self {
             Kind::Enum(kind) => match kind.discriminant_alignment {
                 DiscriminantAlignment::Lsb => bits.collect(),
@@ -334,7 +511,7 @@ fn is_layout_valid(layout: &[KindLayout]) -> bool {
 fn get_chars_per_bit(layout: &[KindLayout]) -> usize {
     layout
         .iter()
-        .map(|x| (x.name.len() + x.cols.len() - 1) / x.cols.len())
+        .map(|x| x.name.len().div_ceil(x.cols.len()))
         .max()
         .unwrap_or(0)
 }
@@ -351,7 +528,10 @@ pub fn text_grid(kind: &Kind, name: &str) -> String {
         let mut col_cursor = 0;
         for entry in row_layout {
             if entry.cols.start > col_cursor {
-                result.extend(repeat('.').take((entry.cols.start - col_cursor) * chars_per_bit));
+                result.extend(repeat_n(
+                    '.',
+                    (entry.cols.start - col_cursor) * chars_per_bit,
+                ));
             }
             result.extend(
                 entry
@@ -721,6 +901,18 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_complex_kind_is_valid() {
+        let kind = make_complex_kind();
+        assert_eq!(kind.validate(), vec![]);
+        let layout = kind.layout();
+        println!("{}", layout);
+        assert!(layout
+            .entries
+            .iter()
+            .all(|entry| entry.range.end <= layout.bits));
+    }
+
     #[test]
     fn test_layout_of_complex_kind() {
         let kind = make_complex_kind();
@@ -859,4 +1051,84 @@ mod test {
             svg::save("test_vertical.svg", &svg).unwrap();
         }
     }
+
+    #[test]
+    fn test_make_placed_struct() {
+        let kind = Kind::make_placed_struct(vec![
+            FieldPlacement::Packed(Kind::make_field("a", Kind::make_bits(2))),
+            FieldPlacement::Pad(0),
+            FieldPlacement::At(Kind::make_field("b", Kind::make_bits(4)), 4..8),
+            FieldPlacement::Pad(3),
+        ]);
+        assert_eq!(
+            kind,
+            Kind::make_struct(vec![
+                Kind::make_field("a", Kind::make_bits(2)),
+                Kind::make_field("_pad0", Kind::make_bits(2)),
+                Kind::make_field("b", Kind::make_bits(4)),
+                Kind::make_field("_pad1", Kind::make_bits(3)),
+            ])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Field b is placed at bit 1, but bit 1 is already in use")]
+    fn test_make_placed_struct_overlap() {
+        Kind::make_placed_struct(vec![
+            FieldPlacement::Packed(Kind::make_field("a", Kind::make_bits(2))),
+            FieldPlacement::At(Kind::make_field("b", Kind::make_bits(4)), 1..5),
+        ]);
+    }
+
+    #[test]
+    fn test_make_placed_struct_msb_first() {
+        // A packed field after a placed one sits directly below it
+        let kind = Kind::make_placed_struct_msb_first(vec![
+            FieldPlacement::Packed(Kind::make_field("a", Kind::make_bits(2))),
+            FieldPlacement::At(Kind::make_field("b", Kind::make_bits(4)), 8..12),
+            FieldPlacement::Packed(Kind::make_field("c", Kind::make_bits(2))),
+            FieldPlacement::Pad(1),
+            FieldPlacement::Packed(Kind::make_field("d", Kind::make_bits(1))),
+        ]);
+        assert_eq!(
+            kind,
+            Kind::make_struct(vec![
+                Kind::make_field("_pad0", Kind::make_bits(4)),
+                Kind::make_field("d", Kind::make_bits(1)),
+                Kind::make_field("_pad1", Kind::make_bits(1)),
+                Kind::make_field("c", Kind::make_bits(2)),
+                Kind::make_field("b", Kind::make_bits(4)),
+                Kind::make_field("a", Kind::make_bits(2)),
+            ])
+        );
+        // Without placed fields, the pieces are simply reversed
+        assert_eq!(
+            Kind::make_placed_struct_msb_first(vec![
+                FieldPlacement::Packed(Kind::make_field("a", Kind::make_bits(2))),
+                FieldPlacement::Pad(1),
+            ]),
+            Kind::make_struct(vec![
+                Kind::make_field("_pad0", Kind::make_bits(1)),
+                Kind::make_field("a", Kind::make_bits(2)),
+            ])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Field b does not fit below bit 2")]
+    fn test_make_placed_struct_msb_first_underflow() {
+        Kind::make_placed_struct_msb_first(vec![
+            FieldPlacement::At(Kind::make_field("a", Kind::make_bits(2)), 2..4),
+            FieldPlacement::Packed(Kind::make_field("b", Kind::make_bits(4))),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Field name _pad0 is reserved for padding")]
+    fn test_make_placed_struct_reserved_name() {
+        Kind::make_placed_struct(vec![
+            FieldPlacement::Packed(Kind::make_field("_pad0", Kind::make_bits(2))),
+            FieldPlacement::Pad(2),
+        ]);
+    }
 }
@@ -0,0 +1,512 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use super::{DiscriminantAlignment, Enum, Field, Kind};
+
+/// A single difference found by [Kind::diff], along with the path
+/// (in the same notation as [Kind::validate]) of the part of the
+/// kind where it was found.  Fields and variants are matched by
+/// name, and tuple elements by position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KindDifference {
+    pub path: String,
+    pub change: KindChange,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KindChange {
+    /// A field, tuple element or variant only exists in the new kind.
+    Added { kind: Kind },
+    /// A field, tuple element or variant only exists in the old kind.
+    Removed { kind: Kind },
+    /// A field or tuple element starts at a different offset within
+    /// its parent.  The ranges are absolute.  The contents of a moved
+    /// field are not reported as moved themselves.
+    Moved {
+        from: Range<usize>,
+        to: Range<usize>,
+    },
+    /// The number of bits in a leaf, discriminant, union or enum
+    /// has changed.
+    Resized { from: usize, to: usize },
+    /// The number of elements in an array has changed.
+    LengthChanged { from: usize, to: usize },
+    /// The variant has been assigned a different discriminant.
+    DiscriminantChanged { variant: String, from: i64, to: i64 },
+    /// The discriminant of the enum has moved to the other end.
+    AlignmentChanged {
+        from: DiscriminantAlignment,
+        to: DiscriminantAlignment,
+    },
+    /// The kind has been replaced by one of a different shape
+    /// (e.g., a struct by an enum).
+    Replaced { from: Kind, to: Kind },
+}
+
+/// The result of [Kind::diff].
+#[derive(Clone, Debug, PartialEq)]
+pub struct KindDiff {
+    pub old_bits: usize,
+    pub new_bits: usize,
+    pub differences: Vec<KindDifference>,
+}
+
+impl KindDiff {
+    /// True if every bit pattern of the old kind decodes to the same
+    /// value with the new kind.  This only holds if the width is
+    /// unchanged and the only differences are additions (such as a
+    /// new variant in an unused discriminant, or a new field of a
+    /// union that is no wider than the others).
+    pub fn is_compatible(&self) -> bool {
+        self.old_bits == self.new_bits
+            && self
+                .differences
+                .iter()
+                .all(|x| matches!(x.change, KindChange::Added { .. }))
+    }
+    /// True if the kinds are identical.
+    pub fn is_empty(&self) -> bool {
+        self.old_bits == self.new_bits && self.differences.is_empty()
+    }
+}
+
+fn shape(kind: &Kind) -> &'static str {
+    match kind {
+        Kind::Empty => "empty",
+        Kind::Bits(_) => "bits",
+        Kind::Array(_) => "array",
+        Kind::Tuple(_) => "tuple",
+        Kind::Struct(_) => "struct",
+        Kind::Union(_) => "union",
+        Kind::Enum(_) => "enum",
+    }
+}
+
+// Ranges are shown MSB first, as they are in a Layout.
+fn format_range(range: &Range<usize>) -> String {
+    if range.len() == 1 {
+        format!("[{}]", range.start)
+    } else {
+        format!("[{}:{}]", range.end - 1, range.start)
+    }
+}
+
+impl Display for KindChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KindChange::Added { kind } => write!(f, "added ({} bits)", kind.bits()),
+            KindChange::Removed { kind } => write!(f, "removed ({} bits)", kind.bits()),
+            KindChange::Moved { from, to } => write!(
+                f,
+                "moved from {} to {}",
+                format_range(from),
+                format_range(to)
+            ),
+            KindChange::Resized { from, to } => {
+                write!(f, "resized from {} to {} bits", from, to)
+            }
+            KindChange::LengthChanged { from, to } => {
+                write!(f, "length changed from {} to {}", from, to)
+            }
+            KindChange::DiscriminantChanged { variant, from, to } => write!(
+                f,
+                "discriminant of variant {} changed from {} to {}",
+                variant, from, to
+            ),
+            KindChange::AlignmentChanged { from, to } => write!(
+                f,
+                "discriminant alignment changed from {:?} to {:?}",
+                from, to
+            ),
+            KindChange::Replaced { from, to } => {
+                write!(f, "replaced {} with {}", shape(from), shape(to))
+            }
+        }
+    }
+}
+
+impl Display for KindDifference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.change)
+        } else {
+            write!(f, "{}: {}", self.path, self.change)
+        }
+    }
+}
+
+impl Display for KindDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for difference in &self.differences {
+            writeln!(f, "{}", difference)?;
+        }
+        if self.old_bits != self.new_bits {
+            writeln!(
+                f,
+                "width changed from {} to {} bits",
+                self.old_bits, self.new_bits
+            )?;
+        }
+        if self.is_compatible() {
+            writeln!(f, "compatible")
+        } else {
+            writeln!(f, "incompatible")
+        }
+    }
+}
+
+// A member of a kind: its path, its offset within the parent and its kind.
+type Member<'a> = (String, usize, &'a Kind);
+
+// The members of a struct or union, along with their offsets.
+// The fields of a union all start at 0.
+fn fields<'a>(path: &str, fields: &'a [Field], packed: bool) -> Vec<Member<'a>> {
+    let mut offset = 0;
+    fields
+        .iter()
+        .map(|field| {
+            let member = (format!("{path}.{}", field.name), offset, &field.kind);
+            if packed {
+                offset += field.kind.bits();
+            }
+            member
+        })
+        .collect()
+}
+
+fn elements<'a>(path: &str, elements: &'a [Kind]) -> Vec<Member<'a>> {
+    let mut offset = 0;
+    elements
+        .iter()
+        .enumerate()
+        .map(|(ndx, element)| {
+            let member = (format!("{path}.{ndx}"), offset, element);
+            offset += element.bits();
+            member
+        })
+        .collect()
+}
+
+// The payloads of an enum, relative to the start of the payload.
+fn variants<'a>(path: &str, e: &'a Enum) -> Vec<Member<'a>> {
+    e.variants
+        .iter()
+        .map(|v| (format!("{path}#{}", v.name), 0, &v.kind))
+        .collect()
+}
+
+struct Differ {
+    differences: Vec<KindDifference>,
+}
+
+impl Differ {
+    fn push(&mut self, path: &str, change: KindChange) {
+        self.differences.push(KindDifference {
+            path: path.to_string(),
+            change,
+        });
+    }
+    // Compare a list of named members, each with its offset relative
+    // to the parent.  Members that are at different relative offsets
+    // are reported as moved.
+    fn members(&mut self, old: Vec<Member>, new: Vec<Member>, offsets: (usize, usize)) {
+        for (path, old_offset, old_kind) in &old {
+            match new.iter().find(|x| &x.0 == path) {
+                Some((_, new_offset, new_kind)) => {
+                    if old_offset != new_offset && old_kind.bits() > 0 {
+                        let from = offsets.0 + old_offset;
+                        let to = offsets.1 + new_offset;
+                        self.push(
+                            path,
+                            KindChange::Moved {
+                                from: from..from + old_kind.bits(),
+                                to: to..to + new_kind.bits(),
+                            },
+                        );
+                    }
+                    self.diff(
+                        path,
+                        old_kind,
+                        new_kind,
+                        (offsets.0 + old_offset, offsets.1 + new_offset),
+                    );
+                }
+                None => self.push(
+                    path,
+                    KindChange::Removed {
+                        kind: (*old_kind).clone(),
+                    },
+                ),
+            }
+        }
+        for (path, _, new_kind) in &new {
+            if !old.iter().any(|x| &x.0 == path) {
+                self.push(
+                    path,
+                    KindChange::Added {
+                        kind: (*new_kind).clone(),
+                    },
+                );
+            }
+        }
+    }
+    fn resized(&mut self, path: &str, old: usize, new: usize) {
+        if old != new {
+            self.push(path, KindChange::Resized { from: old, to: new });
+        }
+    }
+    fn enumeration(
+        &mut self,
+        path: &str,
+        (old_kind, old): (&Kind, &Enum),
+        (new_kind, new): (&Kind, &Enum),
+        offsets: (usize, usize),
+    ) {
+        self.resized(path, old_kind.bits(), new_kind.bits());
+        if old.discriminant_alignment != new.discriminant_alignment {
+            self.push(
+                path,
+                KindChange::AlignmentChanged {
+                    from: old.discriminant_alignment,
+                    to: new.discriminant_alignment,
+                },
+            );
+        }
+        self.resized(
+            &format!("{path}#discriminant"),
+            old.discriminant_width,
+            new.discriminant_width,
+        );
+        for variant in &old.variants {
+            if let Some(other) = new.variants.iter().find(|x| x.name == variant.name) {
+                if variant.discriminant != other.discriminant {
+                    self.push(
+                        path,
+                        KindChange::DiscriminantChanged {
+                            variant: variant.name.clone(),
+                            from: variant.discriminant,
+                            to: other.discriminant,
+                        },
+                    );
+                }
+            }
+        }
+        // The payloads all start at the same place, which only moves
+        // if the alignment or discriminant width changes (and those
+        // are reported above).
+        let payload_offset = |e: &Enum| match e.discriminant_alignment {
+            DiscriminantAlignment::Lsb => e.discriminant_width,
+            DiscriminantAlignment::Msb => 0,
+        };
+        self.members(
+            variants(path, old),
+            variants(path, new),
+            (
+                offsets.0 + payload_offset(old),
+                offsets.1 + payload_offset(new),
+            ),
+        );
+    }
+    fn diff(&mut self, path: &str, old: &Kind, new: &Kind, offsets: (usize, usize)) {
+        match (old, new) {
+            (Kind::Empty, Kind::Empty) => {}
+            (Kind::Bits(old), Kind::Bits(new)) => self.resized(path, *old, *new),
+            (Kind::Array(old), Kind::Array(new)) => {
+                if old.size != new.size {
+                    self.push(
+                        path,
+                        KindChange::LengthChanged {
+                            from: old.size,
+                            to: new.size,
+                        },
+                    );
+                }
+                self.diff(&format!("{path}[]"), &old.base, &new.base, offsets);
+            }
+            (Kind::Tuple(old), Kind::Tuple(new)) => self.members(
+                elements(path, &old.elements),
+                elements(path, &new.elements),
+                offsets,
+            ),
+            (Kind::Struct(old), Kind::Struct(new)) => self.members(
+                fields(path, &old.fields, true),
+                fields(path, &new.fields, true),
+                offsets,
+            ),
+            (Kind::Union(old_union), Kind::Union(new_union)) => {
+                self.resized(path, old.bits(), new.bits());
+                self.members(
+                    fields(path, &old_union.fields, false),
+                    fields(path, &new_union.fields, false),
+                    offsets,
+                )
+            }
+            (Kind::Enum(old_enum), Kind::Enum(new_enum)) => {
+                self.enumeration(path, (old, old_enum), (new, new_enum), offsets)
+            }
+            _ => self.push(
+                path,
+                KindChange::Replaced {
+                    from: old.clone(),
+                    to: new.clone(),
+                },
+            ),
+        }
+    }
+}
+
+pub(super) fn diff(old: &Kind, new: &Kind) -> KindDiff {
+    let mut differ = Differ {
+        differences: vec![],
+    };
+    differ.diff("", old, new, (0, 0));
+    KindDiff {
+        old_bits: old.bits(),
+        new_bits: new.bits(),
+        differences: differ.differences,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn make_packet(alignment: DiscriminantAlignment, color: i64) -> Kind {
+        Kind::make_enum(
+            vec![
+                Kind::make_variant("Idle", Kind::Empty, 0),
+                Kind::make_variant(
+                    "Color",
+                    Kind::make_struct(vec![
+                        Kind::make_field("r", Kind::make_bits(4)),
+                        Kind::make_field("g", Kind::make_bits(4)),
+                    ]),
+                    color,
+                ),
+            ],
+            2,
+            alignment,
+        )
+    }
+
+    #[test]
+    fn test_identical_kinds() {
+        let kind = make_packet(DiscriminantAlignment::Msb, 1);
+        let diff = kind.diff(&kind);
+        assert!(diff.is_empty());
+        assert!(diff.is_compatible());
+        assert_eq!(diff.to_string(), "compatible\n");
+    }
+
+    #[test]
+    fn test_struct_changes() {
+        let old = Kind::make_struct(vec![
+            Kind::make_field("a", Kind::make_bits(4)),
+            Kind::make_field("b", Kind::make_bits(2)),
+            Kind::make_field("c", Kind::make_array(Kind::make_bits(3), 2)),
+            Kind::make_field("d", Kind::make_tuple(vec![Kind::make_bits(1)])),
+        ]);
+        let new = Kind::make_struct(vec![
+            Kind::make_field("a", Kind::make_bits(6)),
+            Kind::make_field("c", Kind::make_array(Kind::make_bits(3), 4)),
+            Kind::make_field("d", Kind::make_bits(1)),
+            Kind::make_field("e", Kind::make_bits(1)),
+        ]);
+        let diff = old.diff(&new);
+        assert!(!diff.is_compatible());
+        assert_eq!(
+            diff.to_string(),
+            "\
+.a: resized from 4 to 6 bits
+.b: removed (2 bits)
+.c: length changed from 2 to 4
+.d: moved from [12] to [18]
+.d: replaced tuple with bits
+.e: added (1 bits)
+width changed from 13 to 20 bits
+incompatible
+"
+        );
+    }
+
+    #[test]
+    fn test_enum_changes() {
+        let old = make_packet(DiscriminantAlignment::Msb, 1);
+        let diff = old.diff(&make_packet(DiscriminantAlignment::Lsb, 2));
+        assert_eq!(
+            diff.differences
+                .iter()
+                .map(|x| x.change.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                KindChange::AlignmentChanged {
+                    from: DiscriminantAlignment::Msb,
+                    to: DiscriminantAlignment::Lsb
+                },
+                KindChange::DiscriminantChanged {
+                    variant: "Color".into(),
+                    from: 1,
+                    to: 2
+                },
+            ]
+        );
+        assert!(!diff.is_compatible());
+        // A new variant in an unused discriminant does not change how
+        // the existing values decode, as long as it fits.
+        let Kind::Enum(mut e) = old.clone() else {
+            unreachable!()
+        };
+        e.variants
+            .push(Kind::make_variant("Level", Kind::make_bits(6), 2));
+        let diff = old.diff(&Kind::Enum(e.clone()));
+        assert_eq!(diff.to_string(), "#Level: added (6 bits)\ncompatible\n");
+        e.variants
+            .push(Kind::make_variant("Wide", Kind::make_bits(9), 3));
+        let diff = old.diff(&Kind::Enum(e));
+        assert_eq!(
+            diff.to_string(),
+            "\
+resized from 10 to 11 bits
+#Level: added (6 bits)
+#Wide: added (9 bits)
+width changed from 10 to 11 bits
+incompatible
+"
+        );
+    }
+
+    #[test]
+    fn test_nested_payload_changes() {
+        let make = |g| {
+            Kind::make_struct(vec![
+                Kind::make_field("x", Kind::make_bits(1)),
+                Kind::make_field(
+                    "p",
+                    Kind::make_enum(
+                        vec![Kind::make_variant(
+                            "Color",
+                            Kind::make_struct(vec![
+                                Kind::make_field("r", Kind::make_bits(4)),
+                                Kind::make_field("g", Kind::make_bits(g)),
+                            ]),
+                            0,
+                        )],
+                        1,
+                        DiscriminantAlignment::Lsb,
+                    ),
+                ),
+            ])
+        };
+        let diff = make(4).diff(&make(5));
+        assert_eq!(
+            diff.differences
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                ".p: resized from 9 to 10 bits",
+                ".p#Color.g: resized from 4 to 5 bits"
+            ]
+        );
+    }
+}
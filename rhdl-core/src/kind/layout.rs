@@ -0,0 +1,183 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use super::Kind;
+use crate::path::path_string;
+
+/// A single leaf of a [Layout].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutEntry {
+    /// The path to the leaf.  Fields and tuple elements are written
+    /// as `.name` and `.0`, array elements as `[3]`, the payload of
+    /// an enum variant as `#Variant` and the discriminant of an
+    /// enum as `#discriminant`.
+    pub path: String,
+    /// The bits occupied by the leaf, counting from the LSB of
+    /// the whole value.
+    pub range: Range<usize>,
+    pub kind: Kind,
+}
+
+/// A report of where every leaf of a [Kind] lives in its binary
+/// representation, produced by [Kind::layout].  The payloads of
+/// the variants of an enum (and the fields of a union) overlap,
+/// and all of them are listed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    pub bits: usize,
+    pub entries: Vec<LayoutEntry>,
+}
+
+// The entries are the leaf paths in text form, so that the layout,
+// the leaf paths and bit_range always agree.
+pub(super) fn layout(kind: &Kind) -> Layout {
+    Layout {
+        bits: kind.bits(),
+        entries: kind
+            .leaf_paths()
+            .into_iter()
+            .map(|leaf| LayoutEntry {
+                path: path_string(&leaf.path),
+                range: leaf.range,
+                kind: leaf.kind,
+            })
+            .collect(),
+    }
+}
+
+// Ranges are shown MSB first, as they would be in Verilog.
+fn format_range(range: &Range<usize>) -> String {
+    if range.len() == 1 {
+        format!("[{}]", range.start)
+    } else {
+        format!("[{}:{}]", range.end - 1, range.start)
+    }
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let path = if entry.path.is_empty() {
+                    "(root)"
+                } else {
+                    &entry.path
+                };
+                (format_range(&entry.range), path, entry.kind.bits())
+            })
+            .collect::<Vec<_>>();
+        let range_width = rows.iter().map(|x| x.0.len()).max().unwrap_or(0).max(4);
+        let path_width = rows.iter().map(|x| x.1.len()).max().unwrap_or(0).max(4);
+        writeln!(f, "{} bits", self.bits)?;
+        writeln!(f, "{:range_width$}  {:path_width$}  width", "bits", "path")?;
+        for (range, path, width) in rows {
+            writeln!(f, "{:range_width$}  {:path_width$}  {}", range, path, width)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::DiscriminantAlignment;
+
+    #[test]
+    fn test_layout_of_struct() {
+        let kind = Kind::make_struct(vec![
+            Kind::make_field("a", Kind::make_bits(4)),
+            Kind::make_field(
+                "b",
+                Kind::make_tuple(vec![Kind::make_bits(1), Kind::Empty, Kind::make_bits(3)]),
+            ),
+            Kind::make_field("c", Kind::make_array(Kind::make_bits(2), 2)),
+        ]);
+        let layout = kind.layout();
+        assert_eq!(layout.bits, 12);
+        let entries = layout
+            .entries
+            .iter()
+            .map(|x| (x.path.as_str(), x.range.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                (".a", 0..4),
+                (".b.0", 4..5),
+                (".b.2", 5..8),
+                (".c[0]", 8..10),
+                (".c[1]", 10..12),
+            ]
+        );
+        assert_eq!(
+            layout.to_string(),
+            "\
+12 bits
+bits     path   width
+[3:0]    .a     4
+[4]      .b.0   1
+[7:5]    .b.2   3
+[9:8]    .c[0]  2
+[11:10]  .c[1]  2
+"
+        );
+    }
+
+    #[test]
+    fn test_layout_of_enums() {
+        let payload = Kind::make_struct(vec![
+            Kind::make_field("x", Kind::make_bits(3)),
+            Kind::make_field("y", Kind::make_bits(2)),
+        ]);
+        let make = |alignment| {
+            Kind::make_enum(
+                vec![
+                    Kind::make_variant("A", Kind::Empty, 0),
+                    Kind::make_variant("B", payload.clone(), 1),
+                    Kind::make_variant("C", Kind::make_bits(4), 2),
+                ],
+                2,
+                alignment,
+            )
+        };
+        let entries = |kind: Kind| {
+            kind.layout()
+                .entries
+                .into_iter()
+                .map(|x| (x.path, x.range))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            entries(make(DiscriminantAlignment::Msb)),
+            vec![
+                ("#discriminant".to_string(), 5..7),
+                ("#B.x".to_string(), 0..3),
+                ("#B.y".to_string(), 3..5),
+                ("#C".to_string(), 0..4),
+            ]
+        );
+        assert_eq!(
+            entries(make(DiscriminantAlignment::Lsb)),
+            vec![
+                ("#discriminant".to_string(), 0..2),
+                ("#B.x".to_string(), 2..5),
+                ("#B.y".to_string(), 5..7),
+                ("#C".to_string(), 2..6),
+            ]
+        );
+        // The ranges agree with bit_range
+        let kind = make(DiscriminantAlignment::Msb);
+        let (range, _) = crate::path::bit_range(
+            kind,
+            &[
+                crate::path::Path::EnumPayload("B".into()),
+                crate::path::Path::Field("y".into()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(range, 3..5);
+        assert_eq!(Kind::make_bits(3).layout().entries[0].path, "");
+    }
+}
@@ -0,0 +1,165 @@
+use std::ops::Range;
+
+use super::Kind;
+use crate::path::{bit_range, Path};
+
+/// A single terminal of a [Kind], produced by [Kind::leaf_paths].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeafPath {
+    pub path: Vec<Path>,
+    /// The bits occupied by the leaf, counting from the LSB of
+    /// the whole value.
+    pub range: Range<usize>,
+    pub kind: Kind,
+}
+
+// Collect the paths only.  The ranges are left to bit_range, so
+// that the two can never disagree.
+fn collect(prefix: &mut Vec<Path>, kind: &Kind, paths: &mut Vec<Vec<Path>>) {
+    let mut visit = |step: Path, kind: &Kind, paths: &mut Vec<Vec<Path>>| {
+        prefix.push(step);
+        collect(prefix, kind, paths);
+        prefix.pop();
+    };
+    match kind {
+        Kind::Empty => {}
+        Kind::Bits(_) => paths.push(prefix.clone()),
+        Kind::Array(array) => {
+            for ndx in 0..array.size {
+                visit(Path::Index(ndx), &array.base, paths);
+            }
+        }
+        // Tuple elements are named `.0`, `.1`, etc., as in Rust
+        Kind::Tuple(tuple) => {
+            for (ndx, element) in tuple.elements.iter().enumerate() {
+                visit(Path::Field(ndx.to_string()), element, paths);
+            }
+        }
+        Kind::Struct(structure) => {
+            for field in &structure.fields {
+                visit(Path::Field(field.name.clone()), &field.kind, paths);
+            }
+        }
+        Kind::Union(union) => {
+            for field in &union.fields {
+                visit(Path::Field(field.name.clone()), &field.kind, paths);
+            }
+        }
+        Kind::Enum(e) => {
+            if e.discriminant_width > 0 {
+                visit(
+                    Path::EnumDiscriminant,
+                    &Kind::Bits(e.discriminant_width),
+                    paths,
+                );
+            }
+            for variant in &e.variants {
+                visit(
+                    Path::EnumPayload(variant.name.clone()),
+                    &variant.kind,
+                    paths,
+                );
+            }
+        }
+    }
+}
+
+pub(super) fn leaf_paths(kind: &Kind) -> Vec<LeafPath> {
+    let mut paths = vec![];
+    collect(&mut vec![], kind, &mut paths);
+    paths
+        .into_iter()
+        .map(|path| {
+            let (range, kind) =
+                bit_range(kind.clone(), &path).expect("leaf paths are valid for their kind");
+            LeafPath { path, range, kind }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::path::path_string;
+    use crate::DiscriminantAlignment;
+
+    fn packet_kind() -> Kind {
+        Kind::make_enum(
+            vec![
+                Kind::make_variant("Idle", Kind::Empty, 0),
+                Kind::make_variant(
+                    "Data",
+                    Kind::make_struct(vec![
+                        Kind::make_field("valid", Kind::make_bits(1)),
+                        Kind::make_field(
+                            "words",
+                            Kind::make_array(
+                                Kind::make_tuple(vec![Kind::make_bits(2), Kind::make_bits(3)]),
+                                2,
+                            ),
+                        ),
+                    ]),
+                    1,
+                ),
+                Kind::make_variant(
+                    "Raw",
+                    Kind::make_union(vec![
+                        Kind::make_field("lo", Kind::make_bits(4)),
+                        Kind::make_field("all", Kind::make_bits(8)),
+                    ]),
+                    2,
+                ),
+            ],
+            2,
+            DiscriminantAlignment::Msb,
+        )
+    }
+
+    #[test]
+    fn test_leaf_paths() {
+        let leaves = packet_kind().leaf_paths();
+        assert_eq!(
+            leaves
+                .iter()
+                .map(|x| (path_string(&x.path), x.range.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("#discriminant".to_string(), 11..13),
+                ("#Data.valid".to_string(), 0..1),
+                ("#Data.words[0].0".to_string(), 1..3),
+                ("#Data.words[0].1".to_string(), 3..6),
+                ("#Data.words[1].0".to_string(), 6..8),
+                ("#Data.words[1].1".to_string(), 8..11),
+                ("#Raw.lo".to_string(), 0..4),
+                ("#Raw.all".to_string(), 0..8),
+            ]
+        );
+        assert!(leaves.iter().all(|x| x.kind.bits() == x.range.len()));
+    }
+
+    #[test]
+    fn test_leaf_paths_match_layout() {
+        let kind = packet_kind();
+        let layout = kind.layout();
+        let leaves = kind.leaf_paths();
+        assert_eq!(leaves.len(), layout.entries.len());
+        for (leaf, entry) in leaves.iter().zip(&layout.entries) {
+            assert_eq!(path_string(&leaf.path), entry.path);
+            assert_eq!(leaf.range, entry.range);
+            assert_eq!(leaf.kind, entry.kind);
+        }
+    }
+
+    #[test]
+    fn test_leaf_paths_of_plain_kinds() {
+        assert!(Kind::Empty.leaf_paths().is_empty());
+        assert_eq!(
+            Kind::make_bits(5).leaf_paths(),
+            vec![LeafPath {
+                path: vec![],
+                range: 0..5,
+                kind: Kind::make_bits(5),
+            }]
+        );
+    }
+}
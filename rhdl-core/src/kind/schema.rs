@@ -0,0 +1,236 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Kind, KindDiagnostic};
+
+/// The version of the schema format written by [KindSchema::to_json].
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A [Kind] tagged with the version of the schema format.  This is a
+/// stable JSON format for kinds, so that layouts can be shipped
+/// alongside captured data and decoded by other tools.
+///
+/// A schema document is an object with a `version` and a `kind`:
+/// ```json
+/// { "version": 1, "kind": { "bits": 8 } }
+/// ```
+/// A kind is one of:
+///
+/// - `"empty"` - occupies no bits.
+/// - `{ "bits": N }` - `N` bits.
+/// - `{ "array": { "base": KIND, "size": N } }` - `N` copies of `base`.
+/// - `{ "tuple": { "elements": [KIND, ...] } }`
+/// - `{ "struct": { "fields": [{ "name": NAME, "kind": KIND }, ...] } }`
+/// - `{ "union": { "fields": [{ "name": NAME, "kind": KIND }, ...] } }`
+/// - `{ "enum": { "variants": [VARIANT, ...], "discriminant_width": N,
+///   "discriminant_alignment": "msb" | "lsb" } }`, where each variant is
+///   `{ "name": NAME, "discriminant": INTEGER, "kind": KIND }`.
+///
+/// To decode a value from its bits (bit 0 being the least significant):
+///
+/// - Arrays, tuples and structs are packed in order, with the first
+///   element or field starting at the lowest bit.
+/// - Every field of a union starts at bit 0.  The union is as wide as
+///   its widest field.
+/// - An enum is `discriminant_width` bits wider than its widest variant.
+///   With `"lsb"` alignment, the discriminant occupies the lowest bits
+///   and the payload starts right above it.  With `"msb"` alignment,
+///   the discriminant occupies the highest bits, and the payload starts
+///   at bit 0.  Unused payload bits are zero.
+/// - The discriminant is a two's complement number if any variant has
+///   a negative discriminant, and unsigned otherwise.
+///
+/// The version is incremented whenever a change to this format would
+/// prevent an existing decoder from reading a document correctly.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KindSchema {
+    pub version: u32,
+    pub kind: Kind,
+}
+
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The document is not valid JSON, or does not match the format.
+    Json(serde_json::Error),
+    /// The document does not have a numeric `version`.
+    MissingVersion,
+    /// The document was written with a version of the format that
+    /// this library does not understand.
+    UnsupportedVersion { found: u64, supported: u32 },
+    /// The kind is well formed, but fails [Kind::validate].
+    Invalid(Vec<KindDiagnostic>),
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaError::Json(err) => write!(f, "malformed schema: {}", err),
+            SchemaError::MissingVersion => write!(f, "schema has no version"),
+            SchemaError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "schema version {} is not supported (expected {})",
+                found, supported
+            ),
+            SchemaError::Invalid(diagnostics) => {
+                write!(f, "invalid kind in schema")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {}", diagnostic)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SchemaError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(err: serde_json::Error) -> Self {
+        SchemaError::Json(err)
+    }
+}
+
+impl KindSchema {
+    /// Wrap a kind in a schema of the current version.
+    pub fn new(kind: Kind) -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            kind,
+        }
+    }
+    /// Write the schema as (pretty printed) JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a kind can always be serialized")
+    }
+    /// Read a schema from JSON.  The version is checked before the
+    /// rest of the document is parsed, and the kind is validated.
+    pub fn from_json(text: &str) -> Result<Self, SchemaError> {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let version = value
+            .get("version")
+            .and_then(|x| x.as_u64())
+            .ok_or(SchemaError::MissingVersion)?;
+        if version != SCHEMA_VERSION as u64 {
+            return Err(SchemaError::UnsupportedVersion {
+                found: version,
+                supported: SCHEMA_VERSION,
+            });
+        }
+        let schema: KindSchema = serde_json::from_value(value)?;
+        let diagnostics = schema.kind.validate();
+        if !diagnostics.is_empty() {
+            return Err(SchemaError::Invalid(diagnostics));
+        }
+        Ok(schema)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::DiscriminantAlignment;
+
+    fn make_kind() -> Kind {
+        Kind::make_enum(
+            vec![
+                Kind::make_variant("Idle", Kind::Empty, -1),
+                Kind::make_variant(
+                    "Data",
+                    Kind::make_struct(vec![
+                        Kind::make_field("bytes", Kind::make_array(Kind::make_bits(8), 2)),
+                        Kind::make_field(
+                            "tag",
+                            Kind::make_tuple(vec![Kind::make_bits(1), Kind::make_bits(3)]),
+                        ),
+                    ]),
+                    1,
+                ),
+                Kind::make_variant(
+                    "Raw",
+                    Kind::make_union(vec![Kind::make_field("word", Kind::make_bits(20))]),
+                    0,
+                ),
+            ],
+            2,
+            DiscriminantAlignment::Lsb,
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let schema = KindSchema::new(make_kind());
+        let json = schema.to_json();
+        assert_eq!(KindSchema::from_json(&json).unwrap(), schema);
+    }
+
+    #[test]
+    fn test_format_is_stable() {
+        let kind = Kind::make_struct(vec![
+            Kind::make_field("a", Kind::make_bits(4)),
+            Kind::make_field("b", Kind::Empty),
+            Kind::make_field(
+                "c",
+                Kind::make_enum(
+                    vec![Kind::make_variant("X", Kind::make_bits(2), 0)],
+                    1,
+                    DiscriminantAlignment::Msb,
+                ),
+            ),
+        ]);
+        let json = serde_json::to_value(KindSchema::new(kind.clone())).unwrap();
+        let expected = serde_json::json!({
+            "version": 1,
+            "kind": {"struct": {"fields": [
+                {"name": "a", "kind": {"bits": 4}},
+                {"name": "b", "kind": "empty"},
+                {"name": "c", "kind": {"enum": {
+                    "variants": [{"name": "X", "discriminant": 0, "kind": {"bits": 2}}],
+                    "discriminant_width": 1,
+                    "discriminant_alignment": "msb"
+                }}}
+            ]}}
+        });
+        assert_eq!(json, expected);
+        assert_eq!(
+            KindSchema::from_json(&expected.to_string()).unwrap().kind,
+            kind
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            KindSchema::from_json(r#"{"version": 2, "kind": {"anything": "goes"}}"#),
+            Err(SchemaError::UnsupportedVersion {
+                found: 2,
+                supported: 1
+            })
+        ));
+        assert!(matches!(
+            KindSchema::from_json(r#"{"kind": "empty"}"#),
+            Err(SchemaError::MissingVersion)
+        ));
+        assert!(matches!(
+            KindSchema::from_json(r#"{"version": 1, "kind": {"bits": -1}}"#),
+            Err(SchemaError::Json(_))
+        ));
+        let err = KindSchema::from_json(
+            r#"{"version": 1, "kind": {"enum": {"variants": [
+                {"name": "A", "discriminant": 4, "kind": "empty"}
+            ], "discriminant_width": 2, "discriminant_alignment": "lsb"}}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid kind in schema\n  discriminant 4 of variant A does not fit in 2 unsigned bits"
+        );
+    }
+}
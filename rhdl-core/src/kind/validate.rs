@@ -0,0 +1,254 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+use super::{Enum, Field, Kind};
+
+/// A problem found by [Kind::validate], along with the path (in the
+/// same notation as [Kind::layout]) of the part of the kind where
+/// it was found.  An empty path refers to the kind itself, and
+/// `[]` refers to every element of an array.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KindDiagnostic {
+    pub path: String,
+    pub problem: KindProblem,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KindProblem {
+    /// The enum has no variants, and so no values.
+    EmptyEnum,
+    /// Two variants of the enum have the same name.
+    DuplicateVariant { name: String },
+    /// Two variants of the enum have the same discriminant.
+    DuplicateDiscriminant {
+        discriminant: i64,
+        first: String,
+        second: String,
+    },
+    /// The discriminant of a variant cannot be represented in the
+    /// discriminant width of the enum.  The discriminants are
+    /// signed if any of them are negative.
+    DiscriminantOutOfRange {
+        variant: String,
+        discriminant: i64,
+        width: usize,
+        signed: bool,
+    },
+    /// Two fields of a struct or union have the same name.
+    DuplicateField { name: String },
+}
+
+impl Display for KindProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KindProblem::EmptyEnum => write!(f, "enum has no variants"),
+            KindProblem::DuplicateVariant { name } => {
+                write!(f, "variant {} is defined more than once", name)
+            }
+            KindProblem::DuplicateDiscriminant {
+                discriminant,
+                first,
+                second,
+            } => write!(
+                f,
+                "variants {} and {} share the discriminant {}",
+                first, second, discriminant
+            ),
+            KindProblem::DiscriminantOutOfRange {
+                variant,
+                discriminant,
+                width,
+                signed,
+            } => write!(
+                f,
+                "discriminant {} of variant {} does not fit in {} {} bits",
+                discriminant,
+                variant,
+                width,
+                if *signed { "signed" } else { "unsigned" }
+            ),
+            KindProblem::DuplicateField { name } => {
+                write!(f, "field {} is defined more than once", name)
+            }
+        }
+    }
+}
+
+impl Display for KindDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.problem)
+        } else {
+            write!(f, "{}: {}", self.path, self.problem)
+        }
+    }
+}
+
+fn discriminant_fits(discriminant: i64, width: usize, signed: bool) -> bool {
+    // Every i64 fits in 64 bits, signed or not
+    if width >= 64 {
+        return true;
+    }
+    let discriminant = discriminant as i128;
+    if signed {
+        width > 0 && (-1_i128 << (width - 1)) <= discriminant && discriminant < 1 << (width - 1)
+    } else {
+        (0..1_i128 << width).contains(&discriminant)
+    }
+}
+
+fn validate_fields(path: &str, fields: &[Field], diagnostics: &mut Vec<KindDiagnostic>) {
+    let mut names = HashSet::new();
+    for field in fields {
+        if !names.insert(&field.name) {
+            diagnostics.push(KindDiagnostic {
+                path: path.to_string(),
+                problem: KindProblem::DuplicateField {
+                    name: field.name.clone(),
+                },
+            });
+        }
+    }
+    for field in fields {
+        validate(&format!("{path}.{}", field.name), &field.kind, diagnostics);
+    }
+}
+
+fn validate_enum(path: &str, e: &Enum, diagnostics: &mut Vec<KindDiagnostic>) {
+    let mut diagnose = |problem| {
+        diagnostics.push(KindDiagnostic {
+            path: path.to_string(),
+            problem,
+        })
+    };
+    if e.variants.is_empty() {
+        diagnose(KindProblem::EmptyEnum);
+    }
+    let signed = e.variants.iter().any(|x| x.discriminant < 0);
+    let mut names = HashSet::new();
+    let mut discriminants = HashMap::new();
+    for variant in &e.variants {
+        if !names.insert(&variant.name) {
+            diagnose(KindProblem::DuplicateVariant {
+                name: variant.name.clone(),
+            });
+        }
+        if let Some(first) = discriminants.insert(variant.discriminant, &variant.name) {
+            diagnose(KindProblem::DuplicateDiscriminant {
+                discriminant: variant.discriminant,
+                first: first.clone(),
+                second: variant.name.clone(),
+            });
+        }
+        if !discriminant_fits(variant.discriminant, e.discriminant_width, signed) {
+            diagnose(KindProblem::DiscriminantOutOfRange {
+                variant: variant.name.clone(),
+                discriminant: variant.discriminant,
+                width: e.discriminant_width,
+                signed,
+            });
+        }
+    }
+    for variant in &e.variants {
+        validate(
+            &format!("{path}#{}", variant.name),
+            &variant.kind,
+            diagnostics,
+        );
+    }
+}
+
+pub(super) fn validate(path: &str, kind: &Kind, diagnostics: &mut Vec<KindDiagnostic>) {
+    match kind {
+        Kind::Empty | Kind::Bits(_) => {}
+        Kind::Array(array) => validate(&format!("{path}[]"), &array.base, diagnostics),
+        Kind::Tuple(tuple) => {
+            for (ndx, element) in tuple.elements.iter().enumerate() {
+                validate(&format!("{path}.{ndx}"), element, diagnostics);
+            }
+        }
+        Kind::Struct(structure) => validate_fields(path, &structure.fields, diagnostics),
+        Kind::Union(union) => validate_fields(path, &union.fields, diagnostics),
+        Kind::Enum(e) => validate_enum(path, e, diagnostics),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::DiscriminantAlignment;
+
+    fn make_enum(discriminants: &[(&str, i64)], width: usize) -> Kind {
+        Kind::make_enum(
+            discriminants
+                .iter()
+                .map(|(name, discriminant)| Kind::make_variant(name, Kind::Empty, *discriminant))
+                .collect(),
+            width,
+            DiscriminantAlignment::Msb,
+        )
+    }
+
+    #[test]
+    fn test_valid_kinds() {
+        assert!(make_enum(&[("A", 0), ("B", 3)], 2).validate().is_empty());
+        assert!(make_enum(&[("A", -2), ("B", 1)], 2).validate().is_empty());
+        assert!(make_enum(&[("A", 0)], 0).validate().is_empty());
+        assert!(make_enum(&[("A", i64::MIN), ("B", i64::MAX)], 64)
+            .validate()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_discriminant_range() {
+        assert_eq!(
+            make_enum(&[("A", 0), ("B", 4)], 2).validate(),
+            vec![KindDiagnostic {
+                path: "".into(),
+                problem: KindProblem::DiscriminantOutOfRange {
+                    variant: "B".into(),
+                    discriminant: 4,
+                    width: 2,
+                    signed: false
+                }
+            }]
+        );
+        // 3 fits in 2 unsigned bits, but not in 2 signed bits
+        let diagnostics = make_enum(&[("A", -1), ("B", 3)], 2).validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "discriminant 3 of variant B does not fit in 2 signed bits"
+        );
+    }
+
+    #[test]
+    fn test_duplicates_are_reported_with_paths() {
+        let kind = Kind::make_struct(vec![
+            Kind::make_field("a", Kind::make_bits(1)),
+            Kind::make_field(
+                "b",
+                Kind::make_tuple(vec![
+                    Kind::make_bits(3),
+                    Kind::make_array(make_enum(&[("A", 1), ("A", 1)], 1), 2),
+                ]),
+            ),
+            Kind::make_field("a", Kind::make_bits(2)),
+            Kind::make_field("e", make_enum(&[], 0)),
+        ]);
+        let diagnostics = kind
+            .validate()
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec![
+                "field a is defined more than once",
+                ".b.1[]: variant A is defined more than once",
+                ".b.1[]: variants A and A share the discriminant 1",
+                ".e: enum has no variants",
+            ]
+        );
+    }
+}
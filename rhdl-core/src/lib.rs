@@ -5,14 +5,33 @@ pub mod clock_details;
 pub mod log_builder;
 pub mod logger;
 pub mod tag_id;
+mod typed_bits;
 
 pub use clock_details::ClockDetails;
+pub use digital::check_bin_len;
+pub use digital::field_ranges;
+pub use digital::split_discriminant;
+pub use digital::take_bin;
 pub use digital::Digital;
+pub use digital::FromBinError;
 pub use kind::DiscriminantAlignment;
+pub use kind::FieldPlacement;
+pub use kind::KindChange;
+pub use kind::KindDiagnostic;
+pub use kind::KindDiff;
+pub use kind::KindDifference;
+pub use kind::KindProblem;
+pub use kind::KindSchema;
+pub use kind::Layout;
+pub use kind::LayoutEntry;
+pub use kind::LeafPath;
+pub use kind::SchemaError;
+pub use kind::SCHEMA_VERSION;
 pub use log_builder::LogBuilder;
 pub use logger::Logger;
 pub use logger::LoggerImpl;
 pub use tag_id::TagID;
+pub use typed_bits::TypedBits;
 
 #[cfg(feature = "svg")]
 pub use kind::kind_svg::svg_grid;
@@ -21,5 +40,6 @@ pub use kind::kind_svg::svg_grid_vertical;
 
 pub use kind::text_grid;
 pub mod ast;
+pub mod codegen;
 pub mod display_ast;
 pub mod path;
@@ -5,6 +5,8 @@ pub trait LogBuilder {
     fn scope(&self, name: &str) -> Self::SubBuilder;
     fn tag<T: Digital>(&mut self, name: &str) -> TagID<T>;
     fn allocate<T: Digital>(&self, tag: TagID<T>, width: usize);
+    // Allocate a signal that carries 4-state (0/1/X/Z) values.
+    fn allocate_logic<T: Digital>(&self, tag: TagID<T>, width: usize);
     fn namespace(&self, name: &str) -> Self::SubBuilder;
     fn add_clock(&mut self, clock: ClockDetails);
     fn add_simple_clock(&mut self, period_in_fs: u64) {
@@ -28,6 +30,9 @@ impl<T: LogBuilder> LogBuilder for &mut T {
     fn allocate<S: Digital>(&self, tag: TagID<S>, width: usize) {
         (**self).allocate(tag, width)
     }
+    fn allocate_logic<S: Digital>(&self, tag: TagID<S>, width: usize) {
+        (**self).allocate_logic(tag, width)
+    }
     fn namespace(&self, name: &str) -> Self::SubBuilder {
         (**self).namespace(name)
     }
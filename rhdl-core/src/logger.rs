@@ -16,6 +16,12 @@ impl<T: LoggerImpl> LoggerImpl for &mut T {
     fn write_bits<S: Digital>(&mut self, tag: TagID<S>, val: u128) {
         (**self).write_bits(tag, val)
     }
+    fn write_wide_bits<S: Digital>(&mut self, tag: TagID<S>, val: &[u64]) {
+        (**self).write_wide_bits(tag, val)
+    }
+    fn write_logic<S: Digital>(&mut self, tag: TagID<S>, aval: u128, bval: u128) {
+        (**self).write_logic(tag, aval, bval)
+    }
     fn write_string<S: Digital>(&mut self, tag: TagID<S>, val: &'static str) {
         (**self).write_string(tag, val)
     }
@@ -27,6 +33,12 @@ impl<T: LoggerImpl> LoggerImpl for &mut T {
 pub trait LoggerImpl: Sized {
     fn write_bool<S: Digital>(&mut self, tag: TagID<S>, val: bool);
     fn write_bits<S: Digital>(&mut self, tag: TagID<S>, val: u128);
+    // Values wider than 128 bits are written as 64 bit limbs,
+    // least significant limb first.
+    fn write_wide_bits<S: Digital>(&mut self, tag: TagID<S>, val: &[u64]);
+    // 4-state values are written as a pair of planes, with
+    // 0 = (0, 0), 1 = (1, 0), Z = (0, 1) and X = (1, 1).
+    fn write_logic<S: Digital>(&mut self, tag: TagID<S>, aval: u128, bval: u128);
     fn write_string<S: Digital>(&mut self, tag: TagID<S>, val: &'static str);
     fn skip<S: Digital>(&mut self, tag: TagID<S>);
 }
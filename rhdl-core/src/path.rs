@@ -1,55 +1,488 @@
 use std::ops::Range;
 
-use anyhow::bail;
-use anyhow::Result;
-
 use crate::DiscriminantAlignment;
 use crate::Kind;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Path {
     All,
     Index(usize),
-    Field(&'static str),
+    // An array index that is only known at run time (i.e., a mux)
+    DynamicIndex,
+    Field(String),
     EnumDiscriminant,
-    EnumPayload(&'static str),
+    EnumPayload(String),
+}
+
+// The text form of a path uses the same notation as the layout of
+// a Kind, so that `.c[1]` or `#Color.g` can be written by hand:
+//   .name           a field of a struct (or union)
+//   .0              an element of a tuple
+//   [3]             an element of an array
+//   []              a dynamically indexed element of an array
+//   #Name           the payload of an enum variant
+//   #discriminant   the discriminant of an enum
+//   .*              the whole value
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Path::All => write!(f, ".*"),
+            Path::Index(i) => write!(f, "[{i}]"),
+            Path::DynamicIndex => write!(f, "[]"),
+            Path::Field(name) => write!(f, ".{name}"),
+            Path::EnumDiscriminant => write!(f, "#discriminant"),
+            Path::EnumPayload(name) => write!(f, "#{name}"),
+        }
+    }
+}
+
+/// Write a path in its text form, e.g., `#Color.g`.  This
+/// is the inverse of [parse_path].
+pub fn path_string(path: &[Path]) -> String {
+    path.iter().map(|p| p.to_string()).collect()
+}
+
+/// The reason the text form of a path could not be parsed.  Each
+/// error records the byte position in the text where it was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParsePathError {
+    /// A `.` or `#` is not followed by a name.
+    MissingName { position: usize, separator: char },
+    /// A `[` has no matching `]`.
+    UnclosedBracket { position: usize },
+    /// The text between `[` and `]` is not a decimal number.
+    InvalidIndex { position: usize, index: String },
+    /// A character that cannot start a step of a path.
+    Unexpected { position: usize, found: char },
+}
+
+impl ParsePathError {
+    /// The byte position in the text where the error was found.
+    pub fn position(&self) -> usize {
+        match self {
+            ParsePathError::MissingName { position, .. }
+            | ParsePathError::UnclosedBracket { position }
+            | ParsePathError::InvalidIndex { position, .. }
+            | ParsePathError::Unexpected { position, .. } => *position,
+        }
+    }
+}
+
+impl std::fmt::Display for ParsePathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParsePathError::MissingName {
+                position,
+                separator,
+            } => write!(
+                f,
+                "expected a name after '{separator}' at position {position}"
+            ),
+            ParsePathError::UnclosedBracket { position } => {
+                write!(f, "unclosed '[' at position {position}")
+            }
+            ParsePathError::InvalidIndex { position, index } => {
+                write!(f, "invalid array index {index:?} at position {position}")
+            }
+            ParsePathError::Unexpected { position, found } => {
+                write!(f, "unexpected '{found}' at position {position}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParsePathError {}
+
+// An index is written in plain decimal, with no sign and no leading
+// zeros, so that each index has exactly one text form (as in `[3]`
+// or the tuple element `.3`).
+fn parse_index(text: &str) -> Option<usize> {
+    let plain = text.bytes().all(|b| b.is_ascii_digit()) && (text == "0" || !text.starts_with('0'));
+    if plain {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Parse the text form of a path, e.g., `.c[1]`, `#Color.g`
+/// or `.0.#discriminant`.  The empty string is the empty path, and
+/// array indices are plain decimal numbers (so `[+1]` and `[01]`
+/// are rejected).
+pub fn parse_path(text: &str) -> Result<Vec<Path>, ParsePathError> {
+    let is_name = |c: char| c.is_alphanumeric() || c == '_';
+    let mut path = vec![];
+    let mut rest = text;
+    while let Some(first) = rest.chars().next() {
+        let position = text.len() - rest.len();
+        rest = &rest[first.len_utf8()..];
+        match first {
+            '.' if rest.starts_with('*') => {
+                rest = &rest[1..];
+                path.push(Path::All);
+            }
+            // A separator before a `#`, as in `.0.#discriminant`
+            '.' if rest.starts_with('#') => {}
+            '.' | '#' => {
+                let end = rest.find(|c| !is_name(c)).unwrap_or(rest.len());
+                let name = &rest[..end];
+                if name.is_empty() {
+                    return Err(ParsePathError::MissingName {
+                        position,
+                        separator: first,
+                    });
+                }
+                rest = &rest[end..];
+                path.push(match (first, name) {
+                    ('.', _) => Path::Field(name.to_string()),
+                    (_, "discriminant") => Path::EnumDiscriminant,
+                    _ => Path::EnumPayload(name.to_string()),
+                });
+            }
+            '[' => {
+                let Some(end) = rest.find(']') else {
+                    return Err(ParsePathError::UnclosedBracket { position });
+                };
+                let index = &rest[..end];
+                rest = &rest[end + 1..];
+                if index.is_empty() {
+                    path.push(Path::DynamicIndex);
+                } else {
+                    let Some(parsed) = parse_index(index) else {
+                        return Err(ParsePathError::InvalidIndex {
+                            position,
+                            index: index.to_string(),
+                        });
+                    };
+                    path.push(Path::Index(parsed));
+                }
+            }
+            _ => {
+                return Err(ParsePathError::Unexpected {
+                    position,
+                    found: first,
+                })
+            }
+        }
+    }
+    Ok(path)
+}
+
+/// One dynamic index along a path.  Selecting element `i` moves
+/// the bits of the path up by `i * stride`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stride {
+    /// The index (in the path) of the dynamic index.
+    pub step: usize,
+    /// The array that is indexed.
+    pub kind: Kind,
+    pub stride: usize,
+    pub count: usize,
+}
+
+/// The location of a path that contains dynamic indices.  The
+/// bits are at `base` when every dynamic index is zero, and are
+/// moved by the strides (in path order) for the other indices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicBitRange {
+    pub base: Range<usize>,
+    pub strides: Vec<Stride>,
+    pub kind: Kind,
+}
+
+impl DynamicBitRange {
+    /// The range selected by the given values of the dynamic indices.
+    /// An index past the end of its array is reported at the step of
+    /// the path where it appears.  Panics if there is not exactly one
+    /// index per stride.
+    pub fn range(&self, indices: &[usize]) -> Result<Range<usize>, PathError> {
+        assert_eq!(
+            indices.len(),
+            self.strides.len(),
+            "Expected {} dynamic indices, but got {}",
+            self.strides.len(),
+            indices.len()
+        );
+        let mut offset = 0;
+        for (index, stride) in indices.iter().zip(&self.strides) {
+            if *index >= stride.count {
+                return Err(PathError::IndexOutOfBounds {
+                    step: stride.step,
+                    element: Path::Index(*index),
+                    kind: stride.kind.clone(),
+                    size: stride.count,
+                });
+            }
+            offset += index * stride.stride;
+        }
+        Ok(self.base.start + offset..self.base.end + offset)
+    }
+    /// Every range the path can select, with the last dynamic
+    /// index varying fastest.  These are the inputs of the mux.
+    pub fn candidates(&self) -> Vec<Range<usize>> {
+        self.strides
+            .iter()
+            .fold(vec![self.base.clone()], |ranges, stride| {
+                ranges
+                    .into_iter()
+                    .flat_map(|range| {
+                        (0..stride.count).map(move |i| {
+                            range.start + i * stride.stride..range.end + i * stride.stride
+                        })
+                    })
+                    .collect()
+            })
+    }
+}
+
+/// The reason a path could not be followed through a [Kind].  Each
+/// error records the index of the step that failed in the path,
+/// the element of the path at that step, and the kind it was
+/// applied to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathError {
+    /// An array or tuple index is past the end.
+    IndexOutOfBounds {
+        step: usize,
+        element: Path,
+        kind: Kind,
+        size: usize,
+    },
+    /// An index was applied to something other than an array or a
+    /// tuple (or a dynamic index to something other than an array).
+    NotIndexable {
+        step: usize,
+        element: Path,
+        kind: Kind,
+    },
+    /// The struct or union has no field with this name.
+    FieldNotFound {
+        step: usize,
+        element: Path,
+        kind: Kind,
+    },
+    /// A field was applied to something other than a struct or union.
+    NoFields {
+        step: usize,
+        element: Path,
+        kind: Kind,
+    },
+    /// The enum has no variant with this name.
+    VariantNotFound {
+        step: usize,
+        element: Path,
+        kind: Kind,
+    },
+    /// A discriminant or payload was applied to something other
+    /// than an enum.
+    NotAnEnum {
+        step: usize,
+        element: Path,
+        kind: Kind,
+    },
+    /// The path has a dynamic index, and so has no single range
+    /// (see [dynamic_bit_range]).
+    DynamicIndex {
+        step: usize,
+        element: Path,
+        kind: Kind,
+    },
+}
+
+impl PathError {
+    /// The index (in the path) of the step that failed.
+    pub fn step(&self) -> usize {
+        self.parts().0
+    }
+    /// The element of the path that could not be followed.
+    pub fn element(&self) -> &Path {
+        self.parts().1
+    }
+    /// The kind the element was applied to.
+    pub fn kind(&self) -> &Kind {
+        self.parts().2
+    }
+    fn parts(&self) -> (usize, &Path, &Kind) {
+        match self {
+            PathError::IndexOutOfBounds {
+                step,
+                element,
+                kind,
+                ..
+            }
+            | PathError::NotIndexable {
+                step,
+                element,
+                kind,
+            }
+            | PathError::FieldNotFound {
+                step,
+                element,
+                kind,
+            }
+            | PathError::NoFields {
+                step,
+                element,
+                kind,
+            }
+            | PathError::VariantNotFound {
+                step,
+                element,
+                kind,
+            }
+            | PathError::NotAnEnum {
+                step,
+                element,
+                kind,
+            }
+            | PathError::DynamicIndex {
+                step,
+                element,
+                kind,
+            } => (*step, element, kind),
+        }
+    }
+}
+
+// A short description of a kind, for error messages.
+fn describe(kind: &Kind) -> String {
+    match kind {
+        Kind::Empty => "()".to_string(),
+        Kind::Bits(digits) => format!("{} bits", digits),
+        Kind::Array(array) => format!("an array of {}", array.size),
+        Kind::Tuple(tuple) => format!("a tuple of {}", tuple.elements.len()),
+        Kind::Struct(_) => "a struct".to_string(),
+        Kind::Union(_) => "a union".to_string(),
+        Kind::Enum(_) => "an enum".to_string(),
+    }
 }
 
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "step {} ({}): ", self.step(), self.element())?;
+        let kind = describe(self.kind());
+        match self {
+            PathError::IndexOutOfBounds { .. } => write!(f, "index out of bounds for {}", kind),
+            PathError::NotIndexable { .. } => write!(f, "cannot index into {}", kind),
+            PathError::FieldNotFound { .. } => write!(f, "field not found in {}", kind),
+            PathError::NoFields { .. } => write!(f, "{} has no fields", kind),
+            PathError::VariantNotFound { .. } => write!(f, "variant not found in {}", kind),
+            PathError::NotAnEnum { .. } => write!(f, "{} is not an enum", kind),
+            PathError::DynamicIndex { .. } => write!(
+                f,
+                "dynamic index has no single range (use dynamic_bit_range)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
 // Given a Kind and a Vec<Path>, compute the bit offsets of
 // the endpoint of the path within the original data structure.
-pub fn bit_range(kind: Kind, path: &[Path]) -> Result<(Range<usize>, Kind)> {
+pub fn bit_range(kind: Kind, path: &[Path]) -> Result<(Range<usize>, Kind), PathError> {
+    if let Some(step) = path.iter().position(|p| p == &Path::DynamicIndex) {
+        let kind = dynamic_bit_range(kind, &path[..step])?.kind;
+        return Err(PathError::DynamicIndex {
+            step,
+            element: Path::DynamicIndex,
+            kind,
+        });
+    }
+    let dynamic = dynamic_bit_range(kind, path)?;
+    Ok((dynamic.base, dynamic.kind))
+}
+
+// As bit_range, but the path may also index arrays dynamically.
+pub fn dynamic_bit_range(kind: Kind, path: &[Path]) -> Result<DynamicBitRange, PathError> {
     let mut range = 0..kind.bits();
     let mut kind = kind;
-    for p in path {
+    let mut strides = vec![];
+    for (step, original) in path.iter().enumerate() {
+        let element = original.clone();
+        // Tuple elements are written as fields (like `.0`), as in Rust
+        let index;
+        let p = match (original, &kind) {
+            (Path::Field(field), Kind::Tuple(_)) => match parse_index(field) {
+                Some(i) => {
+                    index = Path::Index(i);
+                    &index
+                }
+                None => original,
+            },
+            _ => original,
+        };
         match p {
             Path::All => (),
+            Path::DynamicIndex => match &kind {
+                Kind::Array(array) => {
+                    let element_size = array.base.bits();
+                    strides.push(Stride {
+                        step,
+                        kind: kind.clone(),
+                        stride: element_size,
+                        count: array.size,
+                    });
+                    range = range.start..range.start + element_size;
+                    kind = *array.base.clone();
+                }
+                _ => {
+                    return Err(PathError::NotIndexable {
+                        step,
+                        element,
+                        kind,
+                    })
+                }
+            },
             Path::Index(i) => match &kind {
                 Kind::Array(array) => {
                     let element_size = array.base.bits();
                     if i >= &array.size {
-                        bail!("Array index out of bounds")
+                        let size = array.size;
+                        return Err(PathError::IndexOutOfBounds {
+                            step,
+                            element,
+                            kind,
+                            size,
+                        });
                     }
                     range = range.start + i * element_size..range.start + (i + 1) * element_size;
                     kind = *array.base.clone();
                 }
                 Kind::Tuple(tuple) => {
                     if i >= &tuple.elements.len() {
-                        bail!("Tuple index out of bounds")
+                        let size = tuple.elements.len();
+                        return Err(PathError::IndexOutOfBounds {
+                            step,
+                            element,
+                            kind,
+                            size,
+                        });
                     }
-                    let offset = dbg!(tuple.elements[0..*i]
+                    let offset = tuple.elements[0..*i]
                         .iter()
                         .map(|e| e.bits())
-                        .sum::<usize>());
-                    let size = dbg!(tuple.elements[*i].bits());
+                        .sum::<usize>();
+                    let size = tuple.elements[*i].bits();
                     range = range.start + offset..range.start + offset + size;
                     kind = tuple.elements[*i].clone();
                 }
-                _ => bail!("Indexing non-indexable type"),
+                _ => {
+                    return Err(PathError::NotIndexable {
+                        step,
+                        element,
+                        kind,
+                    })
+                }
             },
             Path::Field(field) => match &kind {
                 Kind::Struct(structure) => {
                     if !structure.fields.iter().any(|f| &f.name == field) {
-                        bail!("Field not found")
+                        return Err(PathError::FieldNotFound {
+                            step,
+                            element,
+                            kind,
+                        });
                     }
                     let offset = structure
                         .fields
@@ -67,7 +500,34 @@ pub fn bit_range(kind: Kind, path: &[Path]) -> Result<(Range<usize>, Kind)> {
                     range = range.start + offset..range.start + offset + size;
                     kind = field.clone();
                 }
-                _ => bail!("Field indexing not allowed on this type"),
+                Kind::Union(union) => {
+                    let Some(field) = union.fields.iter().find(|f| &f.name == field) else {
+                        return Err(PathError::FieldNotFound {
+                            step,
+                            element,
+                            kind,
+                        });
+                    };
+                    let field = field.kind.clone();
+                    // Every field of a union starts at the first bit
+                    range = range.start..range.start + field.bits();
+                    kind = field;
+                }
+                // A tuple element that is not a number
+                Kind::Tuple(_) => {
+                    return Err(PathError::FieldNotFound {
+                        step,
+                        element,
+                        kind,
+                    })
+                }
+                _ => {
+                    return Err(PathError::NoFields {
+                        step,
+                        element,
+                        kind,
+                    })
+                }
             },
             Path::EnumDiscriminant => match &kind {
                 Kind::Enum(enumerate) => {
@@ -81,17 +541,24 @@ pub fn bit_range(kind: Kind, path: &[Path]) -> Result<(Range<usize>, Kind)> {
                     };
                     kind = Kind::Bits(enumerate.discriminant_width);
                 }
-                _ => bail!("Enum discriminant not valid for non-enum types"),
+                _ => {
+                    return Err(PathError::NotAnEnum {
+                        step,
+                        element,
+                        kind,
+                    })
+                }
             },
             Path::EnumPayload(name) => match &kind {
                 Kind::Enum(enumerate) => {
-                    let field = enumerate
-                        .variants
-                        .iter()
-                        .find(|f| &f.name == name)
-                        .ok_or_else(|| anyhow::anyhow!("Enum payload not found"))?
-                        .kind
-                        .clone();
+                    let Some(variant) = enumerate.variants.iter().find(|f| &f.name == name) else {
+                        return Err(PathError::VariantNotFound {
+                            step,
+                            element,
+                            kind,
+                        });
+                    };
+                    let field = variant.kind.clone();
                     range = match enumerate.discriminant_alignment {
                         DiscriminantAlignment::Lsb => {
                             range.start + enumerate.discriminant_width
@@ -99,12 +566,289 @@ pub fn bit_range(kind: Kind, path: &[Path]) -> Result<(Range<usize>, Kind)> {
                         }
                         DiscriminantAlignment::Msb => range.start..range.start + field.bits(),
                     };
-                    dbg!(&range);
                     kind = field;
                 }
-                _ => bail!("Enum payload not valid for non-enum types"),
+                _ => {
+                    return Err(PathError::NotAnEnum {
+                        step,
+                        element,
+                        kind,
+                    })
+                }
             },
         }
     }
-    Ok((range, kind))
+    Ok(DynamicBitRange {
+        base: range,
+        strides,
+        kind,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_index() {
+        // An array of 4 (b3, b5) pairs, selecting the second element
+        let pair = Kind::make_tuple(vec![Kind::make_bits(3), Kind::make_bits(5)]);
+        let kind = Kind::make_array(pair, 4);
+        let dynamic =
+            dynamic_bit_range(kind.clone(), &[Path::DynamicIndex, Path::Index(1)]).unwrap();
+        assert_eq!(dynamic.base, 3..8);
+        assert_eq!(
+            dynamic.strides,
+            vec![Stride {
+                step: 0,
+                kind: kind.clone(),
+                stride: 8,
+                count: 4
+            }]
+        );
+        assert_eq!(dynamic.kind, Kind::make_bits(5));
+        assert_eq!(dynamic.candidates(), vec![3..8, 11..16, 19..24, 27..32]);
+        assert_eq!(dynamic.range(&[2]).unwrap(), 19..24);
+        assert_eq!(
+            dynamic.range(&[4]),
+            Err(PathError::IndexOutOfBounds {
+                step: 0,
+                element: Path::Index(4),
+                kind: kind.clone(),
+                size: 4,
+            })
+        );
+        // Each candidate is the same as the constant index
+        for (i, candidate) in dynamic.candidates().into_iter().enumerate() {
+            assert_eq!(
+                bit_range(kind.clone(), &[Path::Index(i), Path::Index(1)])
+                    .unwrap()
+                    .0,
+                candidate
+            );
+        }
+        // A constant path has no strides
+        let fixed = dynamic_bit_range(kind.clone(), &[Path::Index(1)]).unwrap();
+        assert!(fixed.strides.is_empty());
+        assert_eq!(fixed.candidates(), vec![8..16]);
+        assert!(bit_range(kind, &[Path::DynamicIndex]).is_err());
+    }
+
+    #[test]
+    fn test_nested_dynamic_index() {
+        let kind = Kind::make_array(Kind::make_array(Kind::make_bits(2), 3), 2);
+        let dynamic = dynamic_bit_range(kind, &[Path::DynamicIndex, Path::DynamicIndex]).unwrap();
+        assert_eq!(dynamic.kind, Kind::make_bits(2));
+        assert_eq!(
+            dynamic.candidates(),
+            vec![0..2, 2..4, 4..6, 6..8, 8..10, 10..12]
+        );
+        assert_eq!(dynamic.range(&[1, 2]).unwrap(), 10..12);
+        let error = dynamic.range(&[1, 3]).unwrap_err();
+        assert_eq!(error.step(), 1);
+        assert_eq!(
+            error.to_string(),
+            "step 1 ([3]): index out of bounds for an array of 3"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected 1 dynamic indices, but got 0")]
+    fn test_dynamic_index_count() {
+        let kind = Kind::make_array(Kind::make_bits(2), 3);
+        let dynamic = dynamic_bit_range(kind, &[Path::DynamicIndex]).unwrap();
+        let _ = dynamic.range(&[]);
+    }
+
+    #[test]
+    fn test_tuple_element_names() {
+        let kind = Kind::make_tuple(vec![Kind::make_bits(3), Kind::make_bits(5)]);
+        assert_eq!(
+            bit_range(kind.clone(), &[Path::Field("1".into())]).unwrap(),
+            (3..8, Kind::make_bits(5))
+        );
+        // Only the plain decimal form names an element, as in parse_path
+        for name in ["+1", "01", "-0"] {
+            assert_eq!(
+                bit_range(kind.clone(), &[Path::Field(name.into())]),
+                Err(PathError::FieldNotFound {
+                    step: 0,
+                    element: Path::Field(name.into()),
+                    kind: kind.clone(),
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("#Color.g").unwrap(),
+            vec![Path::EnumPayload("Color".into()), Path::Field("g".into())]
+        );
+        assert_eq!(
+            parse_path(".c[1]").unwrap(),
+            vec![Path::Field("c".into()), Path::Index(1)]
+        );
+        assert_eq!(
+            parse_path(".0.#discriminant").unwrap(),
+            vec![Path::Field("0".into()), Path::EnumDiscriminant]
+        );
+        assert_eq!(
+            parse_path("[][2].*").unwrap(),
+            vec![Path::DynamicIndex, Path::Index(2), Path::All]
+        );
+        assert_eq!(parse_path("").unwrap(), vec![]);
+        assert_eq!(
+            parse_path(".é[0]").unwrap(),
+            vec![Path::Field("é".into()), Path::Index(0)]
+        );
+        for bad in [
+            ".", "#", "[1", "[x]", "c", ".a..b", "#A#", "[+1]", "[-1]", "[ 1]", "[01]", "é",
+            ".a→b", "[1]→",
+        ] {
+            assert!(parse_path(bad).is_err(), "{bad:?} should not parse");
+        }
+        assert_eq!(
+            parse_path(".a[x]"),
+            Err(ParsePathError::InvalidIndex {
+                position: 2,
+                index: "x".into()
+            })
+        );
+        assert_eq!(
+            parse_path("#A.").unwrap_err().to_string(),
+            "expected a name after '.' at position 2"
+        );
+        assert_eq!(parse_path(".é→").unwrap_err().position(), 3);
+    }
+
+    #[test]
+    fn test_path_display_round_trip() {
+        let path = vec![
+            Path::EnumPayload("Color".into()),
+            Path::Field("g".into()),
+            Path::Index(3),
+            Path::DynamicIndex,
+            Path::Field("0".into()),
+            Path::EnumDiscriminant,
+            Path::All,
+        ];
+        let text = path_string(&path);
+        assert_eq!(text, "#Color.g[3][].0#discriminant.*");
+        assert_eq!(parse_path(&text).unwrap(), path);
+    }
+
+    #[test]
+    fn test_layout_paths_parse() {
+        // Every path in a layout resolves to the range of that entry
+        let kind = Kind::make_struct(vec![
+            Kind::make_field(
+                "c",
+                Kind::make_array(
+                    Kind::make_tuple(vec![Kind::make_bits(2), Kind::make_bits(3)]),
+                    2,
+                ),
+            ),
+            Kind::make_field(
+                "e",
+                Kind::make_enum(
+                    vec![
+                        Kind::make_variant("A", Kind::Empty, 0),
+                        Kind::make_variant(
+                            "B",
+                            Kind::make_struct(vec![Kind::make_field("y", Kind::make_bits(4))]),
+                            1,
+                        ),
+                    ],
+                    2,
+                    DiscriminantAlignment::Msb,
+                ),
+            ),
+        ]);
+        for entry in kind.layout().entries {
+            let path = parse_path(&entry.path).unwrap();
+            assert_eq!(path_string(&path), entry.path);
+            assert_eq!(bit_range(kind.clone(), &path).unwrap().0, entry.range);
+        }
+    }
+
+    #[test]
+    fn test_dynamic_index_on_tuple_fails() {
+        let kind = Kind::make_tuple(vec![Kind::make_bits(3), Kind::make_bits(5)]);
+        assert_eq!(
+            dynamic_bit_range(kind.clone(), &[Path::DynamicIndex]),
+            Err(PathError::NotIndexable {
+                step: 0,
+                element: Path::DynamicIndex,
+                kind,
+            })
+        );
+    }
+
+    #[test]
+    fn test_path_errors() {
+        let color = Kind::make_struct(vec![
+            Kind::make_field("r", Kind::make_bits(8)),
+            Kind::make_field("g", Kind::make_bits(8)),
+        ]);
+        let kind = Kind::make_enum(
+            vec![
+                Kind::make_variant("Color", color.clone(), 1),
+                Kind::make_variant("Pair", Kind::make_array(Kind::make_bits(4), 2), 2),
+            ],
+            2,
+            DiscriminantAlignment::Lsb,
+        );
+        let error = |text: &str| bit_range(kind.clone(), &parse_path(text).unwrap()).unwrap_err();
+        assert_eq!(
+            error("#Color.b"),
+            PathError::FieldNotFound {
+                step: 1,
+                element: Path::Field("b".into()),
+                kind: color.clone(),
+            }
+        );
+        assert_eq!(
+            error("#Color.g[0]"),
+            PathError::NotIndexable {
+                step: 2,
+                element: Path::Index(0),
+                kind: Kind::make_bits(8),
+            }
+        );
+        assert!(matches!(
+            error("#Pair[2]"),
+            PathError::IndexOutOfBounds {
+                step: 1,
+                size: 2,
+                ..
+            }
+        ));
+        assert!(matches!(
+            error("#Size"),
+            PathError::VariantNotFound { step: 0, .. }
+        ));
+        assert!(matches!(
+            error("#Color#discriminant"),
+            PathError::NotAnEnum { step: 1, .. }
+        ));
+        assert!(matches!(
+            error("#Pair.x"),
+            PathError::NoFields { step: 1, .. }
+        ));
+        assert!(matches!(
+            error("#Pair[].y"),
+            PathError::DynamicIndex { step: 1, .. }
+        ));
+        let err = error("#Color.b");
+        assert_eq!(err.step(), 1);
+        assert_eq!(err.element(), &Path::Field("b".into()));
+        assert_eq!(err.kind(), &color);
+        assert_eq!(err.to_string(), "step 1 (.b): field not found in a struct");
+        assert_eq!(
+            error("#Pair[2]").to_string(),
+            "step 1 ([2]): index out of bounds for an array of 2"
+        );
+    }
 }
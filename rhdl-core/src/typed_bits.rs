@@ -0,0 +1,452 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use anyhow::{anyhow, Result};
+
+use crate::digital::split_discriminant;
+use crate::kind::Variant;
+use crate::path::{bit_range, path_string, Path};
+use crate::{Digital, FromBinError, Kind};
+
+/// A value whose type is only known at run time.  It pairs the
+/// [Kind] of a [Digital] type with the bits of a value of that
+/// type, so that tools (debuggers, loggers, test reports) can
+/// pick apart and print values without access to the Rust type.
+///
+/// Values are displayed with Rust like syntax.  Bits are shown
+/// in hex, and enum variants are shown by name, since a [Kind]
+/// does not record the name of the enum itself.
+/// ```
+/// # use rhdl_core::{Digital, TypedBits};
+/// # use rhdl_bits::Bits;
+/// let x = (true, [Bits::<8>::from(0xaa), Bits::<8>::from(0x5)]).typed_bits();
+/// assert_eq!(x.to_string(), "(0x1, [0xaa, 0x05])");
+/// assert_eq!(x.index(1)?.index(0)?, Bits::<8>::from(0xaa).typed_bits());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedBits {
+    // Private, so that the bits are always as wide as the kind
+    kind: Kind,
+    bits: Vec<bool>,
+}
+
+impl TypedBits {
+    /// Pair a [Kind] with its bits.  The number of bits must
+    /// match the width of the kind.
+    pub fn new(kind: Kind, bits: Vec<bool>) -> Result<Self, FromBinError> {
+        if bits.len() != kind.bits() {
+            return Err(FromBinError::WrongLength {
+                expected: kind.bits(),
+                actual: bits.len(),
+            });
+        }
+        Ok(Self { kind, bits })
+    }
+    /// The kind of the value.
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+    /// The bits of the value, LSB first.
+    pub fn bits(&self) -> &[bool] {
+        &self.bits
+    }
+    /// Take the bits of the value, LSB first.
+    pub fn into_bits(self) -> Vec<bool> {
+        self.bits
+    }
+    /// Convert back to a concrete [Digital] type.  The kind of
+    /// `T` must match the kind of this value.
+    pub fn to_value<T: Digital>(&self) -> Result<T> {
+        if T::static_kind() != self.kind {
+            return Err(anyhow!(
+                "Kind mismatch: expected {:?}, found {:?}",
+                T::static_kind(),
+                self.kind
+            ));
+        }
+        Ok(T::from_bin(&self.bits)?)
+    }
+    /// Extract the part of the value that lies at the end of
+    /// the given path.
+    pub fn path(&self, path: &[Path]) -> Result<TypedBits> {
+        let (range, kind) = bit_range(self.kind.clone(), path)?;
+        Ok(TypedBits {
+            kind,
+            bits: self.bits[range].to_vec(),
+        })
+    }
+    /// Extract a field of a struct, or a view of a union.
+    pub fn field(&self, name: &str) -> Result<TypedBits> {
+        self.path(&[Path::Field(name.into())])
+    }
+    /// Extract an element of an array or tuple.
+    pub fn index(&self, index: usize) -> Result<TypedBits> {
+        self.path(&[Path::Index(index)])
+    }
+    /// Extract the discriminant of an enum.
+    pub fn discriminant(&self) -> Result<TypedBits> {
+        self.path(&[Path::EnumDiscriminant])
+    }
+    /// Extract the payload of the named variant of an enum.  The
+    /// payload is extracted regardless of which variant is active.
+    pub fn payload(&self, name: &str) -> Result<TypedBits> {
+        self.path(&[Path::EnumPayload(name.into())])
+    }
+    /// Replace the part of the value that lies at the end of the
+    /// given path with new bits.  The path may only pass through
+    /// the payload of the active variant of an enum, since the
+    /// payload of any other variant does not exist in the value.
+    /// Writing a different discriminant zeroes the payload, so the
+    /// result is the canonical value of the new variant.
+    pub fn with_path(&self, path: &[Path], bits: &[bool]) -> Result<TypedBits> {
+        for (ndx, step) in path.iter().enumerate() {
+            let Path::EnumPayload(name) = step else {
+                continue;
+            };
+            let active = self.path(&path[..ndx])?;
+            let variant = active.variant()?;
+            if &variant.name != name {
+                return Err(anyhow!(
+                    "Cannot write to the payload of {} at {}, because the active variant is {}",
+                    name,
+                    path_string(&path[..=ndx]),
+                    variant.name
+                ));
+            }
+        }
+        let (range, _) = bit_range(self.kind.clone(), path)?;
+        if bits.len() != range.len() {
+            return Err(FromBinError::WrongLength {
+                expected: range.len(),
+                actual: bits.len(),
+            }
+            .into());
+        }
+        let mut spliced = self.bits.clone();
+        if let Some((Path::EnumDiscriminant, prefix)) = path.split_last() {
+            if spliced[range.clone()] != *bits {
+                let (enum_range, _) = bit_range(self.kind.clone(), prefix)?;
+                spliced[enum_range].fill(false);
+            }
+        }
+        spliced[range].copy_from_slice(bits);
+        Ok(TypedBits {
+            kind: self.kind.clone(),
+            bits: spliced,
+        })
+    }
+    /// Find the active variant of an enum by decoding its
+    /// discriminant.
+    pub fn variant(&self) -> Result<&Variant> {
+        let Kind::Enum(e) = &self.kind else {
+            return Err(anyhow!("Variant requested for non-enum kind"));
+        };
+        let (discriminant, _) = split_discriminant(&self.kind, &self.bits)?;
+        e.variants
+            .iter()
+            .find(|x| x.discriminant == discriminant)
+            .ok_or_else(|| anyhow!("Invalid discriminant {}", discriminant))
+    }
+}
+
+// Values are ordered as unsigned integers, but only values
+// of the same kind can be compared.
+impl PartialOrd for TypedBits {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.kind != other.kind {
+            return None;
+        }
+        Some(self.bits.iter().rev().cmp(other.bits.iter().rev()))
+    }
+}
+
+fn write_hex(f: &mut Formatter<'_>, bits: &[bool]) -> std::fmt::Result {
+    let digits = bits
+        .chunks(4)
+        .map(|nibble| {
+            let value = nibble
+                .iter()
+                .rev()
+                .fold(0, |acc, bit| (acc << 1) | (*bit as u32));
+            std::char::from_digit(value, 16).unwrap()
+        })
+        .collect::<Vec<_>>();
+    write!(f, "0x")?;
+    if digits.is_empty() {
+        write!(f, "0")?;
+    }
+    digits.iter().rev().try_for_each(|d| write!(f, "{}", d))
+}
+
+fn write_fields<'a>(
+    f: &mut Formatter<'_>,
+    fields: impl Iterator<Item = (&'a str, &'a Kind)>,
+    mut bits: &[bool],
+) -> std::fmt::Result {
+    write!(f, "{{ ")?;
+    for (ndx, (name, kind)) in fields.enumerate() {
+        if ndx > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}: ", name)?;
+        let (head, rest) = bits.split_at(kind.bits());
+        write_value(f, kind, head)?;
+        bits = rest;
+    }
+    write!(f, " }}")
+}
+
+fn write_elements<'a>(
+    f: &mut Formatter<'_>,
+    elements: impl Iterator<Item = &'a Kind>,
+    mut bits: &[bool],
+) -> std::fmt::Result {
+    for (ndx, kind) in elements.enumerate() {
+        if ndx > 0 {
+            write!(f, ", ")?;
+        }
+        let (head, rest) = bits.split_at(kind.bits());
+        write_value(f, kind, head)?;
+        bits = rest;
+    }
+    Ok(())
+}
+
+fn write_value(f: &mut Formatter<'_>, kind: &Kind, bits: &[bool]) -> std::fmt::Result {
+    match kind {
+        Kind::Empty => write!(f, "()"),
+        Kind::Bits(_) => write_hex(f, bits),
+        Kind::Array(array) => {
+            write!(f, "[")?;
+            write_elements(f, std::iter::repeat_n(&*array.base, array.size), bits)?;
+            write!(f, "]")
+        }
+        Kind::Tuple(tuple) => {
+            write!(f, "(")?;
+            write_elements(f, tuple.elements.iter(), bits)?;
+            write!(f, ")")
+        }
+        Kind::Struct(structure) => write_fields(
+            f,
+            structure.fields.iter().map(|x| (x.name.as_str(), &x.kind)),
+            bits,
+        ),
+        // The fields of a union all start at bit zero, so each
+        // one is shown as its own view of the same bits.
+        Kind::Union(union) => {
+            write!(f, "union {{ ")?;
+            for (ndx, field) in union.fields.iter().enumerate() {
+                if ndx > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}: ", field.name)?;
+                write_value(f, &field.kind, &bits[0..field.kind.bits()])?;
+            }
+            write!(f, " }}")
+        }
+        Kind::Enum(e) => {
+            let Ok((discriminant, payload)) = split_discriminant(kind, bits) else {
+                return write!(f, "<invalid>");
+            };
+            let Some(variant) = e.variants.iter().find(|x| x.discriminant == discriminant) else {
+                return write!(f, "<invalid discriminant {}>", discriminant);
+            };
+            let payload = &payload[0..variant.kind.bits()];
+            write!(f, "{}", variant.name)?;
+            match &variant.kind {
+                Kind::Empty => Ok(()),
+                Kind::Tuple(tuple) => {
+                    write!(f, "(")?;
+                    write_elements(f, tuple.elements.iter(), payload)?;
+                    write!(f, ")")
+                }
+                Kind::Struct(structure) => {
+                    write!(f, " ")?;
+                    write_fields(
+                        f,
+                        structure.fields.iter().map(|x| (x.name.as_str(), &x.kind)),
+                        payload,
+                    )
+                }
+                kind => {
+                    write!(f, "(")?;
+                    write_value(f, kind, payload)?;
+                    write!(f, ")")
+                }
+            }
+        }
+    }
+}
+
+impl Display for TypedBits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_value(f, &self.kind, &self.bits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rhdl_bits::{Bits, SignedBits};
+
+    use super::*;
+    use crate::DiscriminantAlignment;
+
+    fn packet_kind() -> Kind {
+        Kind::make_enum(
+            vec![
+                Kind::make_variant(
+                    "Color",
+                    Kind::make_struct(vec![
+                        Kind::make_field("r", Kind::make_bits(8)),
+                        Kind::make_field("g", Kind::make_bits(8)),
+                    ]),
+                    1,
+                ),
+                Kind::make_variant(
+                    "Position",
+                    Kind::make_tuple(vec![Kind::make_bits(4), Kind::make_bits(4)]),
+                    2,
+                ),
+                Kind::make_variant("Level", Kind::make_bits(3), 3),
+                Kind::make_variant("Idle", Kind::Empty, 4),
+            ],
+            3,
+            DiscriminantAlignment::Msb,
+        )
+    }
+
+    fn packet(discriminant: u128, payload: Vec<bool>) -> TypedBits {
+        let kind = packet_kind();
+        let bits = kind.pad(
+            rhdl_bits::bits::<3>(discriminant)
+                .to_bools()
+                .into_iter()
+                .chain(payload)
+                .collect(),
+        );
+        TypedBits::new(kind, bits).unwrap()
+    }
+
+    #[test]
+    fn test_display_enum() {
+        let color = packet(1, (Bits::<8>::from(0xaa), Bits::<8>::from(0x3)).bin());
+        assert_eq!(color.to_string(), "Color { r: 0xaa, g: 0x03 }");
+        assert_eq!(color.variant().unwrap().name, "Color");
+        assert_eq!(
+            color.payload("Color").unwrap().field("r").unwrap().bits,
+            Bits::<8>::from(0xaa).bin()
+        );
+        let position = packet(2, (Bits::<4>::from(1), Bits::<4>::from(0xf)).bin());
+        assert_eq!(position.to_string(), "Position(0x1, 0xf)");
+        let level = packet(3, Bits::<3>::from(5).bin());
+        assert_eq!(level.to_string(), "Level(0x5)");
+        let idle = packet(4, vec![]);
+        assert_eq!(idle.to_string(), "Idle");
+        assert_eq!(
+            idle.discriminant().unwrap().bits,
+            rhdl_bits::bits::<3>(4).to_bools()
+        );
+        let invalid = packet(7, vec![]);
+        assert_eq!(invalid.to_string(), "<invalid discriminant 7>");
+        assert!(invalid.variant().is_err());
+    }
+
+    #[test]
+    fn test_with_path() {
+        let color = packet(1, (Bits::<8>::from(0xaa), Bits::<8>::from(0x3)).bin());
+        let g = [Path::EnumPayload("Color".into()), Path::Field("g".into())];
+        let updated = color.with_path(&g, &Bits::<8>::from(0x7e).bin()).unwrap();
+        assert_eq!(updated.to_string(), "Color { r: 0xaa, g: 0x7e }");
+        assert!(color.with_path(&g, &Bits::<4>::from(1).bin()).is_err());
+        let level = [Path::EnumPayload("Level".into())];
+        assert!(color.with_path(&level, &Bits::<3>::from(1).bin()).is_err());
+        // The discriminant can always be written
+        let idle = color
+            .with_path(
+                &[Path::EnumDiscriminant],
+                &rhdl_bits::bits::<3>(4).to_bools(),
+            )
+            .unwrap();
+        assert_eq!(idle.to_string(), "Idle");
+        assert!(idle.with_path(&g, &Bits::<8>::from(0).bin()).is_err());
+        // The payload of the old variant does not survive the change
+        assert_eq!(idle, packet(4, vec![]));
+        let level = color
+            .with_path(
+                &[Path::EnumDiscriminant],
+                &rhdl_bits::bits::<3>(3).to_bools(),
+            )
+            .unwrap();
+        assert_eq!(level, packet(3, Bits::<3>::from(0).bin()));
+        // Writing the same discriminant leaves the payload alone
+        let same = color
+            .with_path(
+                &[Path::EnumDiscriminant],
+                &rhdl_bits::bits::<3>(1).to_bools(),
+            )
+            .unwrap();
+        assert_eq!(same, color);
+    }
+
+    #[test]
+    fn test_display_structs_and_unions() {
+        let kind = Kind::make_struct(vec![
+            Kind::make_field("a", Kind::make_array(Kind::make_bits(2), 3)),
+            Kind::make_field("b", Kind::Empty),
+            Kind::make_field(
+                "c",
+                Kind::make_union(vec![
+                    Kind::make_field("lo", Kind::make_bits(4)),
+                    Kind::make_field("all", Kind::make_bits(8)),
+                ]),
+            ),
+        ]);
+        let bits = (
+            [Bits::<2>::from(1), Bits::<2>::from(2), Bits::<2>::from(3)],
+            Bits::<8>::from(0x5c),
+        )
+            .bin();
+        let value = TypedBits::new(kind, bits).unwrap();
+        assert_eq!(
+            value.to_string(),
+            "{ a: [0x1, 0x2, 0x3], b: (), c: union { lo: 0xc, all: 0x5c } }"
+        );
+        assert_eq!(
+            value.field("a").unwrap().index(2).unwrap().to_string(),
+            "0x3"
+        );
+        assert!(value.field("d").is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let x = (Bits::<4>::from(9), SignedBits::<4>::from(-2));
+        let typed = x.typed_bits();
+        assert_eq!(typed.to_value::<(Bits<4>, SignedBits<4>)>().unwrap(), x);
+        assert!(typed.to_value::<Bits<8>>().is_err());
+        assert_eq!(
+            typed.index(1).unwrap().to_value::<SignedBits<4>>().unwrap(),
+            x.1
+        );
+        assert_eq!(
+            TypedBits::new(Kind::make_bits(4), vec![true]),
+            Err(FromBinError::WrongLength {
+                expected: 4,
+                actual: 1
+            })
+        );
+        let wide = rhdl_bits::WideBits::<132>::from(1_u128 << 127).typed_bits();
+        assert_eq!(wide.to_string(), format!("0x0{:x}", 1_u128 << 127));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let a = Bits::<8>::from(0x10).typed_bits();
+        let b = Bits::<8>::from(0x02).typed_bits();
+        assert!(a > b);
+        assert_eq!(a.partial_cmp(&a), Some(Ordering::Equal));
+        assert_eq!(a.partial_cmp(&Bits::<7>::from(0x10).typed_bits()), None);
+        assert_ne!(a, b);
+    }
+}
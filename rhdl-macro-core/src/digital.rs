@@ -1,38 +1,366 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{spanned::Spanned, Data, DeriveInput};
+use syn::{
+    spanned::Spanned, Attribute, Data, DeriveInput, Expr, ExprLit, GenericParam, Generics, Ident,
+    Lit, RangeLimits,
+};
 
 use crate::digital_enum::derive_digital_enum;
+use crate::digital_overlay::{derive_digital_overlay, is_overlay};
 
 pub fn derive_digital(input: TokenStream) -> syn::Result<TokenStream> {
     let decl = syn::parse2::<syn::DeriveInput>(input)?;
     match &decl.data {
         Data::Struct(_s) => derive_digital_struct(decl),
+        Data::Enum(_e) if is_overlay(&decl.attrs) => derive_digital_overlay(decl),
         Data::Enum(_e) => derive_digital_enum(decl),
-        _ => Err(syn::Error::new(
+        Data::Union(_u) => Err(syn::Error::new(
             decl.span(),
-            "Only structs and enums can be digital",
+            "Unions are not digital (use an enum with #[rhdl(overlay)] for overlapping views)",
         )),
     }
 }
 
+// Every type parameter must be digital for the fields that use it
+// to be digital, so add a `T: rhdl_core::Digital` bound for each of
+// them to the where clause of the impl.
+pub(crate) fn digital_generics(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    let params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    if params.is_empty() {
+        return generics;
+    }
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#param: rhdl_core::Digital));
+    }
+    generics
+}
+
+// The name of the type parameter of the logging methods, which
+// must not clash with a parameter of the type itself.
+pub(crate) fn log_param(generics: &Generics) -> Ident {
+    let names = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(param.ident.to_string()),
+            GenericParam::Const(param) => Some(param.ident.to_string()),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let mut name = "L".to_string();
+    while names.contains(&name) {
+        name.push('_');
+    }
+    Ident::new(&name, proc_macro2::Span::call_site())
+}
+
 fn derive_digital_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
     match &decl.data {
-        Data::Struct(s) => match s.fields {
-            syn::Fields::Named(_) => derive_digital_named_struct(decl),
-            syn::Fields::Unnamed(_) => derive_digital_tuple_struct(decl),
-            syn::Fields::Unit => Err(syn::Error::new(
-                s.fields.span(),
-                "Unit structs are not digital",
-            )),
-        },
+        Data::Struct(s) => {
+            if let syn::Fields::Unit = s.fields {
+                return Err(syn::Error::new(
+                    s.fields.span(),
+                    "Unit structs are not digital",
+                ));
+            }
+            let layout = parse_struct_layout(&decl.attrs, &s.fields)?;
+            if !layout.is_packed() {
+                return derive_digital_placed_struct(decl, layout);
+            }
+            match s.fields {
+                syn::Fields::Named(_) => derive_digital_named_struct(decl),
+                _ => derive_digital_tuple_struct(decl),
+            }
+        }
         _ => Err(syn::Error::new(decl.span(), "Only structs can be digital")),
     }
 }
 
+// The layout attributes of a struct.  On the struct itself,
+// `#[rhdl(msb_first)]` places the first field at the MSB end, and
+// `#[rhdl(pad = N)]` reserves N bits after the last field.  On a
+// field, `#[rhdl(pad = N)]` reserves N bits between it and the
+// previous field, and `#[rhdl(at = a..b)]` places it at exactly
+// those bits (counting from the LSB).  The fields that follow a
+// placed field are packed against it (above it, or below it under
+// `msb_first`).
+#[derive(Default)]
+struct StructLayout {
+    msb_first: bool,
+    pad: usize,
+    fields: Vec<FieldLayout>,
+}
+
+#[derive(Default)]
+struct FieldLayout {
+    pad: usize,
+    at: Option<(usize, usize)>,
+}
+
+impl StructLayout {
+    fn is_packed(&self) -> bool {
+        !self.msb_first && self.pad == 0 && self.fields.iter().all(|x| x.pad == 0 && x.at.is_none())
+    }
+}
+
+// The arguments of all of the `#[rhdl(...)]` attributes.
+fn rhdl_args(attrs: &[Attribute]) -> syn::Result<Vec<Expr>> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("rhdl"))
+        .map(|attr| attr.parse_args::<Expr>())
+        .collect()
+}
+
+fn parse_usize(expr: &Expr) -> syn::Result<usize> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(value),
+            ..
+        }) => value.base10_parse::<usize>(),
+        _ => Err(syn::Error::new(expr.span(), "Expected an integer literal")),
+    }
+}
+
+fn parse_struct_layout(attrs: &[Attribute], fields: &syn::Fields) -> syn::Result<StructLayout> {
+    let mut layout = StructLayout::default();
+    for arg in rhdl_args(attrs)? {
+        match &arg {
+            Expr::Path(path) if path.path.is_ident("msb_first") => layout.msb_first = true,
+            Expr::Assign(assign) => match &*assign.left {
+                Expr::Path(path) if path.path.is_ident("pad") => {
+                    layout.pad = parse_usize(&assign.right)?
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        arg.span(),
+                        "Unknown struct attribute (expected msb_first or pad = N)",
+                    ))
+                }
+            },
+            _ => {
+                return Err(syn::Error::new(
+                    arg.span(),
+                    "Unknown struct attribute (expected msb_first or pad = N)",
+                ))
+            }
+        }
+    }
+    for field in fields {
+        let mut field_layout = FieldLayout::default();
+        for arg in rhdl_args(&field.attrs)? {
+            let Expr::Assign(assign) = &arg else {
+                return Err(syn::Error::new(
+                    arg.span(),
+                    "Unknown field attribute (expected pad = N or at = a..b)",
+                ));
+            };
+            match &*assign.left {
+                Expr::Path(path) if path.path.is_ident("pad") => {
+                    field_layout.pad = parse_usize(&assign.right)?
+                }
+                Expr::Path(path) if path.path.is_ident("at") => match &*assign.right {
+                    Expr::Range(syn::ExprRange {
+                        start: Some(start),
+                        limits: RangeLimits::HalfOpen(_),
+                        end: Some(end),
+                        ..
+                    }) => {
+                        let (start, end) = (parse_usize(start)?, parse_usize(end)?);
+                        if start >= end {
+                            return Err(syn::Error::new(
+                                assign.right.span(),
+                                "Field placement must be a non-empty range",
+                            ));
+                        }
+                        field_layout.at = Some((start, end));
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            assign.right.span(),
+                            "Expected a range of bits, like at = 4..8",
+                        ))
+                    }
+                },
+                _ => {
+                    return Err(syn::Error::new(
+                        arg.span(),
+                        "Unknown field attribute (expected pad = N or at = a..b)",
+                    ))
+                }
+            }
+            if field_layout.pad != 0 && field_layout.at.is_some() {
+                return Err(syn::Error::new(
+                    arg.span(),
+                    "A field cannot have both pad and at attributes",
+                ));
+            }
+        }
+        layout.fields.push(field_layout);
+    }
+    // Placed fields must be in order, from the LSB up
+    let mut placed = layout
+        .fields
+        .iter()
+        .zip(fields)
+        .filter_map(|(x, f)| Some((x.at?, f)));
+    if let Some((mut previous, _)) = placed.next() {
+        for (at, field) in placed {
+            let in_order = if layout.msb_first {
+                at.1 <= previous.0
+            } else {
+                at.0 >= previous.1
+            };
+            if !in_order {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "Field placements overlap, or are out of order",
+                ));
+            }
+            previous = at;
+        }
+    }
+    Ok(layout)
+}
+
+// A struct with layout attributes has its fields placed by
+// `Kind::make_placed_struct`, and the bits of each field are
+// located with `field_ranges`, so that `bin` and `from_bin` always
+// agree with `static_kind` (and so with `bit_range`).  The ranges
+// are computed once per call, in a single pass over the kind.  They
+// cannot be cached in a static, since a static in a generic impl is
+// shared by every instantiation.
+fn derive_digital_placed_struct(
+    decl: DeriveInput,
+    layout: StructLayout,
+) -> syn::Result<TokenStream> {
+    let struct_name = &decl.ident;
+    let generics = digital_generics(&decl.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let log = log_param(&decl.generics);
+    let Data::Struct(s) = &decl.data else {
+        return Err(syn::Error::new(decl.span(), "Only structs can be digital"));
+    };
+    let members = s
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(ndx, field)| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(ndx);
+                quote!(#index)
+            }
+        })
+        .collect::<Vec<_>>();
+    // The reserved bits are named `_pad0`, `_pad1`, etc. in the kind
+    for ident in s.fields.iter().filter_map(|field| field.ident.as_ref()) {
+        if ident
+            .to_string()
+            .strip_prefix("_pad")
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(syn::Error::new(
+                ident.span(),
+                "Field names like _pad0 are reserved for padding",
+            ));
+        }
+    }
+    let field_types = s.fields.iter().map(|x| &x.ty).collect::<Vec<_>>();
+    let slots = (0..members.len()).map(syn::Index::from).collect::<Vec<_>>();
+    // The pieces of the struct in declaration order, and the order of
+    // the fields from the LSB up
+    let mut pieces = vec![];
+    let mut order = vec![];
+    for (ndx, field_layout) in layout.fields.iter().enumerate() {
+        if field_layout.pad != 0 {
+            let pad = field_layout.pad;
+            pieces.push(quote!(rhdl_core::FieldPlacement::Pad(#pad)));
+        }
+        let member = &members[ndx];
+        let ty = field_types[ndx];
+        let field = quote!(rhdl_core::Kind::make_field(stringify!(#member), <#ty as rhdl_core::Digital>::static_kind()));
+        pieces.push(match field_layout.at {
+            Some((start, end)) => quote!(rhdl_core::FieldPlacement::At(#field, #start..#end)),
+            None => quote!(rhdl_core::FieldPlacement::Packed(#field)),
+        });
+        order.push(ndx);
+    }
+    if layout.pad != 0 {
+        let pad = layout.pad;
+        pieces.push(quote!(rhdl_core::FieldPlacement::Pad(#pad)));
+    }
+    // Under msb_first, the pieces are in declaration order (from the
+    // MSB down), and are placed from the top by the kind
+    let make = if layout.msb_first {
+        order.reverse();
+        quote!(make_placed_struct_msb_first)
+    } else {
+        quote!(make_placed_struct)
+    };
+    let log_members = order.iter().map(|ndx| &members[*ndx]).collect::<Vec<_>>();
+    let log_types = order
+        .iter()
+        .map(|ndx| field_types[*ndx])
+        .collect::<Vec<_>>();
+    Ok(quote! {
+        impl #impl_generics rhdl_core::Digital for #struct_name #ty_generics #where_clause {
+            fn static_kind() -> rhdl_core::Kind {
+                rhdl_core::Kind::#make(vec![
+                    #(
+                        #pieces,
+                    )*
+                ])
+            }
+            fn bin(self) -> Vec<bool> {
+                let kind = Self::static_kind();
+                let ranges = rhdl_core::field_ranges(&kind, &[#(stringify!(#members)),*]);
+                let mut result = vec![false; kind.bits()];
+                #(
+                    result[ranges[#slots].clone()].copy_from_slice(&self.#members.bin());
+                )*
+                result
+            }
+            fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                rhdl_core::check_bin_len::<Self>(bits)?;
+                let ranges = rhdl_core::field_ranges(&Self::static_kind(), &[#(stringify!(#members)),*]);
+                Ok(Self {
+                    #(
+                        #members: <#field_types as rhdl_core::Digital>::from_bin(&bits[ranges[#slots].clone()])?,
+                    )*
+                })
+            }
+            fn allocate<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, builder: impl rhdl_core::LogBuilder) {
+                #(
+                    <#log_types as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(#log_members)));
+                )*
+            }
+            fn record<#log: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
+                #(
+                    self.#log_members.record(tag, &mut logger);
+                )*
+            }
+            fn skip<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
+                #(
+                    <#log_types as rhdl_core::Digital>::skip(tag, &mut logger);
+                )*
+            }
+        }
+    })
+}
+
 fn derive_digital_tuple_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
     let struct_name = &decl.ident;
-    let (impl_generics, ty_generics, where_clause) = decl.generics.split_for_impl();
+    let generics = digital_generics(&decl.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let log = log_param(&decl.generics);
     match decl.data {
         Data::Struct(s) => {
             let fields = s
@@ -46,6 +374,10 @@ fn derive_digital_tuple_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
             let field_types = s.fields.iter().map(|x| &x.ty);
             let field_types2 = field_types.clone();
             let field_types_3 = field_types.clone();
+            let field_takes = s
+                .fields
+                .iter()
+                .map(|_| quote!(rhdl_core::take_bin(&mut bits)?));
             Ok(quote! {
                 impl #impl_generics rhdl_core::Digital for #struct_name #ty_generics #where_clause {
                     fn static_kind() -> rhdl_core::Kind {
@@ -62,17 +394,26 @@ fn derive_digital_tuple_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
                         )*
                         result
                     }
-                    fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
+                    fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                        rhdl_core::check_bin_len::<Self>(bits)?;
+                        let mut bits = bits;
+                        Ok(Self(
+                            #(
+                                #field_takes,
+                            )*
+                        ))
+                    }
+                    fn allocate<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, builder: impl rhdl_core::LogBuilder) {
                         #(
                             <#field_types as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(#fields)));
                         )*
                     }
-                    fn record<L: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+                    fn record<#log: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
                         #(
                             self.#fields2.record(tag, &mut logger);
                         )*
                     }
-                    fn skip<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+                    fn skip<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
                         #(
                             <#field_types2 as rhdl_core::Digital>::skip(tag, &mut logger);
                         )*
@@ -86,7 +427,9 @@ fn derive_digital_tuple_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
 
 fn derive_digital_named_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
     let struct_name = &decl.ident;
-    let (impl_generics, ty_generics, where_clause) = decl.generics.split_for_impl();
+    let generics = digital_generics(&decl.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let log = log_param(&decl.generics);
     match decl.data {
         Data::Struct(s) => {
             let fields = s.fields.iter().map(|field| &field.ident);
@@ -96,6 +439,7 @@ fn derive_digital_named_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
             let field_types = s.fields.iter().map(|x| &x.ty);
             let field_types_2 = field_types.clone();
             let field_types_3 = field_types.clone();
+            let fields_5 = fields.clone();
             Ok(quote! {
                 impl #impl_generics rhdl_core::Digital for #struct_name #ty_generics #where_clause {
                     fn static_kind() -> rhdl_core::Kind {
@@ -112,17 +456,26 @@ fn derive_digital_named_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
                         )*
                         result
                     }
-                    fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
+                    fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                        rhdl_core::check_bin_len::<Self>(bits)?;
+                        let mut bits = bits;
+                        Ok(Self {
+                            #(
+                                #fields_5: rhdl_core::take_bin(&mut bits)?,
+                            )*
+                        })
+                    }
+                    fn allocate<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, builder: impl rhdl_core::LogBuilder) {
                         #(
                             <#field_types as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(#fields)));
                         )*
                     }
-                    fn record<L: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+                    fn record<#log: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
                         #(
                             self.#fields2.record(tag, &mut logger);
                         )*
                     }
-                    fn skip<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+                    fn skip<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
                         #(
                             <#field_types_2 as rhdl_core::Digital>::skip(tag, &mut logger);
                         )*
@@ -165,6 +518,15 @@ mod test {
                     result.extend(self.nest_3.bin());
                     result
                 }
+                fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    rhdl_core::check_bin_len::<Self>(bits)?;
+                    let mut bits = bits;
+                    Ok(Self {
+                        nest_1: rhdl_core::take_bin(&mut bits)?,
+                        nest_2: rhdl_core::take_bin(&mut bits)?,
+                        nest_3: rhdl_core::take_bin(&mut bits)?,
+                    })
+                }
                 fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
                     <bool as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(nest_1)));
                     <u8 as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(nest_2)));
@@ -211,6 +573,15 @@ mod test {
                     result.extend(self.read.bin());
                     result
                 }
+                fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    rhdl_core::check_bin_len::<Self>(bits)?;
+                    let mut bits = bits;
+                    Ok(Self {
+                        input: rhdl_core::take_bin(&mut bits)?,
+                        write: rhdl_core::take_bin(&mut bits)?,
+                        read: rhdl_core::take_bin(&mut bits)?,
+                    })
+                }
                 fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
                     <u32 as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(input)));
                     <bool as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(write)));
@@ -258,6 +629,15 @@ mod test {
                     result.extend(self.read.bin());
                     result
                 }
+                fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    rhdl_core::check_bin_len::<Self>(bits)?;
+                    let mut bits = bits;
+                    Ok(Self {
+                        input: rhdl_core::take_bin(&mut bits)?,
+                        write: rhdl_core::take_bin(&mut bits)?,
+                        read: rhdl_core::take_bin(&mut bits)?,
+                    })
+                }
                 fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
                     <u32 as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(input)));
                     <bool as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(write)));
@@ -300,6 +680,15 @@ mod test {
                     result.extend(self.2.bin());
                     result
                 }
+                fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    rhdl_core::check_bin_len::<Self>(bits)?;
+                    let mut bits = bits;
+                    Ok(Self(
+                        rhdl_core::take_bin(&mut bits)?,
+                        rhdl_core::take_bin(&mut bits)?,
+                        rhdl_core::take_bin(&mut bits)?,
+                    ))
+                }
                 fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
                     <u32 as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(0)));
                     <bool as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(1)));
@@ -319,4 +708,174 @@ mod test {
         };
         assert_tokens_eq(&expected, &output);
     }
+
+    #[test]
+    fn test_digital_with_placed_struct() {
+        let decl = quote!(
+            #[rhdl(msb_first)]
+            pub struct Control {
+                enable: bool,
+                #[rhdl(pad = 3)]
+                mode: u8,
+                #[rhdl(at = 0..4)]
+                irq: b4,
+            }
+        );
+        let output = derive_digital(decl).unwrap();
+        let expected = quote! {
+            impl rhdl_core::Digital for Control {
+                fn static_kind() -> rhdl_core::Kind {
+                    rhdl_core::Kind::make_placed_struct_msb_first(vec![
+                        rhdl_core::FieldPlacement::Packed(rhdl_core::Kind::make_field(stringify!(enable), <bool as rhdl_core::Digital>::static_kind())),
+                        rhdl_core::FieldPlacement::Pad(3usize),
+                        rhdl_core::FieldPlacement::Packed(rhdl_core::Kind::make_field(stringify!(mode), <u8 as rhdl_core::Digital>::static_kind())),
+                        rhdl_core::FieldPlacement::At(rhdl_core::Kind::make_field(stringify!(irq), <b4 as rhdl_core::Digital>::static_kind()), 0usize..4usize),
+                    ])
+                }
+                fn bin(self) -> Vec<bool> {
+                    let kind = Self::static_kind();
+                    let ranges = rhdl_core::field_ranges(&kind, &[stringify!(enable), stringify!(mode), stringify!(irq)]);
+                    let mut result = vec![false; kind.bits()];
+                    result[ranges[0].clone()].copy_from_slice(&self.enable.bin());
+                    result[ranges[1].clone()].copy_from_slice(&self.mode.bin());
+                    result[ranges[2].clone()].copy_from_slice(&self.irq.bin());
+                    result
+                }
+                fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    rhdl_core::check_bin_len::<Self>(bits)?;
+                    let ranges = rhdl_core::field_ranges(&Self::static_kind(), &[stringify!(enable), stringify!(mode), stringify!(irq)]);
+                    Ok(Self {
+                        enable: <bool as rhdl_core::Digital>::from_bin(&bits[ranges[0].clone()])?,
+                        mode: <u8 as rhdl_core::Digital>::from_bin(&bits[ranges[1].clone()])?,
+                        irq: <b4 as rhdl_core::Digital>::from_bin(&bits[ranges[2].clone()])?,
+                    })
+                }
+                fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
+                    <b4 as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(irq)));
+                    <u8 as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(mode)));
+                    <bool as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(enable)));
+                }
+                fn record<L: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+                    self.irq.record(tag, &mut logger);
+                    self.mode.record(tag, &mut logger);
+                    self.enable.record(tag, &mut logger);
+                }
+                fn skip<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+                    <b4 as rhdl_core::Digital>::skip(tag, &mut logger);
+                    <u8 as rhdl_core::Digital>::skip(tag, &mut logger);
+                    <bool as rhdl_core::Digital>::skip(tag, &mut logger);
+                }
+            }
+        };
+        assert_tokens_eq(&expected, &output);
+    }
+
+    #[test]
+    fn test_struct_layout_errors() {
+        let error = |decl| derive_digital(decl).unwrap_err().to_string();
+        assert_eq!(
+            error(quote!(
+                struct A {
+                    #[rhdl(at = 4..8)]
+                    a: b4,
+                    #[rhdl(at = 6..10)]
+                    b: b4,
+                }
+            )),
+            "Field placements overlap, or are out of order"
+        );
+        assert_eq!(
+            error(quote!(
+                struct A {
+                    #[rhdl(pad = x)]
+                    a: b4,
+                }
+            )),
+            "Expected an integer literal"
+        );
+        assert_eq!(
+            error(quote!(
+                struct A {
+                    #[rhdl(pad = 1)]
+                    #[rhdl(at = 4..8)]
+                    a: b4,
+                }
+            )),
+            "A field cannot have both pad and at attributes"
+        );
+        assert_eq!(
+            error(quote!(
+                struct A {
+                    #[rhdl(at = 8..4)]
+                    a: b4,
+                }
+            )),
+            "Field placement must be a non-empty range"
+        );
+        assert_eq!(
+            error(quote!(
+                #[rhdl(lsb_first)]
+                struct A(b4);
+            )),
+            "Unknown struct attribute (expected msb_first or pad = N)"
+        );
+        assert_eq!(
+            error(quote!(
+                struct A {
+                    _pad0: b2,
+                    #[rhdl(at = 4..8)]
+                    a: b4,
+                }
+            )),
+            "Field names like _pad0 are reserved for padding"
+        );
+    }
+
+    #[test]
+    fn test_digital_with_generic_struct() {
+        let decl = quote!(
+            pub struct Tagged<L: Copy, const N: usize>(L, [bool; N]);
+        );
+        let output = derive_digital(decl).unwrap();
+        let expected = quote! {
+            impl<L: Copy, const N: usize> rhdl_core::Digital for Tagged<L, N>
+            where
+                L: rhdl_core::Digital,
+            {
+                fn static_kind() -> rhdl_core::Kind {
+                    rhdl_core::Kind::make_struct(vec![
+                        rhdl_core::Kind::make_field(stringify!(0), <L as rhdl_core::Digital>::static_kind()),
+                        rhdl_core::Kind::make_field(stringify!(1), <[bool; N] as rhdl_core::Digital>::static_kind()),
+                    ])
+                }
+                fn bin(self) -> Vec<bool> {
+                    let mut result = vec![];
+                    result.extend(self.0.bin());
+                    result.extend(self.1.bin());
+                    result
+                }
+                fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    rhdl_core::check_bin_len::<Self>(bits)?;
+                    let mut bits = bits;
+                    Ok(Self(
+                        rhdl_core::take_bin(&mut bits)?,
+                        rhdl_core::take_bin(&mut bits)?,
+                    ))
+                }
+                fn allocate<L_: rhdl_core::Digital>(tag: rhdl_core::TagID<L_>, builder: impl rhdl_core::LogBuilder) {
+                    <L as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(0)));
+                    <[bool; N] as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(1)));
+                }
+                fn record<L_: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<L_>, mut logger: impl rhdl_core::LoggerImpl) {
+                    self.0.record(tag, &mut logger);
+                    self.1.record(tag, &mut logger);
+                }
+                fn skip<L_: rhdl_core::Digital>(tag: rhdl_core::TagID<L_>, mut logger: impl rhdl_core::LoggerImpl) {
+                    <L as rhdl_core::Digital>::skip(tag, &mut logger);
+                    <[bool; N] as rhdl_core::Digital>::skip(tag, &mut logger);
+                }
+            }
+        };
+        assert_tokens_eq(&expected, &output);
+    }
 }
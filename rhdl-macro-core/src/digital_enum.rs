@@ -10,6 +10,8 @@ use syn::Lit;
 use syn::Variant;
 use syn::{Data, DeriveInput};
 
+use crate::digital::{digital_generics, log_param};
+
 // To determine the number of bits needed to represent the discriminant, we
 // need to consider the case where the discriminant is unsigned vs signed.
 // For an unsigned discriminant, we simply find the smallest power of two
@@ -265,6 +267,34 @@ fn variant_payload_bin(
     }
 }
 
+// Generate the expression that rebuilds a variant from the
+// bits of its payload.
+fn variant_from_bin(variant: &Variant) -> TokenStream {
+    let variant_name = &variant.ident;
+    match &variant.fields {
+        syn::Fields::Unit => quote! { Self::#variant_name },
+        syn::Fields::Unnamed(fields) => {
+            let field_takes = fields
+                .unnamed
+                .iter()
+                .map(|_| quote!(rhdl_core::take_bin(&mut bits)?));
+            quote! {
+                Self::#variant_name(#(#field_takes),*)
+            }
+        }
+        syn::Fields::Named(fields) => {
+            let field_names = fields.named.iter().map(|f| &f.ident);
+            quote! {
+                Self::#variant_name {
+                    #(
+                        #field_names: rhdl_core::take_bin(&mut bits)?
+                    ),*
+                }
+            }
+        }
+    }
+}
+
 fn variant_payload_skip(variant: &Variant) -> TokenStream {
     let field_types = variant.fields.iter().map(|f| &f.ty);
     quote! (
@@ -375,7 +405,9 @@ pub const fn clog2(t: u128) -> usize {
 
 pub fn derive_digital_enum(decl: DeriveInput) -> syn::Result<TokenStream> {
     let enum_name = &decl.ident;
-    let (impl_generics, ty_generics, where_clause) = decl.generics.split_for_impl();
+    let generics = digital_generics(&decl.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let log = log_param(&decl.generics);
     let Data::Enum(e) = decl.data else {
         return Err(syn::Error::new(decl.span(), "Only enums can be digital"));
     };
@@ -420,6 +452,14 @@ pub fn derive_digital_enum(decl: DeriveInput) -> syn::Result<TokenStream> {
         .iter()
         .zip(discriminants_values.iter())
         .map(|(variant, discriminant)| variant_payload_bin(variant, width, *discriminant));
+    let from_bin_fns = e.variants.iter().map(variant_from_bin);
+    let discriminants_for_from_bin = discriminants.clone();
+    // Only bind the payload if some variant has one to decode
+    let payload = if e.variants.iter().any(|x| !x.fields.is_empty()) {
+        quote! { mut bits }
+    } else {
+        quote! { _ }
+    };
     Ok(quote! {
         impl #impl_generics rhdl_core::Digital for #enum_name #ty_generics #where_clause {
             fn static_kind() -> rhdl_core::Kind {
@@ -441,21 +481,30 @@ pub fn derive_digital_enum(decl: DeriveInput) -> syn::Result<TokenStream> {
                 })
 
             }
-            fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
+            fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                let (discriminant, #payload) = rhdl_core::split_discriminant(&Self::static_kind(), bits)?;
+                match discriminant {
+                    #(
+                        #discriminants_for_from_bin => Ok(#from_bin_fns),
+                    )*
+                    _ => Err(rhdl_core::FromBinError::InvalidDiscriminant { value: discriminant }),
+                }
+            }
+            fn allocate<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, builder: impl rhdl_core::LogBuilder) {
                 use rhdl_core::LogBuilder;
                 builder.namespace("$disc").allocate(tag, 0);
                 #(
                     #allocate_fns
                 )*
             }
-            fn record<L: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+            fn record<#log: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
                 match self {
                     #(
                         Self::#variants #variant_destructure_args => {#record_fns},
                     )*
                 }
             }
-            fn skip<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+            fn skip<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
                 logger.skip(tag);
                 #(
                     #skip_fns;
@@ -199,20 +199,20 @@ fn test_enum_derive() {
         &quote! {
             impl rhdl_core::Digital for Test {
                 fn static_kind() -> rhdl_core::Kind {
-                    Kind::make_enum(
+                    rhdl_core::Kind::make_enum(
                         vec![
-                            Kind::make_variant(stringify!(A), rhdl_core::Kind::Empty, 1i64),
-                            Kind::make_variant(stringify!(B),
+                            rhdl_core::Kind::make_variant(stringify!(A), rhdl_core::Kind::Empty, 1i64),
+                            rhdl_core::Kind::make_variant(stringify!(B),
                             rhdl_core::Kind::make_tuple(vec![< Bits:: < 16 > as
                             rhdl_core::Digital > ::static_kind()]), 2i64),
-                            Kind::make_variant(stringify!(C),
+                            rhdl_core::Kind::make_variant(stringify!(C),
                             rhdl_core::Kind::make_struct(vec![rhdl_core::Kind::make_field(stringify!(a),
                             < Bits:: < 32 > as rhdl_core::Digital > ::static_kind()),
                             rhdl_core::Kind::make_field(stringify!(b), < Bits:: < 8 > as
                             rhdl_core::Digital > ::static_kind())]), 3i64)
                         ],
                         2usize,
-                        DiscriminantAlignment::Msb,
+                        rhdl_core::DiscriminantAlignment::Msb,
                     )
                 }
                 fn bin(self) -> Vec<bool> {
@@ -236,11 +236,21 @@ fn test_enum_derive() {
                             },
                         )
                 }
+                fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    let (discriminant, mut bits) = rhdl_core::split_discriminant(&Self::static_kind(), bits)?;
+                    match discriminant {
+                        1i64 => Ok(Self::A),
+                        2i64 => Ok(Self::B(rhdl_core::take_bin(&mut bits)?)),
+                        3i64 => Ok(Self::C { a: rhdl_core::take_bin(&mut bits)?, b: rhdl_core::take_bin(&mut bits)? }),
+                        _ => Err(rhdl_core::FromBinError::InvalidDiscriminant { value: discriminant }),
+                    }
+                }
                 fn allocate<L: rhdl_core::Digital>(
                     tag: rhdl_core::TagID<L>,
                     builder: impl rhdl_core::LogBuilder,
                 ) {
-                    builder.namespace("$disc").allocate(tag, 0);
+                    use rhdl_core::LogBuilder;
+                builder.namespace("$disc").allocate(tag, 0);
                     {
                         let mut builder = builder.namespace(stringify!(B));
                         <Bits<
@@ -321,16 +331,16 @@ fn test_enum_no_payloads() {
     let expected = quote! {
         impl rhdl_core::Digital for State {
             fn static_kind() -> rhdl_core::Kind {
-                Kind::make_enum(
+                rhdl_core::Kind::make_enum(
                     vec![
-                        Kind::make_variant(stringify!(Init), rhdl_core::Kind::Empty, 0i64),
-                        Kind::make_variant(stringify!(Boot), rhdl_core::Kind::Empty, 1i64),
-                        Kind::make_variant(stringify!(Running), rhdl_core::Kind::Empty, 2i64),
-                        Kind::make_variant(stringify!(Stop), rhdl_core::Kind::Empty, 3i64),
-                        Kind::make_variant(stringify!(Boom), rhdl_core::Kind::Empty, 4i64)
+                        rhdl_core::Kind::make_variant(stringify!(Init), rhdl_core::Kind::Empty, 0i64),
+                        rhdl_core::Kind::make_variant(stringify!(Boot), rhdl_core::Kind::Empty, 1i64),
+                        rhdl_core::Kind::make_variant(stringify!(Running), rhdl_core::Kind::Empty, 2i64),
+                        rhdl_core::Kind::make_variant(stringify!(Stop), rhdl_core::Kind::Empty, 3i64),
+                        rhdl_core::Kind::make_variant(stringify!(Boom), rhdl_core::Kind::Empty, 4i64)
                     ],
                     3usize,
-                    DiscriminantAlignment::Msb,
+                    rhdl_core::DiscriminantAlignment::Msb,
                 )
             }
             fn bin(self) -> Vec<bool> {
@@ -355,10 +365,22 @@ fn test_enum_no_payloads() {
                         },
                     )
             }
-            fn allocate<L: rhdl_core::Digital>(
+            fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    let (discriminant, _) = rhdl_core::split_discriminant(&Self::static_kind(), bits)?;
+                    match discriminant {
+                        0i64 => Ok(Self::Init),
+                        1i64 => Ok(Self::Boot),
+                        2i64 => Ok(Self::Running),
+                        3i64 => Ok(Self::Stop),
+                        4i64 => Ok(Self::Boom),
+                        _ => Err(rhdl_core::FromBinError::InvalidDiscriminant { value: discriminant }),
+                    }
+                }
+                fn allocate<L: rhdl_core::Digital>(
                 tag: rhdl_core::TagID<L>,
                 builder: impl rhdl_core::LogBuilder
             ) {
+                use rhdl_core::LogBuilder;
                 builder.namespace("$disc").allocate(tag, 0);
             }
             fn record<L: rhdl_core::Digital>(
@@ -408,14 +430,14 @@ fn test_enum_with_signed_discriminants() {
     let expected = quote! {
         impl rhdl_core::Digital for Test {
             fn static_kind() -> rhdl_core::Kind {
-                Kind::make_enum(
+                rhdl_core::Kind::make_enum(
                     vec![
-                        Kind::make_variant(stringify!(A), rhdl_core::Kind::Empty, 1i64),
-                        Kind::make_variant(stringify!(B), rhdl_core::Kind::Empty, 9i64),
-                        Kind::make_variant(stringify!(C), rhdl_core::Kind::Empty, -8i64)
+                        rhdl_core::Kind::make_variant(stringify!(A), rhdl_core::Kind::Empty, 1i64),
+                        rhdl_core::Kind::make_variant(stringify!(B), rhdl_core::Kind::Empty, 9i64),
+                        rhdl_core::Kind::make_variant(stringify!(C), rhdl_core::Kind::Empty, -8i64)
                     ],
                     5usize,
-                    DiscriminantAlignment::Msb,
+                    rhdl_core::DiscriminantAlignment::Msb,
                 )
             }
             fn bin(self) -> Vec<bool> {
@@ -423,21 +445,31 @@ fn test_enum_with_signed_discriminants() {
                     .pad(
                         match self {
                             Self::A => {
-                                rhdl_bits::signed_bits::<5usize>(1i64 as i128).to_bools()
+                                rhdl_bits::signed::<5usize>(1i64 as i128).to_bools()
                             }
                             Self::B => {
-                                rhdl_bits::signed_bits::<5usize>(9i64 as i128).to_bools()
+                                rhdl_bits::signed::<5usize>(9i64 as i128).to_bools()
                             }
                             Self::C => {
-                                rhdl_bits::signed_bits::<5usize>(-8i64 as i128).to_bools()
+                                rhdl_bits::signed::<5usize>(-8i64 as i128).to_bools()
                             }
                         },
                     )
             }
-            fn allocate<L: rhdl_core::Digital>(
+            fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    let (discriminant, _) = rhdl_core::split_discriminant(&Self::static_kind(), bits)?;
+                    match discriminant {
+                        1i64 => Ok(Self::A),
+                        9i64 => Ok(Self::B),
+                        -8i64 => Ok(Self::C),
+                        _ => Err(rhdl_core::FromBinError::InvalidDiscriminant { value: discriminant }),
+                    }
+                }
+                fn allocate<L: rhdl_core::Digital>(
                 tag: rhdl_core::TagID<L>,
                 builder: impl rhdl_core::LogBuilder
             ) {
+                use rhdl_core::LogBuilder;
                 builder.namespace("$disc").allocate(tag, 0);
             }
             fn record<L: rhdl_core::Digital>(
@@ -481,9 +513,9 @@ fn test_enum_with_discriminants() {
     let expected = quote! {
         impl rhdl_core::Digital for Test {
             fn static_kind() -> rhdl_core::Kind {
-                Kind::make_enum(vec![Kind::make_variant(stringify!(A), rhdl_core::Kind::Empty, 1i64),
-                Kind::make_variant(stringify!(B), rhdl_core::Kind::Empty, 6i64),
-                Kind::make_variant(stringify!(C), rhdl_core::Kind::Empty, 8i64)], 4usize, DiscriminantAlignment::Msb)
+                rhdl_core::Kind::make_enum(vec![rhdl_core::Kind::make_variant(stringify!(A), rhdl_core::Kind::Empty, 1i64),
+                rhdl_core::Kind::make_variant(stringify!(B), rhdl_core::Kind::Empty, 6i64),
+                rhdl_core::Kind::make_variant(stringify!(C), rhdl_core::Kind::Empty, 8i64)], 4usize, rhdl_core::DiscriminantAlignment::Msb)
             }
             fn bin(self) -> Vec<bool> {
                 self.kind()
@@ -501,10 +533,20 @@ fn test_enum_with_discriminants() {
                         },
                     )
             }
-            fn allocate<L: rhdl_core::Digital>(
+            fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    let (discriminant, _) = rhdl_core::split_discriminant(&Self::static_kind(), bits)?;
+                    match discriminant {
+                        1i64 => Ok(Self::A),
+                        6i64 => Ok(Self::B),
+                        8i64 => Ok(Self::C),
+                        _ => Err(rhdl_core::FromBinError::InvalidDiscriminant { value: discriminant }),
+                    }
+                }
+                fn allocate<L: rhdl_core::Digital>(
                 tag: rhdl_core::TagID<L>,
                 builder: impl rhdl_core::LogBuilder
             ) {
+                use rhdl_core::LogBuilder;
                 builder.namespace("$disc").allocate(tag, 0);
             }
             fn record<L: rhdl_core::Digital>(
@@ -0,0 +1,267 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::Attribute;
+use syn::Expr;
+use syn::{Data, DeriveInput};
+
+use crate::digital::{digital_generics, log_param};
+
+// An enum marked with `#[rhdl(overlay)]` has no discriminant.  Each
+// variant holds a single value, which is a different view of the same
+// bits, and the kind of the enum is a union of the views (named after
+// the variants).  All of the views are logged, by reinterpreting the
+// bits of the active one.  Since the same bits can be held by any of
+// the views, two overlays are equal when their bits are equal, and
+// the derive provides that `PartialEq` (so it must not be derived too).
+pub(crate) fn is_overlay(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("rhdl")
+            && matches!(attr.parse_args::<Expr>(), Ok(Expr::Path(path)) if path.path.is_ident("overlay"))
+    })
+}
+
+pub fn derive_digital_overlay(decl: DeriveInput) -> syn::Result<TokenStream> {
+    let enum_name = &decl.ident;
+    let generics = digital_generics(&decl.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let log = log_param(&decl.generics);
+    let Data::Enum(e) = &decl.data else {
+        return Err(syn::Error::new(decl.span(), "Only enums can be overlays"));
+    };
+    for attr in decl
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("rhdl"))
+    {
+        if !is_overlay(std::slice::from_ref(attr)) {
+            return Err(syn::Error::new(
+                attr.span(),
+                "Overlays have no discriminant, so only #[rhdl(overlay)] is allowed",
+            ));
+        }
+    }
+    if e.variants.is_empty() {
+        return Err(syn::Error::new(
+            decl.span(),
+            "Overlays must have at least one view",
+        ));
+    }
+    let mut variants = vec![];
+    let mut view_types = vec![];
+    for variant in &e.variants {
+        if let Some((_, discriminant)) = &variant.discriminant {
+            return Err(syn::Error::new(
+                discriminant.span(),
+                "Overlays have no discriminant",
+            ));
+        }
+        match &variant.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                variants.push(&variant.ident);
+                view_types.push(&fields.unnamed[0].ty);
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "Each view of an overlay must hold exactly one value, like View(T)",
+                ))
+            }
+        }
+    }
+    Ok(quote! {
+        impl #impl_generics rhdl_core::Digital for #enum_name #ty_generics #where_clause {
+            fn static_kind() -> rhdl_core::Kind {
+                rhdl_core::Kind::make_union(vec![
+                    #(
+                        rhdl_core::Kind::make_field(stringify!(#variants), <#view_types as rhdl_core::Digital>::static_kind()),
+                    )*
+                ])
+            }
+            fn bin(self) -> Vec<bool> {
+                self.kind().pad(match self {
+                    #(
+                        Self::#variants(view) => view.bin(),
+                    )*
+                })
+            }
+            fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                rhdl_core::check_bin_len::<Self>(bits)?;
+                // Decode the first of the widest views that accepts the bits,
+                // so that no bits are lost
+                let mut error = None;
+                #(
+                    if <#view_types as rhdl_core::Digital>::static_kind().bits() == bits.len() {
+                        match <#view_types as rhdl_core::Digital>::from_bin(bits) {
+                            Ok(view) => return Ok(Self::#variants(view)),
+                            Err(err) => error = Some(err),
+                        }
+                    }
+                )*
+                Err(error.expect("the widest view is as wide as the overlay"))
+            }
+            fn allocate<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, builder: impl rhdl_core::LogBuilder) {
+                #(
+                    <#view_types as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(#variants)));
+                )*
+            }
+            fn record<#log: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
+                // The active view is recorded as is, and the others are
+                // decoded from its bits
+                let bits = self.bin();
+                #(
+                    match self {
+                        Self::#variants(view) => view.record(tag, &mut logger),
+                        _ => match <#view_types as rhdl_core::Digital>::from_bin(&bits[..<#view_types as rhdl_core::Digital>::static_kind().bits()]) {
+                            Ok(view) => view.record(tag, &mut logger),
+                            Err(_) => <#view_types as rhdl_core::Digital>::skip(tag, &mut logger),
+                        },
+                    }
+                )*
+            }
+            fn skip<#log: rhdl_core::Digital>(tag: rhdl_core::TagID<#log>, mut logger: impl rhdl_core::LoggerImpl) {
+                #(
+                    <#view_types as rhdl_core::Digital>::skip(tag, &mut logger);
+                )*
+            }
+        }
+        impl #impl_generics PartialEq for #enum_name #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                rhdl_core::Digital::bin(*self) == rhdl_core::Digital::bin(*other)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::derive_digital;
+    use crate::utils::assert_tokens_eq;
+
+    #[test]
+    fn test_digital_overlay() {
+        let decl = quote!(
+            #[rhdl(overlay)]
+            pub enum Word {
+                Raw(b16),
+                Bytes([b8; 2]),
+                Flag(bool),
+            }
+        );
+        let output = derive_digital(decl).unwrap();
+        let expected = quote! {
+            impl rhdl_core::Digital for Word {
+                fn static_kind() -> rhdl_core::Kind {
+                    rhdl_core::Kind::make_union(vec![
+                        rhdl_core::Kind::make_field(stringify!(Raw), <b16 as rhdl_core::Digital>::static_kind()),
+                        rhdl_core::Kind::make_field(stringify!(Bytes), <[b8; 2] as rhdl_core::Digital>::static_kind()),
+                        rhdl_core::Kind::make_field(stringify!(Flag), <bool as rhdl_core::Digital>::static_kind()),
+                    ])
+                }
+                fn bin(self) -> Vec<bool> {
+                    self.kind().pad(match self {
+                        Self::Raw(view) => view.bin(),
+                        Self::Bytes(view) => view.bin(),
+                        Self::Flag(view) => view.bin(),
+                    })
+                }
+                fn from_bin(bits: &[bool]) -> Result<Self, rhdl_core::FromBinError> {
+                    rhdl_core::check_bin_len::<Self>(bits)?;
+                    let mut error = None;
+                    if <b16 as rhdl_core::Digital>::static_kind().bits() == bits.len() {
+                        match <b16 as rhdl_core::Digital>::from_bin(bits) {
+                            Ok(view) => return Ok(Self::Raw(view)),
+                            Err(err) => error = Some(err),
+                        }
+                    }
+                    if <[b8; 2] as rhdl_core::Digital>::static_kind().bits() == bits.len() {
+                        match <[b8; 2] as rhdl_core::Digital>::from_bin(bits) {
+                            Ok(view) => return Ok(Self::Bytes(view)),
+                            Err(err) => error = Some(err),
+                        }
+                    }
+                    if <bool as rhdl_core::Digital>::static_kind().bits() == bits.len() {
+                        match <bool as rhdl_core::Digital>::from_bin(bits) {
+                            Ok(view) => return Ok(Self::Flag(view)),
+                            Err(err) => error = Some(err),
+                        }
+                    }
+                    Err(error.expect("the widest view is as wide as the overlay"))
+                }
+                fn allocate<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, builder: impl rhdl_core::LogBuilder) {
+                    <b16 as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(Raw)));
+                    <[b8; 2] as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(Bytes)));
+                    <bool as rhdl_core::Digital>::allocate(tag, builder.namespace(stringify!(Flag)));
+                }
+                fn record<L: rhdl_core::Digital>(&self, tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+                    let bits = self.bin();
+                    match self {
+                        Self::Raw(view) => view.record(tag, &mut logger),
+                        _ => match <b16 as rhdl_core::Digital>::from_bin(&bits[..<b16 as rhdl_core::Digital>::static_kind().bits()]) {
+                            Ok(view) => view.record(tag, &mut logger),
+                            Err(_) => <b16 as rhdl_core::Digital>::skip(tag, &mut logger),
+                        },
+                    }
+                    match self {
+                        Self::Bytes(view) => view.record(tag, &mut logger),
+                        _ => match <[b8; 2] as rhdl_core::Digital>::from_bin(&bits[..<[b8; 2] as rhdl_core::Digital>::static_kind().bits()]) {
+                            Ok(view) => view.record(tag, &mut logger),
+                            Err(_) => <[b8; 2] as rhdl_core::Digital>::skip(tag, &mut logger),
+                        },
+                    }
+                    match self {
+                        Self::Flag(view) => view.record(tag, &mut logger),
+                        _ => match <bool as rhdl_core::Digital>::from_bin(&bits[..<bool as rhdl_core::Digital>::static_kind().bits()]) {
+                            Ok(view) => view.record(tag, &mut logger),
+                            Err(_) => <bool as rhdl_core::Digital>::skip(tag, &mut logger),
+                        },
+                    }
+                }
+                fn skip<L: rhdl_core::Digital>(tag: rhdl_core::TagID<L>, mut logger: impl rhdl_core::LoggerImpl) {
+                    <b16 as rhdl_core::Digital>::skip(tag, &mut logger);
+                    <[b8; 2] as rhdl_core::Digital>::skip(tag, &mut logger);
+                    <bool as rhdl_core::Digital>::skip(tag, &mut logger);
+                }
+            }
+            impl PartialEq for Word {
+                fn eq(&self, other: &Self) -> bool {
+                    rhdl_core::Digital::bin(*self) == rhdl_core::Digital::bin(*other)
+                }
+            }
+        };
+        assert_tokens_eq(&expected, &output);
+    }
+
+    #[test]
+    fn test_overlay_errors() {
+        let error = |decl| derive_digital(decl).unwrap_err().to_string();
+        assert_eq!(
+            error(quote!(
+                #[rhdl(overlay)]
+                enum A {
+                    X(b4, b4),
+                }
+            )),
+            "Each view of an overlay must hold exactly one value, like View(T)"
+        );
+        assert_eq!(
+            error(quote!(
+                #[rhdl(overlay)]
+                #[rhdl(discriminant_width = 4)]
+                enum A {
+                    X(b4),
+                }
+            )),
+            "Overlays have no discriminant, so only #[rhdl(overlay)] is allowed"
+        );
+        assert_eq!(
+            error(quote!(
+                union A {
+                    x: b4,
+                }
+            )),
+            "Unions are not digital (use an enum with #[rhdl(overlay)] for overlapping views)"
+        );
+    }
+}
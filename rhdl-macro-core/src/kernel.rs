@@ -162,7 +162,8 @@ fn hdl_field_pat(expr: &syn::FieldPat) -> Result<TS> {
     })
 }
 
-fn hdl_pat_rest(pat: &syn::PatRest) -> Result<TS> {
+#[allow(dead_code)]
+fn hdl_pat_rest(_pat: &syn::PatRest) -> Result<TS> {
     Ok(quote! {
         rhdl_core::ast::PatRest
     })
@@ -2,5 +2,6 @@ mod digital;
 mod utils;
 pub use digital::derive_digital;
 mod digital_enum;
+mod digital_overlay;
 mod kernel;
 pub use kernel::hdl_kernel;
@@ -1,6 +1,9 @@
 use rhdl::basic_logger;
 use rhdl_bits::alias::*;
-use rhdl_core::{path::Path, Digital, Kind, LogBuilder, Logger};
+use rhdl_core::{
+    path::{parse_path, Path},
+    Digital, Kind, LogBuilder, Logger,
+};
 use rhdl_macro::Digital;
 
 #[derive(Copy, Clone, PartialEq, Debug, Digital)]
@@ -14,6 +17,7 @@ enum Packet {
 }
 
 #[derive(Copy, Clone, PartialEq, Debug, Digital)]
+#[allow(dead_code)]
 enum State {
     Init = -2,
     Boot,
@@ -36,19 +40,19 @@ fn test_color_case() {
         b: b8::from(0b11110000),
     };
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Color"), Path::Field("g")])
+        foo.path(&[Path::EnumPayload("Color".into()), Path::Field("g".into())])
             .unwrap()
             .0,
         b8::from(0b11010101).bin()
     );
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Color"), Path::Field("g")])
+        foo.path(&[Path::EnumPayload("Color".into()), Path::Field("g".into())])
             .unwrap()
             .1,
         Kind::make_bits(8)
     );
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Color"), Path::Field("r")])
+        foo.path(&[Path::EnumPayload("Color".into()), Path::Field("r".into())])
             .unwrap()
             .0,
         b8::from(0b10101010).bin()
@@ -66,19 +70,19 @@ fn test_size_case() {
         h: b16::from(0b1101010110101010),
     };
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Size"), Path::Field("w")])
+        foo.path(&[Path::EnumPayload("Size".into()), Path::Field("w".into())])
             .unwrap()
             .0,
         b16::from(0b1010101010101010).bin()
     );
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Size"), Path::Field("w")])
+        foo.path(&[Path::EnumPayload("Size".into()), Path::Field("w".into())])
             .unwrap()
             .1,
         Kind::make_bits(16)
     );
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Size"), Path::Field("h")])
+        foo.path(&[Path::EnumPayload("Size".into()), Path::Field("h".into())])
             .unwrap()
             .0,
         b16::from(0b1101010110101010).bin()
@@ -93,19 +97,19 @@ fn test_size_case() {
 fn test_position_case() {
     let foo = Packet::Position(b4::from(0b1010), b4::from(0b1101));
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Position"), Path::Index(0)])
+        foo.path(&[Path::EnumPayload("Position".into()), Path::Index(0)])
             .unwrap()
             .0,
         b4::from(0b1010).bin()
     );
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Position"), Path::Index(0)])
+        foo.path(&[Path::EnumPayload("Position".into()), Path::Index(0)])
             .unwrap()
             .1,
         Kind::make_bits(4)
     );
     assert_eq!(
-        foo.path(&[Path::EnumPayload("Position"), Path::Index(1)])
+        foo.path(&[Path::EnumPayload("Position".into()), Path::Index(1)])
             .unwrap()
             .0,
         b4::from(0b1101).bin()
@@ -122,7 +126,7 @@ fn test_state_case() {
     assert_eq!(
         packet
             .path(&[
-                Path::EnumPayload("State"),
+                Path::EnumPayload("State".into()),
                 Path::Index(0),
                 Path::EnumDiscriminant
             ])
@@ -138,7 +142,7 @@ fn test_state_case() {
     assert_eq!(
         packet
             .path(&[
-                Path::EnumPayload("State"),
+                Path::EnumPayload("State".into()),
                 Path::Index(0),
                 Path::EnumDiscriminant
             ])
@@ -159,7 +163,7 @@ fn test_nested_struct_case() {
     };
     assert_eq!(
         packet
-            .path(&[Path::EnumPayload("Log"), Path::Field("msg")])
+            .path(&[Path::EnumPayload("Log".into()), Path::Field("msg".into())])
             .unwrap()
             .0,
         b32::from(0xDEAD_BEEF).bin()
@@ -167,9 +171,9 @@ fn test_nested_struct_case() {
     assert_eq!(
         packet
             .path(&[
-                Path::EnumPayload("Log"),
-                Path::Field("level"),
-                Path::Field("active")
+                Path::EnumPayload("Log".into()),
+                Path::Field("level".into()),
+                Path::Field("active".into())
             ])
             .unwrap()
             .0,
@@ -178,9 +182,9 @@ fn test_nested_struct_case() {
     assert_eq!(
         packet
             .path(&[
-                Path::EnumPayload("Log"),
-                Path::Field("level"),
-                Path::Field("level")
+                Path::EnumPayload("Log".into()),
+                Path::Field("level".into()),
+                Path::Field("level".into())
             ])
             .unwrap()
             .0,
@@ -188,6 +192,68 @@ fn test_nested_struct_case() {
     )
 }
 
+#[test]
+fn test_write_nested_field() {
+    let packet = Packet::Log {
+        msg: b32::from(0xDEAD_BEEF),
+        level: LogLevel {
+            level: b8::from(0xBA),
+            active: true,
+        },
+    };
+    let path = parse_path("#Log.level.level").unwrap();
+    assert_eq!(
+        packet.with_path(&path, &b8::from(0x42).bin()).unwrap(),
+        Packet::Log {
+            msg: b32::from(0xDEAD_BEEF),
+            level: LogLevel {
+                level: b8::from(0x42),
+                active: true,
+            },
+        }
+    );
+    // The bits must be as wide as the field
+    assert!(packet.with_path(&path, &b4::from(1).bin()).is_err());
+    // Only the payload of the active variant can be written
+    let error = packet
+        .with_path(&parse_path("#Color.g").unwrap(), &b8::from(1).bin())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Cannot write to the payload of Color at #Color, because the active variant is Log"
+    );
+    // Writing the discriminant changes the variant, and fails if
+    // there is no such variant
+    let state = Packet::State(State::Boot);
+    assert_eq!(
+        state
+            .with_path(
+                &parse_path("#State.0#discriminant").unwrap(),
+                &b3::from(0b010).bin()
+            )
+            .unwrap(),
+        Packet::State(State::Boom)
+    );
+    // Changing the variant zeroes the payload of the old one
+    let position = packet
+        .with_path(
+            &[Path::EnumDiscriminant],
+            &Packet::Position(b4::from(0), b4::from(0))
+                .path(&[Path::EnumDiscriminant])
+                .unwrap()
+                .0,
+        )
+        .unwrap();
+    assert_eq!(position, Packet::Position(b4::from(0), b4::from(0)));
+    assert_eq!(
+        position.typed_bits(),
+        Packet::Position(b4::from(0), b4::from(0)).typed_bits()
+    );
+    assert!(state
+        .with_path(&[Path::EnumDiscriminant], &b5::from(0b00011).bin())
+        .is_err());
+}
+
 #[cfg(feature = "svg")]
 #[test]
 fn test_documentation_svgs() {
@@ -239,3 +305,114 @@ fn test_vcd_generation() {
     let mut vcd_file = std::fs::File::create("packet.vcd").unwrap();
     logger.vcd(&mut vcd_file).unwrap();
 }
+
+#[test]
+fn test_typed_bits_display() {
+    let foo = Packet::Log {
+        msg: 0xCAFE_BEEF.into(),
+        level: LogLevel {
+            level: 0xBA.into(),
+            active: true,
+        },
+    };
+    let typed = foo.typed_bits();
+    assert_eq!(
+        typed.to_string(),
+        "Log { msg: 0xcafebeef, level: { level: 0xba, active: 0x1 } }"
+    );
+    assert_eq!(typed.variant().unwrap().name, "Log");
+    assert_eq!(
+        typed.payload("Log").unwrap().field("level").unwrap(),
+        LogLevel {
+            level: 0xBA.into(),
+            active: true,
+        }
+        .typed_bits()
+    );
+    assert_eq!(typed.to_value::<Packet>().unwrap(), foo);
+    assert_eq!(
+        Packet::State(State::Init).typed_bits().to_string(),
+        "State(Init)"
+    );
+    assert!(
+        Packet::Position(1.into(), 2.into()).typed_bits() < Packet::State(State::Init).typed_bits()
+    );
+}
+
+#[test]
+fn test_packet_layout() {
+    let kind = Packet::static_kind();
+    assert!(kind.validate().is_empty());
+    let layout = kind.layout();
+    println!("{}", layout);
+    let entry = |path: &str| {
+        layout
+            .entries
+            .iter()
+            .find(|x| x.path == path)
+            .unwrap()
+            .range
+            .clone()
+    };
+    assert_eq!(entry("#discriminant"), 41..46);
+    assert_eq!(entry("#Color.g"), 8..16);
+    assert_eq!(entry("#Log.level.active"), 40..41);
+    assert_eq!(entry("#State.0#discriminant"), 0..3);
+    assert_eq!(
+        entry("#Color.g"),
+        rhdl_core::path::bit_range(
+            kind.clone(),
+            &[Path::EnumPayload("Color".into()), Path::Field("g".into())]
+        )
+        .unwrap()
+        .0
+    );
+}
+
+#[test]
+fn test_packet_schema_round_trip() {
+    let schema = rhdl_core::KindSchema::new(Packet::static_kind());
+    let json = schema.to_json();
+    let decoded = rhdl_core::KindSchema::from_json(&json).unwrap();
+    assert_eq!(decoded.version, rhdl_core::SCHEMA_VERSION);
+    assert_eq!(decoded.kind, Packet::static_kind());
+}
+
+#[test]
+fn test_packet_format_changes() {
+    // Adding a variant in an unused discriminant keeps the format
+    #[derive(Copy, Clone, PartialEq, Debug, Digital)]
+    #[repr(i8)]
+    #[allow(dead_code)]
+    enum PacketWithReset {
+        Reset = 0,
+        Color { r: b8, g: b8, b: b8 } = 1,
+        Size { w: b16, h: b16 } = 2,
+        Position(b4, b4) = 4,
+        State(State) = 8,
+        Log { msg: b32, level: LogLevel } = 16,
+    }
+    let diff = Packet::static_kind().diff(&PacketWithReset::static_kind());
+    assert!(diff.is_compatible(), "{}", diff);
+    // Reordering the fields of a variant does not
+    #[derive(Copy, Clone, PartialEq, Debug, Digital)]
+    #[repr(i8)]
+    #[allow(dead_code)]
+    enum PacketReordered {
+        Color { g: b8, r: b8, b: b8 } = 1,
+        Size { w: b16, h: b16 } = 2,
+        Position(b4, b4) = 4,
+        State(State) = 8,
+        Log { msg: b32, level: LogLevel } = 16,
+    }
+    let diff = Packet::static_kind().diff(&PacketReordered::static_kind());
+    assert!(!diff.is_compatible());
+    assert_eq!(
+        diff.to_string(),
+        "\
+#Color.r: moved from [7:0] to [15:8]
+#Color.g: moved from [15:8] to [7:0]
+incompatible
+"
+    );
+}
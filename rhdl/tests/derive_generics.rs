@@ -0,0 +1,6 @@
+#[test]
+fn test_derive_generics() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/generic_*.rs");
+    t.compile_fail("tests/ui/fail_*.rs");
+}
@@ -0,0 +1,12 @@
+use rhdl_core::Digital;
+use rhdl_macro::Digital;
+
+#[derive(Copy, Clone, PartialEq, Debug, Digital)]
+struct Wrapper<T>(T);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct NotDigital;
+
+fn main() {
+    let _ = Wrapper::<NotDigital>::static_kind();
+}
@@ -0,0 +1,34 @@
+use rhdl_bits::alias::*;
+use rhdl_core::{path::Path, Digital};
+use rhdl_macro::Digital;
+
+#[derive(Copy, Clone, PartialEq, Debug, Digital)]
+enum Maybe<T> {
+    Nothing,
+    Just(T),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Digital)]
+#[rhdl(discriminant_align = "lsb")]
+enum Either<L, R: Digital, const N: usize> {
+    Left(L),
+    Right { value: R, history: [R; N] },
+}
+
+fn main() {
+    assert_eq!(Maybe::<b8>::static_kind().bits(), 9);
+    for value in [Maybe::Nothing, Maybe::Just(b8::from(0x5A))] {
+        assert_eq!(Maybe::from_bin(&value.bin()), Ok(value));
+    }
+    let value = Either::<b4, bool, 2>::Right {
+        value: true,
+        history: [false, true],
+    };
+    assert_eq!(Either::<b4, bool, 2>::static_kind().bits(), 5);
+    assert_eq!(value.binary_string(), "01011");
+    assert_eq!(Either::from_bin(&value.bin()), Ok(value));
+    let (bits, _) = value
+        .path(&[Path::EnumPayload("Right".into()), Path::Field("value".into())])
+        .unwrap();
+    assert_eq!(bits, vec![true]);
+}
@@ -0,0 +1,45 @@
+use rhdl_bits::{alias::*, Bits};
+use rhdl_core::{path::Path, Digital, Kind};
+use rhdl_macro::Digital;
+
+#[derive(Copy, Clone, PartialEq, Debug, Digital)]
+struct Fifo<T: Digital, const N: usize> {
+    data: [T; N],
+    count: b4,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Digital)]
+#[rhdl(msb_first)]
+struct Register<T, const W: usize>
+where
+    T: Copy,
+{
+    value: T,
+    #[rhdl(pad = 2)]
+    flags: Bits<W>,
+}
+
+fn main() {
+    let fifo = Fifo::<b8, 3> {
+        data: [b8::from(1), b8::from(2), b8::from(3)],
+        count: b4::from(3),
+    };
+    assert_eq!(Fifo::<b8, 3>::static_kind().bits(), 28);
+    assert_eq!(
+        Fifo::<bool, 2>::static_kind(),
+        Kind::make_struct(vec![
+            Kind::make_field("data", Kind::make_array(Kind::make_bits(1), 2)),
+            Kind::make_field("count", Kind::make_bits(4)),
+        ])
+    );
+    assert_eq!(Fifo::from_bin(&fifo.bin()), Ok(fifo));
+    let (bits, _) = fifo.path(&[Path::Field("data".into()), Path::Index(1)]).unwrap();
+    assert_eq!(bits, b8::from(2).bin());
+
+    let register = Register::<b4, 3> {
+        value: b4::from(0b1001),
+        flags: Bits::<3>::from(0b101),
+    };
+    assert_eq!(register.binary_string(), "100100101");
+    assert_eq!(Register::from_bin(&register.bin()), Ok(register));
+}
@@ -0,0 +1,25 @@
+use rhdl_bits::alias::*;
+use rhdl_core::{Digital, LogBuilder, Logger};
+use rhdl_macro::Digital;
+
+// No bounds are written, so the derive must add them
+#[derive(Copy, Clone, PartialEq, Debug, Digital)]
+struct Pair<A, B>(A, B);
+
+// A parameter named L must not clash with the logging methods
+#[derive(Copy, Clone, PartialEq, Debug, Digital)]
+struct Tagged<L, const N: usize>(L, [bool; N]);
+
+fn main() {
+    let pair = Pair(b3::from(5), (true, b2::from(1)));
+    assert_eq!(Pair::<b3, (bool, b2)>::static_kind().bits(), 6);
+    assert_eq!(pair.binary_string(), "011101");
+    assert_eq!(Pair::from_bin(&pair.bin()), Ok(pair));
+    let tagged = Tagged(b4::from(9), [true, false]);
+    assert_eq!(Tagged::from_bin(&tagged.bin()), Ok(tagged));
+    let mut builder = rhdl::basic_logger::Builder::default();
+    let tag = builder.tag("tagged");
+    let mut logger = builder.build();
+    logger.set_time_in_fs(0);
+    logger.log(tag, tagged);
+}